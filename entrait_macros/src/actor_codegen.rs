@@ -0,0 +1,181 @@
+//! Code generation for the `delegate_by = actor` delegation mode.
+//!
+//! Generates a message enum for the methods of the trait, and a handle type implementing the trait
+//! by sending messages over a channel to a task running the actor loop.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote_spanned};
+
+use crate::analyze_generics::TraitFn;
+use crate::generics::TraitGenerics;
+use crate::idents::CrateIdents;
+use crate::opt::Opts;
+use crate::remote_codegen::{RemoteMethod, RemoteMethodAnalyzer};
+
+pub struct ActorCodegen<'s> {
+    pub opts: &'s Opts,
+    pub crate_idents: &'s CrateIdents,
+    pub handle_visibility: &'s syn::Visibility,
+    pub trait_ident: &'s syn::Ident,
+    pub trait_generics: &'s TraitGenerics,
+    pub handle_ident: &'s syn::Ident,
+    /// Whether the trait has a user-supplied `#[async_trait]` attribute
+    pub has_async_trait_attr: bool,
+}
+
+impl<'s> ActorCodegen<'s> {
+    ///
+    /// Generate code like
+    ///
+    /// ```no_compile
+    /// pub enum FooActorMessage { Method { arg: Arg, reply: oneshot::Sender<Output> } }
+    ///
+    /// #[derive(Clone)]
+    /// pub struct FooActor(mpsc::Sender<FooActorMessage>);
+    ///
+    /// impl FooActor {
+    ///     pub fn channel(buffer: usize) -> (Self, mpsc::Receiver<FooActorMessage>) { .. }
+    ///     pub async fn run<EntraitD: Foo>(receiver: mpsc::Receiver<FooActorMessage>, target: EntraitD) { .. }
+    /// }
+    ///
+    /// impl Foo for FooActor { .. }
+    /// ```
+    ///
+    pub fn gen_actor(
+        &self,
+        trait_fns: &[TraitFn],
+        impl_attrs: &TokenStream,
+    ) -> syn::Result<TokenStream> {
+        let span = self.handle_ident.span();
+        let methods = RemoteMethodAnalyzer {
+            option_name: "delegate_by = actor",
+            transport_error: "entrait::actor::ActorStopped",
+            opts: self.opts,
+            trait_generics: self.trait_generics,
+            has_async_trait_attr: self.has_async_trait_attr,
+            span,
+        }
+        .analyze(trait_fns)?;
+        let message_ident = format_ident!("{}Message", self.handle_ident);
+
+        let message = self.gen_message(&methods, &message_ident, span);
+        let handle = self.gen_handle(&methods, &message_ident, impl_attrs, span);
+
+        Ok(quote_spanned! { span=>
+            #message
+            #handle
+        })
+    }
+
+    fn gen_message(
+        &self,
+        methods: &[RemoteMethod],
+        message_ident: &syn::Ident,
+        span: Span,
+    ) -> TokenStream {
+        let entrait = &self.crate_idents.entrait;
+        let vis = self.handle_visibility;
+        let trait_ident = self.trait_ident;
+
+        let variants = methods.iter().map(|method| {
+            let variant = method.variant_ident();
+            let arg_idents = &method.arg_idents;
+            let arg_types = &method.arg_types;
+            let output_ty = method.output_ty(span);
+
+            quote_spanned! { span=>
+                #variant {
+                    #(#arg_idents: #arg_types,)*
+                    reply: ::#entrait::__actor::oneshot::Sender<#output_ty>,
+                }
+            }
+        });
+        let arms = methods.iter().map(|method| {
+            let variant = method.variant_ident();
+            let fn_ident = &method.trait_fn.sig().ident;
+            let arg_idents = &method.arg_idents;
+
+            quote_spanned! { span=>
+                #message_ident::#variant { #(#arg_idents,)* reply } => {
+                    let _ = reply.send(<EntraitD as #trait_ident>::#fn_ident(target, #(#arg_idents),*).await);
+                }
+            }
+        });
+
+        quote_spanned! { span=>
+            #vis enum #message_ident {
+                #(#variants,)*
+            }
+
+            impl #message_ident {
+                /// Execute the message against `target`, sending the reply back to the caller.
+                pub async fn handle<EntraitD: #trait_ident>(self, target: &EntraitD) {
+                    match self {
+                        #(#arms)*
+                    }
+                }
+            }
+        }
+    }
+
+    fn gen_handle(
+        &self,
+        methods: &[RemoteMethod],
+        message_ident: &syn::Ident,
+        impl_attrs: &TokenStream,
+        span: Span,
+    ) -> TokenStream {
+        let entrait = &self.crate_idents.entrait;
+        let core = &self.crate_idents.core;
+        let vis = self.handle_visibility;
+        let trait_ident = self.trait_ident;
+        let handle_ident = self.handle_ident;
+
+        let items = methods.iter().map(|method| {
+            let sig = method.trait_fn.sig();
+            let attrs = &method.trait_fn.attrs;
+            let variant = method.variant_ident();
+            let arg_idents = &method.arg_idents;
+
+            quote_spanned! { span=>
+                #(#attrs)*
+                #sig {
+                    match ::#entrait::__actor::call(&self.0, |reply| #message_ident::#variant { #(#arg_idents,)* reply }).await {
+                        ::#core::result::Result::Ok(output) => output,
+                        ::#core::result::Result::Err(error) => {
+                            ::#core::result::Result::Err(::#core::convert::From::from(error))
+                        }
+                    }
+                }
+            }
+        });
+
+        quote_spanned! { span=>
+            #[derive(Clone)]
+            #vis struct #handle_ident(pub ::#entrait::__actor::mpsc::Sender<#message_ident>);
+
+            impl #handle_ident {
+                /// Create a handle, along with the receiver to pass to [Self::run].
+                pub fn channel(buffer: usize) -> (Self, ::#entrait::__actor::mpsc::Receiver<#message_ident>) {
+                    let (sender, receiver) = ::#entrait::__actor::mpsc::channel(buffer);
+                    (Self(sender), receiver)
+                }
+
+                /// Execute messages from `receiver` against `target`, one at a time, until all handles are dropped.
+                pub async fn run<EntraitD: #trait_ident>(
+                    mut receiver: ::#entrait::__actor::mpsc::Receiver<#message_ident>,
+                    target: EntraitD,
+                ) {
+                    while let ::#core::option::Option::Some(message) = receiver.recv().await {
+                        message.handle(&target).await;
+                    }
+                }
+            }
+
+            #impl_attrs
+            impl #trait_ident for #handle_ident {
+                #(#items)*
+            }
+        }
+    }
+}
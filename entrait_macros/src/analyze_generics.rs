@@ -77,11 +77,11 @@ pub(super) fn detect_trait_dependency_mode<'t, 'c>(
                     ty.span(),
                     "Using concrete dependencies in a module is an anti-pattern. Instead, write a trait manually, use the #[entrait] attribute on it, and implement it for your application type",
                 )),
-                FnInputMode::ImplBlock(_) => Err(syn::Error::new(
+                FnInputMode::ImplBlock(_) | FnInputMode::ImplBlockApi(_) => Err(syn::Error::new(
                     ty.span(),
                     "Cannot (yet) use concrete dependency in an impl block"
                 )),
-                FnInputMode::RawTrait(_) => panic!("Should not detect dependencies for this input mode")
+                FnInputMode::BlockingVariant | FnInputMode::RawTrait(_) => panic!("Should not detect dependencies for this input mode")
             };
         }
     }
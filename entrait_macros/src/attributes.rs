@@ -3,10 +3,10 @@ use crate::generics::{self, TraitIndirection};
 use crate::idents::CrateIdents;
 use crate::input::FnInputMode;
 use crate::opt::{AsyncStrategy, MockApiIdent, Opts, SpanOpt};
-use crate::token_util::{comma_sep, push_tokens};
+use crate::token_util::{comma_sep, push_tokens, TokenPair};
 
 use proc_macro2::{Span, TokenStream};
-use quote::ToTokens;
+use quote::{format_ident, ToTokens};
 
 pub struct Attr<P>(pub P);
 
@@ -52,6 +52,26 @@ impl<'a, P: ToTokens + IsEmpty> ToTokens for ExportGatedAttr<'a, P> {
     }
 }
 
+/// Gates an item with `#[cfg(test)]`, unless mocks are exported.
+pub struct ExportGate<'a> {
+    pub opts: &'a Opts,
+}
+
+impl<'a> ToTokens for ExportGate<'a> {
+    fn to_tokens(&self, stream: &mut TokenStream) {
+        if self.opts.export_value() {
+            return;
+        }
+        push_tokens!(stream, syn::token::Pound::default());
+        syn::token::Bracket::default().surround(stream, |stream| {
+            push_tokens!(stream, syn::Ident::new("cfg", Span::call_site()));
+            syn::token::Paren::default().surround(stream, |stream| {
+                push_tokens!(stream, syn::Ident::new("test", Span::call_site()));
+            });
+        });
+    }
+}
+
 pub struct EntraitForTraitParams<'a> {
     pub crate_idents: &'a CrateIdents,
 }
@@ -141,7 +161,10 @@ impl<'s> ToTokens for UnimockAttrParams<'s> {
                     push_tokens!(stream, Ident::new("api", span), Eq(span));
 
                     // flatten=[TraitMock] for single-fn entraits
-                    if matches!(self.fn_input_mode, FnInputMode::SingleFn(_)) {
+                    if matches!(
+                        self.fn_input_mode,
+                        FnInputMode::SingleFn(_) | FnInputMode::BlockingVariant
+                    ) {
                         Bracket(span).surround(stream, |stream| push_tokens!(stream, mock_api.0));
                     } else {
                         push_tokens!(stream, mock_api.0);
@@ -149,7 +172,11 @@ impl<'s> ToTokens for UnimockAttrParams<'s> {
                 });
             }
 
-            if !matches!(self.fn_input_mode, FnInputMode::RawTrait(_)) {
+            // A blocking variant has no function to unmock into
+            if !matches!(
+                self.fn_input_mode,
+                FnInputMode::RawTrait(_) | FnInputMode::BlockingVariant
+            ) {
                 // unmock_with=[...]
                 if !self.trait_fns.is_empty() {
                     punctuator.push_fn(|stream| {
@@ -170,17 +197,27 @@ impl<'s> UnimockAttrParams<'s> {
 
         push_tokens!(stream, Ident::new("unmock_with", span), Eq(span));
 
+        // The functions of an impl block are called through its type
+        let opt_self_path = match self.fn_input_mode {
+            FnInputMode::ImplBlockApi(self_path) => Some(TokenPair(self_path, PathSep(span))),
+            _ => None,
+        };
+
         Bracket(span).surround(stream, |stream| {
             let mut punctuator = comma_sep(stream, span);
 
             for trait_fn in self.trait_fns {
-                let fn_ident = &trait_fn.sig().ident;
+                let fn_ident = TokenPair(&opt_self_path, &trait_fn.sig().ident);
 
                 match &trait_fn.deps {
-                    generics::FnDeps::Generic { .. } => {
-                        punctuator.push(fn_ident);
+                    // The function returns the unboxed iterator or stream
+                    _ if trait_fn.entrait_sig.boxed_return.is_some() => {
+                        punctuator.push(Underscore(span));
+                    }
+                    deps @ generics::FnDeps::Generic { .. } if !deps.is_scope_context() => {
+                        punctuator.push(&fn_ident);
                     }
-                    generics::FnDeps::Concrete(_) => {
+                    generics::FnDeps::Generic { .. } | generics::FnDeps::Concrete(_) => {
                         punctuator.push(Underscore(span));
                     }
                     generics::FnDeps::NoDeps { .. } => {
@@ -229,6 +266,47 @@ impl ToTokens for MockallAutomockParams {
     }
 }
 
+/// The name of the mock struct of a mockall-mocked trait: the `mock_api` alias, or mockall's own `Mock{Trait}`.
+pub fn mockall_mock_ident(opts: &Opts, trait_ident: &syn::Ident) -> Option<syn::Ident> {
+    match opts.default_option(opts.mockall, false) {
+        SpanOpt(true, _) => Some(match &opts.mock_api {
+            Some(mock_api) => mock_api.0.clone(),
+            None => automock_ident(trait_ident),
+        }),
+        SpanOpt(false, _) => None,
+    }
+}
+
+fn automock_ident(trait_ident: &syn::Ident) -> syn::Ident {
+    format_ident!("Mock{}", trait_ident)
+}
+
+/// Re-export of the mockall mock under the `mock_api` name.
+pub struct MockallAlias<'a> {
+    pub visibility: &'a dyn ToTokens,
+    pub trait_ident: &'a syn::Ident,
+    pub mock_api: &'a MockApiIdent,
+    pub opts: &'a Opts,
+}
+
+impl<'a> ToTokens for MockallAlias<'a> {
+    fn to_tokens(&self, stream: &mut TokenStream) {
+        let span = self.mock_api.0.span();
+        push_tokens!(
+            stream,
+            ExportGate { opts: self.opts },
+            self.visibility,
+            syn::token::Use(span),
+            syn::token::SelfValue(span),
+            syn::token::PathSep(span),
+            automock_ident(self.trait_ident),
+            syn::token::As(span),
+            self.mock_api.0,
+            syn::token::Semi(span)
+        );
+    }
+}
+
 pub fn opt_async_trait_attr<'s, 'o>(
     opts: &'s Opts,
     crate_idents: &'s CrateIdents,
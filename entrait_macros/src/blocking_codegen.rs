@@ -0,0 +1,181 @@
+//! Code generation for the `blocking_variant` option.
+//!
+//! Generates a synchronous trait with the method of an async entraited function,
+//! implemented for `Impl<T>` by blocking on the async method using the `Executor` of the application.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote_spanned, ToTokens};
+
+use crate::analyze_generics::TraitFn;
+use crate::generics::{TraitDependencyMode, TraitGenerics, TraitIndirection};
+use crate::idents::{CrateIdents, GenericIdents};
+use crate::input::FnInputMode;
+use crate::opt::{MockApiIdent, Opts};
+use crate::signature::EntraitSignature;
+use crate::trait_codegen::{Supertraits, TraitCodegen};
+use crate::wrapper_codegen::{FeatureGated, ImplWrapper};
+
+pub struct BlockingVariantCodegen<'s> {
+    pub opts: &'s Opts,
+    pub crate_idents: &'s CrateIdents,
+    pub trait_visibility: &'s syn::Visibility,
+    pub trait_ident: &'s syn::Ident,
+    pub blocking_ident: &'s syn::Ident,
+    pub trait_generics: &'s TraitGenerics,
+}
+
+impl<'s> BlockingVariantCodegen<'s> {
+    ///
+    /// Generate code like
+    ///
+    /// ```no_compile
+    /// pub trait FooBlocking {
+    ///     fn foo(&self, arg: Arg) -> Output;
+    /// }
+    ///
+    /// impl<EntraitT> FooBlocking for ::entrait::Impl<EntraitT>
+    /// where
+    ///     Self: Foo + ::entrait::runtime::HasExecutor,
+    /// {
+    ///     fn foo(&self, arg: Arg) -> Output {
+    ///         ::entrait::runtime::block_on(self.entrait_executor(), <Self as Foo>::foo(self, arg))
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// When the function is mockable, the same is implemented for the wrappers around `Impl<T>`,
+    /// like `::entrait::overrides::Overrides<T>`, which then implement `Foo` as well.
+    ///
+    pub fn gen_blocking_variant(&self, trait_fn: &TraitFn) -> syn::Result<TokenStream> {
+        let span = self.blocking_ident.span();
+
+        if !trait_fn.originally_async {
+            return Err(syn::Error::new(
+                span,
+                "blocking_variant requires an async function",
+            ));
+        }
+        if !self.trait_generics.params.is_empty() {
+            return Err(syn::Error::new(
+                span,
+                "blocking_variant does not support generic traits",
+            ));
+        }
+
+        let mut sig = trait_fn.sig().clone();
+        sig.asyncness = None;
+        let blocking_fns = [TraitFn {
+            deps: trait_fn.deps.clone(),
+            attrs: trait_fn.attrs.clone(),
+            entrait_sig: EntraitSignature::new(sig.clone()),
+            originally_async: false,
+        }];
+
+        // The mock API of the blocking trait is named after it, like `FooBlockingMock`
+        let blocking_opts = Opts {
+            default_span: self.opts.default_span,
+            no_deps: self.opts.no_deps,
+            debug: self.opts.debug,
+            async_strategy: self.opts.async_strategy,
+            export: self.opts.export,
+            mock_api: self
+                .opts
+                .mock_api
+                .as_ref()
+                .map(|_| MockApiIdent(format_ident!("{}Mock", self.blocking_ident))),
+            unimock: self.opts.unimock,
+            mockall: self.opts.mockall,
+        };
+
+        let trait_def = TraitCodegen {
+            opts: &blocking_opts,
+            crate_idents: self.crate_idents,
+            trait_indirection: TraitIndirection::Plain,
+            trait_dependency_mode: &TraitDependencyMode::Generic(GenericIdents::new(
+                self.crate_idents,
+                span,
+            )),
+        }
+        .gen_trait_def(
+            self.trait_visibility,
+            self.blocking_ident,
+            self.trait_generics,
+            &Supertraits::None,
+            &blocking_fns,
+            &FnInputMode::BlockingVariant,
+        )?;
+
+        let entrait = &self.crate_idents.entrait;
+        let generic_idents = GenericIdents::new(self.crate_idents, span);
+        let impl_t = &generic_idents.impl_t;
+        let impl_block = self.gen_impl_block(
+            trait_fn,
+            &sig,
+            quote_spanned! { span=> <#impl_t> },
+            quote_spanned! { span=> ::#entrait::Impl<#impl_t> },
+        )?;
+
+        let mut wrapper_impl_blocks = TokenStream::new();
+        if self.opts.mockable().yes() {
+            for wrapper in ImplWrapper::ALL {
+                let impl_params = if wrapper.has_scope_c() {
+                    let scope_c = &generic_idents.scope_c;
+                    quote_spanned! { span=> <#impl_t, #scope_c> }
+                } else {
+                    quote_spanned! { span=> <#impl_t> }
+                };
+
+                FeatureGated {
+                    wrapper,
+                    crate_idents: self.crate_idents,
+                    span,
+                    tokens: self.gen_impl_block(
+                        trait_fn,
+                        &sig,
+                        impl_params,
+                        wrapper.path(&generic_idents, span).into_token_stream(),
+                    )?,
+                }
+                .to_tokens(&mut wrapper_impl_blocks);
+            }
+        }
+
+        // The `runtime` module of entrait requires the `alloc` feature
+        Ok(quote_spanned! { span=>
+            ::#entrait::__runtime! {
+                #trait_def
+                #impl_block
+                #wrapper_impl_blocks
+            }
+        })
+    }
+
+    fn gen_impl_block(
+        &self,
+        trait_fn: &TraitFn,
+        sig: &syn::Signature,
+        impl_params: TokenStream,
+        self_ty: TokenStream,
+    ) -> syn::Result<TokenStream> {
+        let span = self.blocking_ident.span();
+        let entrait = &self.crate_idents.entrait;
+        let trait_ident = self.trait_ident;
+        let blocking_ident = self.blocking_ident;
+        let fn_ident = &sig.ident;
+        let arguments = trait_fn.entrait_sig.arg_idents()?;
+
+        Ok(quote_spanned! { span=>
+            impl #impl_params #blocking_ident for #self_ty
+            where
+                Self: #trait_ident + ::#entrait::runtime::HasExecutor,
+            {
+                #sig {
+                    ::#entrait::runtime::block_on(
+                        ::#entrait::runtime::HasExecutor::entrait_executor(self),
+                        <Self as #trait_ident>::#fn_ident(self, #(#arguments),*),
+                    )
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,199 @@
+//! Code generation for the `check_deps` option.
+//!
+//! Entrait doesn't know the methods of the traits bounding the dependency parameter,
+//! so the check relies on the naming convention of entraited functions:
+//! A bound `FetchUser` is used when the body calls `deps.fetch_user(..)`.
+//!
+//! To avoid false positives, the check is inconclusive, and reports nothing, when the body
+//! uses the dependency parameter for anything else than calling methods following this convention,
+//! e.g. passing it on to another function or calling a method of a module trait.
+
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+use quote::quote_spanned;
+use syn::spanned::Spanned;
+
+use crate::analyze_generics::TraitFn;
+use crate::generics::FnDeps;
+use crate::input::InputFn;
+use crate::opt::{CheckDeps, SpanOpt};
+
+/// Traits without methods, or with methods not meant to be called on dependencies
+const IGNORED_BOUNDS: &[&str] = &[
+    "Any",
+    "Copy",
+    "RefUnwindSafe",
+    "Send",
+    "Sized",
+    "Sync",
+    "Unpin",
+    "UnwindSafe",
+];
+
+/// Check the body of `input_fn` for bounds of its dependency parameter that are never used.
+///
+/// In `Warn` mode, each unused bound generates a use of a deprecated item, spanned at the bound,
+/// which makes rustc emit a warning that can be silenced with `#[allow(deprecated)]`.
+pub fn gen_unused_deps_check(
+    check_deps: Option<&SpanOpt<CheckDeps>>,
+    trait_fn: &TraitFn,
+    input_fn: &InputFn,
+) -> syn::Result<TokenStream> {
+    let mut stream = TokenStream::new();
+    let check_deps = match check_deps {
+        Some(SpanOpt(check_deps, _)) => *check_deps,
+        None => return Ok(stream),
+    };
+    let trait_bounds = match &trait_fn.deps {
+        FnDeps::Generic { trait_bounds, .. } => trait_bounds,
+        FnDeps::Concrete(_) | FnDeps::NoDeps => return Ok(stream),
+    };
+    let called_methods = match input_fn.fn_sig.inputs.first() {
+        Some(syn::FnArg::Typed(pat_type)) => match pat_type.pat.as_ref() {
+            syn::Pat::Ident(pat_ident) => {
+                match called_methods(&pat_ident.ident, input_fn.fn_body.clone()) {
+                    Some(called_methods) => called_methods,
+                    None => return Ok(stream),
+                }
+            }
+            syn::Pat::Wild(_) => vec![],
+            _ => return Ok(stream),
+        },
+        _ => return Ok(stream),
+    };
+
+    let checked_bounds = trait_bounds
+        .iter()
+        .filter_map(|bound| match bound {
+            syn::TypeParamBound::Trait(trait_bound)
+                if matches!(trait_bound.modifier, syn::TraitBoundModifier::None) =>
+            {
+                let trait_ident = &trait_bound.path.segments.last()?.ident;
+                let trait_name = trait_ident.to_string();
+                if IGNORED_BOUNDS.contains(&trait_name.as_str()) {
+                    None
+                } else {
+                    Some((trait_name, bound.span()))
+                }
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let all_calls_attributed = called_methods.iter().all(|method| {
+        checked_bounds
+            .iter()
+            .any(|(trait_name, _)| follows_convention(trait_name, method))
+    });
+    if !all_calls_attributed {
+        return Ok(stream);
+    }
+
+    let mut errors: Option<syn::Error> = None;
+
+    for (trait_name, span) in checked_bounds {
+        if called_methods
+            .iter()
+            .any(|method| follows_convention(&trait_name, method))
+        {
+            continue;
+        }
+
+        let message = format!(
+            "the `{trait_name}` bound of the dependency parameter is never used by `{}`",
+            input_fn.fn_sig.ident
+        );
+
+        match check_deps {
+            CheckDeps::Off => {}
+            CheckDeps::Warn => stream.extend(gen_warning(&message, span)),
+            CheckDeps::Strict => {
+                let error = syn::Error::new(span, message);
+                match &mut errors {
+                    Some(errors) => errors.combine(error),
+                    None => errors = Some(error),
+                }
+            }
+        }
+    }
+
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(stream),
+    }
+}
+
+fn gen_warning(message: &str, span: Span) -> TokenStream {
+    let note = syn::LitStr::new(message, span);
+
+    quote_spanned! { span=>
+        const _: () = {
+            #[deprecated(note = #note)]
+            struct UnusedDependency;
+            let _ = UnusedDependency;
+        };
+    }
+}
+
+/// Whether `method` is the method of an entraited function with the trait `trait_name`,
+/// e.g. `fetch_user` for `FetchUser`.
+fn follows_convention(trait_name: &str, method: &str) -> bool {
+    let method = method.trim_start_matches("r#").replace('_', "");
+    let trait_name = trait_name.trim_start_matches("r#");
+
+    trait_name.eq_ignore_ascii_case(&method)
+}
+
+/// The names of methods called on `deps_ident` in `body`,
+/// or `None` if it is used in any other way.
+fn called_methods(deps_ident: &syn::Ident, body: TokenStream) -> Option<Vec<String>> {
+    let mut methods = vec![];
+    collect_called_methods(deps_ident, body, &mut methods)?;
+    Some(methods)
+}
+
+fn collect_called_methods(
+    deps_ident: &syn::Ident,
+    tokens: TokenStream,
+    methods: &mut Vec<String>,
+) -> Option<()> {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+
+    for (index, token) in tokens.iter().enumerate() {
+        match token {
+            TokenTree::Group(group) => {
+                collect_called_methods(deps_ident, group.stream(), methods)?;
+            }
+            TokenTree::Ident(ident) if ident == deps_ident => {
+                // Field access or path segment with the same name, like `self.deps` or `ctx::deps`
+                if index > 0 && is_punct(&tokens[index - 1], &['.', ':']) {
+                    continue;
+                }
+
+                match (tokens.get(index + 1), tokens.get(index + 2)) {
+                    (Some(TokenTree::Punct(dot)), Some(TokenTree::Ident(method)))
+                        if dot.as_char() == '.' && is_call(&tokens[index + 3..]) =>
+                    {
+                        methods.push(method.to_string());
+                    }
+                    _ => return None,
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(())
+}
+
+/// Whether the tokens following a method name are call arguments, possibly with a turbofish.
+fn is_call(tokens: &[TokenTree]) -> bool {
+    match tokens.first() {
+        Some(TokenTree::Group(group)) => group.delimiter() == Delimiter::Parenthesis,
+        Some(token) => is_punct(token, &[':']),
+        None => false,
+    }
+}
+
+fn is_punct(token: &TokenTree, chars: &[char]) -> bool {
+    matches!(token, TokenTree::Punct(punct) if chars.contains(&punct.as_char()))
+}
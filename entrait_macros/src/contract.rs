@@ -0,0 +1,280 @@
+//! The `contract` attribute, running the same tests against several delegation targets.
+//!
+//! The `#[test]` functions of the module take the dependency as their only parameter, and are left generic.
+//! For each target, a module with a concrete test per function is generated,
+//! passing an `Impl` of an application that delegates to the target through the `Target` type of the delegation trait.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+
+use crate::idents::{snake_case, CrateIdents};
+
+pub struct ContractAttr {
+    /// The delegation trait, like `DelegateRepository`
+    delegate_by: syn::Path,
+    targets: Vec<ContractTarget>,
+    span: Span,
+}
+
+/// A target type, with an optional expression creating it
+struct ContractTarget {
+    ty: syn::Path,
+    init: Option<syn::Expr>,
+}
+
+impl Parse for ContractAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        let trait_path: syn::Path = input.parse()?;
+
+        let mut delegate_by = None;
+        let mut targets = None;
+
+        while !input.is_empty() {
+            let _: syn::token::Comma = input.parse()?;
+            if input.is_empty() {
+                break;
+            }
+
+            let ident: syn::Ident = input.parse()?;
+            match ident.to_string().as_str() {
+                "delegate_by" => {
+                    let _: syn::token::Eq = input.parse()?;
+                    delegate_by = Some(input.parse()?);
+                }
+                "targets" => {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    let mut parsed = vec![];
+                    while !content.is_empty() {
+                        let ty: syn::Path = content.parse()?;
+                        let init = if content.peek(syn::token::Eq) {
+                            let _: syn::token::Eq = content.parse()?;
+                            Some(content.parse()?)
+                        } else {
+                            None
+                        };
+                        parsed.push(ContractTarget { ty, init });
+
+                        if content.is_empty() {
+                            break;
+                        }
+                        let _: syn::token::Comma = content.parse()?;
+                    }
+                    targets = Some(parsed);
+                }
+                other => {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        format!("Unknown contract option \"{other}\""),
+                    ))
+                }
+            }
+        }
+
+        // Named after the convention of the docs, e.g. `DelegateRepository`
+        let delegate_by = delegate_by.unwrap_or_else(|| {
+            let mut path = trait_path.clone();
+            if let Some(last) = path.segments.last_mut() {
+                last.ident = format_ident!("Delegate{}", last.ident);
+            }
+            path
+        });
+
+        match targets {
+            Some(targets) if !targets.is_empty() => Ok(Self {
+                delegate_by,
+                targets,
+                span,
+            }),
+            _ => Err(syn::Error::new(
+                span,
+                "contract requires at least one target, like `targets(MyRepository)`",
+            )),
+        }
+    }
+}
+
+/// A `#[test]` function of the contract
+struct ContractTest {
+    attrs: Vec<syn::Attribute>,
+    ident: syn::Ident,
+    is_async: bool,
+    by_ref: bool,
+}
+
+pub fn output_tokens(attr: ContractAttr, item_mod: syn::ItemMod) -> syn::Result<TokenStream> {
+    let syn::ItemMod {
+        attrs: mod_attrs,
+        vis,
+        ident: mod_ident,
+        content,
+        ..
+    } = item_mod;
+
+    let mut items = match content {
+        Some((_, items)) => items,
+        None => {
+            return Err(syn::Error::new(
+                mod_ident.span(),
+                "contract requires an inline module",
+            ))
+        }
+    };
+
+    let mut tests = vec![];
+    for item in &mut items {
+        if let syn::Item::Fn(item_fn) = item {
+            let (test_attrs, other_attrs) =
+                item_fn.attrs.drain(..).partition::<Vec<_>, _>(is_test_attr);
+            if test_attrs.is_empty() {
+                item_fn.attrs = other_attrs;
+                continue;
+            }
+            // Attributes like `#[should_panic]` belong to the generated tests
+            let attrs = test_attrs.into_iter().chain(other_attrs).collect();
+
+            let mut inputs = item_fn.sig.inputs.iter();
+            let by_ref = match (inputs.next(), inputs.next()) {
+                (Some(syn::FnArg::Typed(pat_type)), None) => {
+                    matches!(pat_type.ty.as_ref(), syn::Type::Reference(_))
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        item_fn.sig.ident.span(),
+                        "contract tests must take the dependency as their only parameter",
+                    ))
+                }
+            };
+
+            tests.push(ContractTest {
+                attrs,
+                ident: item_fn.sig.ident.clone(),
+                is_async: item_fn.sig.asyncness.is_some(),
+                by_ref,
+            });
+        }
+    }
+
+    if tests.is_empty() {
+        return Err(syn::Error::new(
+            mod_ident.span(),
+            "contract requires `#[test]` functions",
+        ));
+    }
+
+    let crate_idents = CrateIdents::new(attr.span);
+    let entrait = &crate_idents.entrait;
+    let core = &crate_idents.core;
+    let delegate_by = &attr.delegate_by;
+
+    let mut target_mods: Vec<syn::Ident> = vec![];
+    for target in &attr.targets {
+        let ty = &target.ty;
+        let target_ident = &ty
+            .segments
+            .last()
+            .ok_or_else(|| syn::Error::new(ty.span(), "Expected a type"))?
+            .ident;
+        let target_mod = snake_case(target_ident);
+        // Targets like `a::Repo` and `b::Repo` would generate the same module
+        if target_mods.contains(&target_mod) {
+            return Err(syn::Error::new(
+                ty.span(),
+                format!("the tests of each contract target are generated in a module named after the type, and `{target_mod}` is used for another target already. Import one of the types under another name in the contract module, like `use ... as Other{target_ident};`"),
+            ));
+        }
+        target_mods.push(target_mod);
+    }
+
+    let target_items = attr
+        .targets
+        .iter()
+        .zip(&target_mods)
+        .map(|(target, target_mod)| {
+            let ty = &target.ty;
+            let init = match &target.init {
+                Some(init) => quote! { #init },
+                None => quote! { ::#core::default::Default::default() },
+            };
+
+            let test_fns = tests.iter().map(|test| {
+                let attrs = &test.attrs;
+                let ident = &test.ident;
+                let opt_ref = if test.by_ref {
+                    Some(quote! { & })
+                } else {
+                    None
+                };
+
+                if test.is_async {
+                    quote! {
+                        #(#attrs)*
+                        async fn #ident() {
+                            super::#ident(#opt_ref contract_app()).await
+                        }
+                    }
+                } else {
+                    quote! {
+                        #(#attrs)*
+                        fn #ident() {
+                            super::#ident(#opt_ref contract_app())
+                        }
+                    }
+                }
+            });
+
+            quote! {
+                impl #delegate_by<ContractApp<#ty>> for ContractApp<#ty> {
+                    type Target = #ty;
+                }
+
+                mod #target_mod {
+                    use super::*;
+
+                    fn contract_app() -> ::#entrait::Impl<super::ContractApp<#ty>> {
+                        ::#entrait::Impl::new(super::ContractApp(#init))
+                    }
+
+                    #(#test_fns)*
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(quote! {
+        #(#mod_attrs)*
+        #vis mod #mod_ident {
+            #(#items)*
+
+            /// The application of the contract tests, delegating to the target `X`.
+            struct ContractApp<X>(X);
+
+            impl<X> ::#core::convert::AsRef<X> for ContractApp<X> {
+                fn as_ref(&self) -> &X {
+                    &self.0
+                }
+            }
+
+            #(#target_items)*
+        }
+    })
+}
+
+/// Whether this is `#[test]`, `#[tokio::test]`, or the test attribute by its path in `core` or `std`
+fn is_test_attr(attr: &syn::Attribute) -> bool {
+    let idents = attr
+        .path()
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>();
+    let idents = idents.iter().map(String::as_str).collect::<Vec<_>>();
+
+    matches!(
+        idents[..],
+        ["test"] | ["tokio", "test"] | ["core" | "std", "prelude", _, "test"]
+    )
+}
@@ -9,6 +9,27 @@ pub struct EntraitFnAttr {
     pub trait_ident: syn::Ident,
     pub opts: Opts,
 
+    /// Name of the generated tower `Service` type
+    pub tower_service: Option<SpanOpt<syn::Ident>>,
+
+    /// Name of the generated synchronous trait
+    pub blocking_variant: Option<SpanOpt<syn::Ident>>,
+
+    /// Caching of the delegation from `Impl<T>`
+    pub cache: Option<CacheOpt>,
+
+    /// Retrying of the delegation from `Impl<T>`
+    pub retry: Option<SpanOpt<RetryOpt>>,
+
+    /// Timeout of the delegation from `Impl<T>`, in milliseconds
+    pub timeout_ms: Option<SpanOpt<syn::LitInt>>,
+
+    /// Metrics instrumentation of the delegation from `Impl<T>`
+    pub metrics: Option<SpanOpt<bool>>,
+
+    /// Checking of the function bodies for unused dependency bounds
+    pub check_deps: Option<SpanOpt<CheckDeps>>,
+
     pub crate_idents: CrateIdents,
 }
 
@@ -26,6 +47,14 @@ impl Parse for EntraitFnAttr {
         let mut mock_api = None;
         let mut unimock = None;
         let mut mockall = None;
+        let mut tower_service = None;
+        let mut blocking_variant = None;
+        let mut cache = None;
+        let mut cache_ttl_secs = None;
+        let mut retry = None;
+        let mut timeout_ms = None;
+        let mut metrics = None;
+        let mut check_deps = None;
 
         while input.peek(syn::token::Comma) {
             input.parse::<syn::token::Comma>()?;
@@ -43,12 +72,35 @@ impl Parse for EntraitFnAttr {
                 EntraitOpt::MockApi(ident) => mock_api = Some(ident),
                 EntraitOpt::Unimock(opt) => unimock = Some(opt),
                 EntraitOpt::Mockall(opt) => mockall = Some(opt),
+                EntraitOpt::TowerService(opt) => tower_service = Some(opt),
+                EntraitOpt::BlockingVariant(opt) => blocking_variant = Some(opt),
+                EntraitOpt::Cache(opt) => cache = Some(opt),
+                EntraitOpt::CacheTtlSecs(opt) => cache_ttl_secs = Some(opt),
+                EntraitOpt::Retry(opt) => retry = Some(opt),
+                EntraitOpt::TimeoutMs(opt) => timeout_ms = Some(opt),
+                EntraitOpt::Metrics(opt) => metrics = Some(opt),
+                EntraitOpt::CheckDeps(opt) => check_deps = Some(opt),
                 opt => return Err(syn::Error::new(opt.span(), "Unsupported option")),
             };
         }
 
+        check_mock_api(mock_api.as_ref(), unimock.as_ref(), mockall.as_ref())?;
+
         let default_span = trait_ident.span();
 
+        // `cache_ttl_secs` implies `cache`, unless explicitly disabled
+        let cache = match (cache, cache_ttl_secs) {
+            (Some(SpanOpt(false, _)), _) | (None, None) => None,
+            (Some(SpanOpt(true, span)), ttl_secs) => Some(CacheOpt {
+                ttl_secs: ttl_secs.map(|SpanOpt(lit, _)| lit),
+                span,
+            }),
+            (None, Some(SpanOpt(lit, span))) => Some(CacheOpt {
+                ttl_secs: Some(lit),
+                span,
+            }),
+        };
+
         Ok(EntraitFnAttr {
             trait_visibility,
             trait_ident,
@@ -62,6 +114,13 @@ impl Parse for EntraitFnAttr {
                 unimock,
                 mockall,
             },
+            tower_service,
+            blocking_variant,
+            cache,
+            retry,
+            timeout_ms,
+            metrics: metrics.filter(|SpanOpt(enabled, _)| *enabled),
+            check_deps: check_deps.filter(|SpanOpt(check, _)| *check != CheckDeps::Off),
             crate_idents: CrateIdents::new(span),
         })
     }
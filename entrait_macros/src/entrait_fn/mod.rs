@@ -8,11 +8,17 @@ pub mod input_attr;
 use crate::analyze_generics;
 use crate::analyze_generics::GenericsAnalyzer;
 use crate::analyze_generics::TraitFnAnalyzer;
+use crate::attributes;
+use crate::blocking_codegen::BlockingVariantCodegen;
+use crate::check_deps_codegen;
 use crate::fn_delegation_codegen;
 use crate::generics;
 use crate::input::FnInputMode;
 use crate::input::{InputFn, InputMod, ModItem};
+use crate::metrics_codegen;
+use crate::opt::SpanOpt;
 use crate::signature;
+use crate::tower_codegen::TowerServiceCodegen;
 use crate::trait_codegen::Supertraits;
 use crate::trait_codegen::TraitCodegen;
 use input_attr::*;
@@ -42,6 +48,10 @@ pub fn entrait_for_single_fn(attr: &EntraitFnAttr, input_fn: InputFn) -> syn::Re
     )?;
     let use_associated_future =
         generics::detect_use_associated_future(&attr.opts, [&input_fn].into_iter());
+    check_cache(attr, use_associated_future)?;
+    check_resilience(attr, &trait_fns, use_associated_future)?;
+    metrics_codegen::check_metrics(attr.metrics.as_ref(), use_associated_future)?;
+    check_unimock_impl_returns(attr, &trait_fns)?;
 
     let trait_generics = generics_analyzer.into_trait_generics();
     let trait_def = TraitCodegen {
@@ -58,7 +68,7 @@ pub fn entrait_for_single_fn(attr: &EntraitFnAttr, input_fn: InputFn) -> syn::Re
         &trait_fns,
         &fn_input_mode,
     )?;
-    let impl_block = fn_delegation_codegen::FnDelegationCodegen {
+    let fn_delegation_codegen = fn_delegation_codegen::FnDelegationCodegen {
         opts: &attr.opts,
         crate_idents: &attr.crate_idents,
         trait_ref: &attr.trait_ident,
@@ -68,8 +78,46 @@ pub fn entrait_for_single_fn(attr: &EntraitFnAttr, input_fn: InputFn) -> syn::Re
         fn_input_mode: &fn_input_mode,
         trait_dependency_mode: &trait_dependency_mode,
         use_associated_future,
-    }
-    .gen_impl_block(&trait_fns);
+        cache: attr.cache.as_ref(),
+        retry: attr.retry.as_ref(),
+        timeout_ms: attr.timeout_ms.as_ref(),
+        metrics: attr.metrics.as_ref(),
+    };
+    let impl_block = fn_delegation_codegen.gen_impl_block(&trait_fns)?;
+    let opt_wrapper_impl_blocks = fn_delegation_codegen.gen_wrapper_impl_blocks(&trait_fns)?;
+    let opt_tower_service = match &attr.tower_service {
+        Some(SpanOpt(service_ident, _)) => Some(
+            TowerServiceCodegen {
+                opts: &attr.opts,
+                crate_idents: &attr.crate_idents,
+                trait_visibility: &attr.trait_visibility,
+                trait_ident: &attr.trait_ident,
+                service_ident,
+                trait_generics: &trait_generics,
+            }
+            .gen_service(&trait_fns[0])?,
+        ),
+        None => None,
+    };
+    let opt_blocking_variant = match &attr.blocking_variant {
+        Some(SpanOpt(blocking_ident, _)) => Some(
+            BlockingVariantCodegen {
+                opts: &attr.opts,
+                crate_idents: &attr.crate_idents,
+                trait_visibility: &attr.trait_visibility,
+                trait_ident: &attr.trait_ident,
+                blocking_ident,
+                trait_generics: &trait_generics,
+            }
+            .gen_blocking_variant(&trait_fns[0])?,
+        ),
+        None => None,
+    };
+    let unused_deps_check = check_deps_codegen::gen_unused_deps_check(
+        attr.check_deps.as_ref(),
+        &trait_fns[0],
+        &input_fn,
+    )?;
 
     let InputFn {
         fn_attrs,
@@ -83,10 +131,27 @@ pub fn entrait_for_single_fn(attr: &EntraitFnAttr, input_fn: InputFn) -> syn::Re
         #(#fn_attrs)* #fn_vis #fn_sig #fn_body
         #trait_def
         #impl_block
+        #opt_wrapper_impl_blocks
+        #opt_tower_service
+        #opt_blocking_variant
+        #unused_deps_check
     })
 }
 
 pub fn entrait_for_mod(attr: &EntraitFnAttr, input_mod: InputMod) -> syn::Result<TokenStream> {
+    if let Some(SpanOpt(_, span)) = &attr.tower_service {
+        return Err(syn::Error::new(
+            *span,
+            "tower_service is only supported for single functions",
+        ));
+    }
+    if let Some(SpanOpt(_, span)) = &attr.blocking_variant {
+        return Err(syn::Error::new(
+            *span,
+            "blocking_variant is only supported for single functions",
+        ));
+    }
+
     let fn_input_mode = FnInputMode::Module(&input_mod.ident);
     let mut generics_analyzer = analyze_generics::GenericsAnalyzer::new();
     let trait_fns = input_mod
@@ -114,6 +179,10 @@ pub fn entrait_for_mod(attr: &EntraitFnAttr, input_mod: InputMod) -> syn::Result
         &attr.opts,
         input_mod.items.iter().filter_map(ModItem::filter_pub_fn),
     );
+    check_cache(attr, use_associated_future)?;
+    check_resilience(attr, &trait_fns, use_associated_future)?;
+    metrics_codegen::check_metrics(attr.metrics.as_ref(), use_associated_future)?;
+    check_unimock_impl_returns(attr, &trait_fns)?;
 
     let trait_generics = generics_analyzer.into_trait_generics();
     let trait_def = TraitCodegen {
@@ -130,7 +199,7 @@ pub fn entrait_for_mod(attr: &EntraitFnAttr, input_mod: InputMod) -> syn::Result
         &trait_fns,
         &fn_input_mode,
     )?;
-    let impl_block = fn_delegation_codegen::FnDelegationCodegen {
+    let fn_delegation_codegen = fn_delegation_codegen::FnDelegationCodegen {
         opts: &attr.opts,
         crate_idents: &attr.crate_idents,
         trait_ref: &attr.trait_ident,
@@ -140,8 +209,22 @@ pub fn entrait_for_mod(attr: &EntraitFnAttr, input_mod: InputMod) -> syn::Result
         fn_input_mode: &fn_input_mode,
         trait_dependency_mode: &trait_dependency_mode,
         use_associated_future,
-    }
-    .gen_impl_block(&trait_fns);
+        cache: attr.cache.as_ref(),
+        retry: attr.retry.as_ref(),
+        timeout_ms: attr.timeout_ms.as_ref(),
+        metrics: attr.metrics.as_ref(),
+    };
+    let impl_block = fn_delegation_codegen.gen_impl_block(&trait_fns)?;
+    let opt_wrapper_impl_blocks = fn_delegation_codegen.gen_wrapper_impl_blocks(&trait_fns)?;
+    let unused_deps_checks = input_mod
+        .items
+        .iter()
+        .filter_map(ModItem::filter_pub_fn)
+        .zip(&trait_fns)
+        .map(|(input_fn, trait_fn)| {
+            check_deps_codegen::gen_unused_deps_check(attr.check_deps.as_ref(), trait_fn, input_fn)
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
 
     let InputMod {
         attrs,
@@ -154,6 +237,14 @@ pub fn entrait_for_mod(attr: &EntraitFnAttr, input_mod: InputMod) -> syn::Result
 
     let trait_vis = &attr.trait_visibility;
     let trait_ident = &attr.trait_ident;
+    let opt_mockall_reexport =
+        attributes::mockall_mock_ident(&attr.opts, trait_ident).map(|mock_ident| {
+            let export_gate = attributes::ExportGate { opts: &attr.opts };
+            quote! {
+                #export_gate
+                #trait_vis use #mod_ident::#mock_ident;
+            }
+        });
 
     Ok(quote! {
         #(#attrs)*
@@ -162,8 +253,88 @@ pub fn entrait_for_mod(attr: &EntraitFnAttr, input_mod: InputMod) -> syn::Result
 
             #trait_def
             #impl_block
+            #opt_wrapper_impl_blocks
+            #(#unused_deps_checks)*
         }
 
         #trait_vis use #mod_ident::#trait_ident;
+        #opt_mockall_reexport
     })
 }
+
+fn check_cache(
+    attr: &EntraitFnAttr,
+    use_associated_future: generics::UseAssociatedFuture,
+) -> syn::Result<()> {
+    match &attr.cache {
+        Some(cache) if use_associated_future.0 => Err(syn::Error::new(
+            cache.span,
+            "cache is not supported with associated futures",
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn check_resilience(
+    attr: &EntraitFnAttr,
+    trait_fns: &[analyze_generics::TraitFn],
+    use_associated_future: generics::UseAssociatedFuture,
+) -> syn::Result<()> {
+    let (option_name, span) = match (&attr.retry, &attr.timeout_ms) {
+        (Some(SpanOpt(_, span)), _) => ("retry", *span),
+        (None, Some(SpanOpt(_, span))) => ("timeout_ms", *span),
+        (None, None) => return Ok(()),
+    };
+
+    if use_associated_future.0 {
+        return Err(syn::Error::new(
+            span,
+            format!("{option_name} is not supported with associated futures"),
+        ));
+    }
+
+    for trait_fn in trait_fns {
+        if !trait_fn.originally_async
+            || signature::ResultParts::detect(&trait_fn.sig().output).is_none()
+        {
+            return Err(syn::Error::new(
+                span,
+                format!("{option_name} requires async functions returning `Result`"),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Unimock needs a nameable output type, which `impl Iterator` and `impl Stream` only get when boxed
+fn check_unimock_impl_returns(
+    attr: &EntraitFnAttr,
+    trait_fns: &[analyze_generics::TraitFn],
+) -> syn::Result<()> {
+    let opts = &attr.opts;
+    let mock_api = match &opts.mock_api {
+        Some(mock_api)
+            if opts.default_option(opts.unimock, false).0
+                && !opts.default_option(opts.mockall, false).0 =>
+        {
+            mock_api
+        }
+        _ => return Ok(()),
+    };
+
+    for trait_fn in trait_fns {
+        let unboxed_impl_return = match &trait_fn.entrait_sig.associated_fut {
+            Some(associated_fut) => associated_fut.impl_bounds.is_some(),
+            None => signature::impl_return::ImplReturn::detect(&trait_fn.sig().output).is_some(),
+        };
+        if unboxed_impl_return {
+            return Err(syn::Error::new(
+                mock_api.0.span(),
+                "Mocking `impl Iterator` and `impl Stream` return types with unimock requires boxed futures (`box_future`)",
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -3,13 +3,20 @@ use crate::opt::*;
 
 use syn::parse::{Parse, ParseStream};
 
-// Input of #[entrait(ref|dyn?)] impl A for B {}
+// Input of #[entrait(ref|dyn? ApiTrait?)] impl A for B {}
 pub struct EntraitSimpleImplAttr {
     pub impl_kind: ImplKind,
+    pub api_trait: Option<ApiTrait>,
     pub opts: Opts,
     pub crate_idents: CrateIdents,
 }
 
+/// A mockable trait exposing the functions of the impl block, implemented for `Impl<T>`
+pub struct ApiTrait {
+    pub visibility: syn::Visibility,
+    pub ident: syn::Ident,
+}
+
 #[derive(Clone, Copy)]
 pub enum ImplKind {
     Static,
@@ -23,12 +30,37 @@ impl Parse for EntraitSimpleImplAttr {
         let ref_token: Option<syn::token::Ref> = input.parse()?;
         let dyn_token: Option<syn::token::Dyn> = input.parse()?;
 
+        if (ref_token.is_some() || dyn_token.is_some()) && input.peek(syn::token::Comma) {
+            input.parse::<syn::token::Comma>()?;
+        }
+
+        let api_trait = if peek_api_trait(input) {
+            let api_trait = ApiTrait {
+                visibility: input.parse()?,
+                ident: input.parse()?,
+            };
+            if input.peek(syn::token::Comma) {
+                input.parse::<syn::token::Comma>()?;
+            }
+            Some(api_trait)
+        } else {
+            None
+        };
+
         let mut debug = None;
+        let mut export = None;
+        let mut mock_api = None;
+        let mut unimock = None;
+        let mut mockall = None;
 
         if !input.is_empty() {
             loop {
                 match input.parse::<EntraitOpt>()? {
                     EntraitOpt::Debug(opt) => debug = Some(opt),
+                    EntraitOpt::Export(opt) => export = Some(opt),
+                    EntraitOpt::MockApi(ident) => mock_api = Some(ident),
+                    EntraitOpt::Unimock(opt) => unimock = Some(opt),
+                    EntraitOpt::Mockall(opt) => mockall = Some(opt),
                     entrait_opt => {
                         return Err(syn::Error::new(entrait_opt.span(), "Unsupported option"))
                     }
@@ -42,27 +74,56 @@ impl Parse for EntraitSimpleImplAttr {
             }
         }
 
+        check_mock_api(mock_api.as_ref(), unimock.as_ref(), mockall.as_ref())?;
+
+        if api_trait.is_none() {
+            let mock_span = match (&mock_api, &mockall) {
+                (Some(mock_api), _) => Some(mock_api.0.span()),
+                (None, Some(SpanOpt(true, span))) => Some(*span),
+                _ => None,
+            };
+            if let Some(span) = mock_span {
+                return Err(syn::Error::new(
+                    span,
+                    "Mocking an impl block requires an API trait, e.g. `#[entrait(FooApi, mock_api = FooApiMock)]`",
+                ));
+            }
+        }
+
         Ok(Self {
             impl_kind: if dyn_token.is_some() || ref_token.is_some() {
                 ImplKind::DynRef
             } else {
                 ImplKind::Static
             },
+            api_trait,
             opts: Opts {
                 default_span: span,
                 no_deps: None,
                 debug,
                 async_strategy: None,
-                export: None,
-                mock_api: None,
-                unimock: None,
-                mockall: None,
+                export,
+                mock_api,
+                unimock,
+                mockall,
             },
             crate_idents: CrateIdents::new(span),
         })
     }
 }
 
+/// Whether the input starts with an API trait, i.e. an UpperCamelCase ident with optional visibility.
+///
+/// Anything else is parsed as an option, so that misspelled options are reported as such.
+fn peek_api_trait(input: ParseStream) -> bool {
+    let fork = input.fork();
+    fork.parse::<syn::Visibility>().is_ok()
+        && matches!(
+            fork.parse::<syn::Ident>(),
+            Ok(ident) if ident.to_string().starts_with(|c: char| c.is_ascii_uppercase())
+        )
+}
+
 pub struct EntraitImplAttr {
     pub opts: Opts,
     pub crate_idents: CrateIdents,
@@ -10,10 +10,13 @@ use crate::input::InputImpl;
 use crate::opt::AsyncStrategy;
 use crate::opt::SpanOpt;
 use crate::signature;
+use crate::trait_codegen::Supertraits;
+use crate::trait_codegen::TraitCodegen;
 
 use quote::quote;
 use syn::spanned::Spanned;
 
+use self::input_attr::ApiTrait;
 use self::input_attr::EntraitSimpleImplAttr;
 use self::input_attr::ImplKind;
 
@@ -84,8 +87,17 @@ pub fn output_tokens_for_impl(
         fn_input_mode: &fn_input_mode,
         trait_dependency_mode: &trait_dependency_mode,
         use_associated_future,
+        cache: None,
+        retry: None,
+        timeout_ms: None,
+        metrics: None,
     }
-    .gen_impl_block(&trait_fns);
+    .gen_impl_block(&trait_fns)?;
+
+    let opt_api_trait = match &attr.api_trait {
+        Some(api_trait) => Some(gen_api_trait(&attr, api_trait, &self_ty, &items)?),
+        None => None,
+    };
 
     Ok(quote! {
         #(#attrs)*
@@ -93,5 +105,102 @@ pub fn output_tokens_for_impl(
             #(#items)*
         }
         #impl_block
+        #opt_api_trait
+    })
+}
+
+/// Generate a trait with the functions of the impl block, delegated to from `Impl<T>`.
+///
+/// Like an entraited module, the trait is mockable, and its mocks can be unmocked
+/// into the functions of the impl block, e.g. `MyImpl::foo`.
+fn gen_api_trait(
+    attr: &EntraitSimpleImplAttr,
+    api_trait: &ApiTrait,
+    self_ty: &syn::Type,
+    items: &[ImplItem],
+) -> syn::Result<proc_macro2::TokenStream> {
+    let self_path = match self_ty {
+        syn::Type::Path(type_path)
+            if type_path.qself.is_none()
+                && type_path
+                    .path
+                    .segments
+                    .iter()
+                    .all(|segment| segment.arguments.is_empty()) =>
+        {
+            &type_path.path
+        }
+        _ => {
+            return Err(syn::Error::new(
+                self_ty.span(),
+                "An API trait requires an impl block for a non-generic type path",
+            ))
+        }
+    };
+
+    let fn_input_mode = crate::input::FnInputMode::ImplBlockApi(self_path);
+    let mut generics_analyzer = analyze_generics::GenericsAnalyzer::new();
+    let trait_fns = items
+        .iter()
+        .filter_map(ImplItem::filter_fn)
+        .map(|input_fn| {
+            TraitFnAnalyzer {
+                impl_receiver_kind: signature::ImplReceiverKind::SelfRef,
+                trait_span: api_trait.ident.span(),
+                crate_idents: &attr.crate_idents,
+                opts: &attr.opts,
+            }
+            .analyze(input_fn.input_sig(), &mut generics_analyzer)
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let trait_dependency_mode = detect_trait_dependency_mode(
+        &fn_input_mode,
+        &trait_fns,
+        &attr.crate_idents,
+        api_trait.ident.span(),
+    )?;
+    let use_associated_future = generics::detect_use_associated_future(
+        &attr.opts,
+        items.iter().filter_map(ImplItem::filter_fn),
+    );
+
+    let trait_generics = generics_analyzer.into_trait_generics();
+    let trait_def = TraitCodegen {
+        opts: &attr.opts,
+        crate_idents: &attr.crate_idents,
+        trait_indirection: generics::TraitIndirection::Plain,
+        trait_dependency_mode: &trait_dependency_mode,
+    }
+    .gen_trait_def(
+        &api_trait.visibility,
+        &api_trait.ident,
+        &trait_generics,
+        &Supertraits::None,
+        &trait_fns,
+        &fn_input_mode,
+    )?;
+    let fn_delegation_codegen = fn_delegation_codegen::FnDelegationCodegen {
+        opts: &attr.opts,
+        crate_idents: &attr.crate_idents,
+        trait_ref: &api_trait.ident,
+        trait_span: api_trait.ident.span(),
+        impl_indirection: generics::ImplIndirection::None,
+        trait_generics: &trait_generics,
+        fn_input_mode: &fn_input_mode,
+        trait_dependency_mode: &trait_dependency_mode,
+        use_associated_future,
+        cache: None,
+        retry: None,
+        timeout_ms: None,
+        metrics: None,
+    };
+    let impl_block = fn_delegation_codegen.gen_impl_block(&trait_fns)?;
+    let opt_wrapper_impl_blocks = fn_delegation_codegen.gen_wrapper_impl_blocks(&trait_fns)?;
+
+    Ok(quote! {
+        #trait_def
+        #impl_block
+        #opt_wrapper_impl_blocks
     })
 }
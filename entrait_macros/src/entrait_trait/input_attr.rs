@@ -7,6 +7,14 @@ pub struct EntraitTraitAttr {
     pub impl_trait: Option<ImplTrait>,
     pub opts: Opts,
     pub delegation_kind: Option<SpanOpt<Delegate>>,
+    /// Prefix of the generated gRPC server and client types
+    pub grpc: Option<SpanOpt<syn::Ident>>,
+    /// Name of the generated remote client type
+    pub remote: Option<SpanOpt<syn::Ident>>,
+    /// Metrics instrumentation of the delegation from `Impl<T>`
+    pub metrics: Option<SpanOpt<bool>>,
+    /// In-memory fake implementation
+    pub fake: Option<SpanOpt<FakeOpt>>,
     pub crate_idents: CrateIdents,
 }
 
@@ -35,6 +43,10 @@ impl Parse for EntraitTraitAttr {
         let mut unimock = None;
         let mut mockall = None;
         let mut delegation_kind = None;
+        let mut grpc = None;
+        let mut remote = None;
+        let mut metrics = None;
+        let mut fake = None;
 
         if !input.is_empty() {
             loop {
@@ -50,6 +62,10 @@ impl Parse for EntraitTraitAttr {
                     EntraitOpt::Unimock(opt) => unimock = Some(opt),
                     EntraitOpt::Mockall(opt) => mockall = Some(opt),
                     EntraitOpt::DelegateBy(kind) => delegation_kind = Some(kind),
+                    EntraitOpt::Grpc(opt) => grpc = Some(opt),
+                    EntraitOpt::Remote(opt) => remote = Some(opt),
+                    EntraitOpt::Metrics(opt) => metrics = Some(opt),
+                    EntraitOpt::Fake(opt) => fake = Some(opt),
                     entrait_opt => {
                         return Err(syn::Error::new(entrait_opt.span(), "Unsupported option"))
                     }
@@ -63,6 +79,8 @@ impl Parse for EntraitTraitAttr {
             }
         }
 
+        check_mock_api(mock_api.as_ref(), unimock.as_ref(), mockall.as_ref())?;
+
         Ok(Self {
             impl_trait,
             opts: Opts {
@@ -76,6 +94,10 @@ impl Parse for EntraitTraitAttr {
                 mockall,
             },
             delegation_kind,
+            grpc,
+            remote,
+            metrics: metrics.filter(|SpanOpt(enabled, _)| *enabled),
+            fake,
             crate_idents: CrateIdents::new(span),
         })
     }
@@ -6,18 +6,27 @@ mod out_trait;
 use input_attr::EntraitTraitAttr;
 use proc_macro2::Span;
 
+use crate::actor_codegen::ActorCodegen;
 use crate::analyze_generics::TraitFn;
 use crate::attributes;
 use crate::entrait_trait::input_attr::ImplTrait;
+use crate::fake_codegen::FakeCodegen;
+use crate::fn_field_codegen::FnFieldCodegen;
 use crate::generics;
 use crate::generics::TraitDependencyMode;
+use crate::grpc_codegen::GrpcCodegen;
 use crate::idents::GenericIdents;
 use crate::input::FnInputMode;
 use crate::input::LiteralAttrs;
+use crate::metrics_codegen;
+use crate::metrics_codegen::CallTimer;
 use crate::opt::*;
+use crate::remote_codegen::RemoteCodegen;
 use crate::token_util::*;
 use crate::trait_codegen::Supertraits;
 use crate::trait_codegen::TraitCodegen;
+use crate::wrapper_codegen;
+use crate::wrapper_codegen::ImplWrapper;
 
 use proc_macro2::TokenStream;
 use quote::quote;
@@ -40,6 +49,22 @@ pub fn output_tokens(
             "Cannot use a custom delegating trait without a custom trait to delegate to. Use either `#[entrait(TraitImpl, delegate_by = DelegateTrait)]` or `#[entrait(delegate_by = ref)]`",
         ));
     }
+    if let (None, Some(SpanOpt(Delegate::ByActor, span))) =
+        (&attr.impl_trait, &attr.delegation_kind)
+    {
+        return Err(syn::Error::new(
+            *span,
+            "Actor delegation requires a name for the actor handle. Use `#[entrait(TraitActor, delegate_by = actor)]`",
+        ));
+    }
+    if let (None, Some(SpanOpt(Delegate::ByFnField, span))) =
+        (&attr.impl_trait, &attr.delegation_kind)
+    {
+        return Err(syn::Error::new(
+            *span,
+            "Fn field delegation requires a name for the accessor trait. Use `#[entrait(TraitFns, delegate_by = fn_field)]`",
+        ));
+    }
 
     let trait_ident_span = item_trait.ident.span();
     let contains_async = ContainsAsync(item_trait.items.iter().any(|item| match item {
@@ -59,6 +84,15 @@ pub fn output_tokens(
         .collect::<Vec<_>>();
 
     let out_trait = out_trait::analyze_trait(item_trait)?;
+    metrics_codegen::check_metrics(
+        attr.metrics.as_ref(),
+        generics::UseAssociatedFuture(
+            out_trait
+                .fns
+                .iter()
+                .any(|trait_fn| trait_fn.entrait_sig.associated_fut.is_some()),
+        ),
+    )?;
     let trait_dependency_mode = TraitDependencyMode::Generic(GenericIdents::new(
         &attr.crate_idents,
         out_trait.ident.span(),
@@ -111,16 +145,164 @@ pub fn output_tokens(
         span: trait_ident_span,
     };
 
-    let impl_assoc_types = out_trait.fns.iter().filter_map(|trait_fn| {
-        trait_fn
-            .entrait_sig
-            .associated_fut_impl(generics::TraitIndirection::Plain, &attr.crate_idents)
-    });
+    let impl_assoc_types = out_trait
+        .fns
+        .iter()
+        .filter_map(|trait_fn| {
+            trait_fn
+                .entrait_sig
+                .associated_fut_impl(generics::TraitIndirection::Plain, &attr.crate_idents)
+        })
+        .collect::<Vec<_>>();
 
     let method_items = out_trait
         .fns
         .iter()
-        .map(|trait_fn| gen_delegation_method(trait_fn, generic_idents, &attr, contains_async));
+        .map(|trait_fn| {
+            gen_delegation_method(
+                trait_fn,
+                &out_trait.ident,
+                generic_idents,
+                &attr,
+                contains_async,
+            )
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let wrapper_impls = ImplWrapper::ALL
+        .into_iter()
+        .map(|wrapper| {
+            let params = match wrapper.has_scope_c() {
+                true => params.clone().with_scope_c(&generic_idents.scope_c),
+                false => params.clone(),
+            };
+            gen_wrapper_impl(
+                wrapper,
+                &out_trait,
+                generic_idents,
+                &attr,
+                !impl_attrs.is_empty(),
+                quote! {
+                    #(#impl_attrs)*
+                    #impl_async_trait_attr
+                    impl #params #trait_ident #args
+                },
+                &impl_assoc_types,
+            )
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let opt_grpc = match &attr.grpc {
+        Some(SpanOpt(prefix, _)) => Some(
+            GrpcCodegen {
+                opts: &attr.opts,
+                crate_idents: &attr.crate_idents,
+                trait_visibility: &out_trait.vis,
+                trait_ident,
+                trait_generics: &out_trait.generics,
+                prefix,
+                has_async_trait_attr: !impl_attrs.is_empty(),
+            }
+            .gen_server_and_client(
+                &out_trait.fns,
+                &quote! {
+                    #(#impl_attrs)*
+                    #impl_async_trait_attr
+                },
+            )?,
+        ),
+        None => None,
+    };
+
+    let opt_actor = match (&attr.impl_trait, &attr.delegation_kind) {
+        (Some(ImplTrait(handle_visibility, handle_ident)), Some(SpanOpt(Delegate::ByActor, _))) => {
+            Some(
+                ActorCodegen {
+                    opts: &attr.opts,
+                    crate_idents: &attr.crate_idents,
+                    handle_visibility,
+                    trait_ident,
+                    trait_generics: &out_trait.generics,
+                    handle_ident,
+                    has_async_trait_attr: !impl_attrs.is_empty(),
+                }
+                .gen_actor(
+                    &out_trait.fns,
+                    &quote! {
+                        #(#impl_attrs)*
+                        #impl_async_trait_attr
+                    },
+                )?,
+            )
+        }
+        _ => None,
+    };
+
+    let opt_fn_field = match (&attr.impl_trait, &attr.delegation_kind) {
+        (
+            Some(ImplTrait(accessor_visibility, accessor_ident)),
+            Some(SpanOpt(Delegate::ByFnField, _)),
+        ) => Some(
+            FnFieldCodegen {
+                crate_idents: &attr.crate_idents,
+                accessor_visibility,
+                trait_ident,
+                trait_generics: &out_trait.generics,
+                accessor_ident,
+            }
+            .gen_accessor_trait(&out_trait.fns)?,
+        ),
+        _ => None,
+    };
+
+    let opt_fake = match &attr.fake {
+        Some(SpanOpt(fake, span)) => Some(
+            FakeCodegen {
+                crate_idents: &attr.crate_idents,
+                fake_visibility: &out_trait.vis,
+                trait_ident,
+                trait_generics: &out_trait.generics,
+                delegation: match (&attr.impl_trait, &attr.delegation_kind) {
+                    (Some(ImplTrait(_, impl_trait_ident)), Some(SpanOpt(delegate, _))) => {
+                        Some((impl_trait_ident, delegate))
+                    }
+                    _ => None,
+                },
+                fake,
+                span: *span,
+            }
+            .gen_fake(
+                &out_trait.fns,
+                &quote! {
+                    #(#impl_attrs)*
+                    #impl_async_trait_attr
+                },
+            )?,
+        ),
+        None => None,
+    };
+
+    let opt_remote = match &attr.remote {
+        Some(SpanOpt(client_ident, _)) => Some(
+            RemoteCodegen {
+                opts: &attr.opts,
+                crate_idents: &attr.crate_idents,
+                trait_visibility: &out_trait.vis,
+                trait_ident,
+                trait_generics: &out_trait.generics,
+                client_ident,
+                has_async_trait_attr: !impl_attrs.is_empty(),
+            }
+            .gen_remote(
+                &out_trait.fns,
+                &quote! {
+                    #(#impl_attrs)*
+                    #impl_async_trait_attr
+                },
+            )?,
+        ),
+        None => None,
+    };
 
     Ok(quote! {
         #trait_def
@@ -133,9 +315,148 @@ pub fn output_tokens(
             #(#impl_assoc_types)*
             #(#method_items)*
         }
+
+        #(#wrapper_impls)*
+
+        #opt_actor
+
+        #opt_fn_field
+
+        #opt_grpc
+
+        #opt_remote
+
+        #opt_fake
     })
 }
 
+/// Generate the feature-gated impl for a wrapper around `Impl<T>`, which forwards to `Impl<T>`.
+/// For `::entrait::overrides::Overrides<T>`, stubs are looked up first.
+fn gen_wrapper_impl(
+    wrapper: ImplWrapper,
+    out_trait: &OutTrait,
+    generic_idents: &GenericIdents,
+    attr: &EntraitTraitAttr,
+    has_async_trait_attr: bool,
+    impl_header: TokenStream,
+    impl_assoc_types: &[impl ToTokens],
+) -> syn::Result<TokenStream> {
+    let span = out_trait.ident.span();
+
+    let async_strategy = if has_async_trait_attr {
+        SpanOpt(AsyncStrategy::BoxFuture, span)
+    } else {
+        attr.opts.async_strategy()
+    };
+    let supertraits_overridable = match &out_trait.supertraits {
+        Supertraits::None => true,
+        Supertraits::Some { bounds, .. } => bounds.iter().all(|bound| match bound {
+            syn::TypeParamBound::Lifetime(_) => true,
+            syn::TypeParamBound::Trait(trait_bound) => {
+                trait_bound.path.is_ident("Send") || trait_bound.path.is_ident("Sync")
+            }
+            _ => false,
+        }),
+    };
+    let is_overridable = wrapper_codegen::IsOverridable(
+        matches!(wrapper, ImplWrapper::Overrides)
+            && supertraits_overridable
+            && wrapper_codegen::detect_overridable(
+                async_strategy,
+                &out_trait.generics,
+                &out_trait.fns,
+            )
+            .0,
+    );
+
+    let trait_ident = &out_trait.ident;
+    let trait_with_args = TokenPair(
+        trait_ident,
+        out_trait
+            .generics
+            .arguments(&generics::ImplIndirection::None),
+    );
+    let opt_overridable_impl = if is_overridable.0 {
+        Some(wrapper_codegen::OverridableImpl {
+            crate_idents: &attr.crate_idents,
+            trait_ref: trait_ident,
+            span,
+        })
+    } else {
+        None
+    };
+
+    let wrapper_path = wrapper.path(generic_idents, span);
+    let as_impl = wrapper.as_impl_expr(&attr.crate_idents, span);
+    let impl_path = generic_idents.impl_path(span);
+    let where_predicates = &out_trait.generics.where_predicates;
+    let opt_scope_c_bounds = match (&out_trait.supertraits, wrapper.has_scope_c()) {
+        (Supertraits::Some { bounds, .. }, true) => {
+            let scope_c = &generic_idents.scope_c;
+            let lifetimes = bounds.iter().filter_map(|bound| match bound {
+                syn::TypeParamBound::Lifetime(lifetime) => Some(lifetime),
+                _ => None,
+            });
+            Some(quote! { #scope_c: #(#lifetimes +)*, })
+        }
+        _ => None,
+    };
+
+    let method_items = out_trait
+        .fns
+        .iter()
+        .map(|trait_fn| {
+            let fn_ident = &trait_fn.sig().ident;
+            let arguments = trait_fn.entrait_sig.arg_idents()?;
+
+            Ok(DelegatingMethod {
+                attr,
+                trait_fn,
+                needs_async_move: false,
+                opt_override_lookup: if is_overridable.0 {
+                    Some(
+                        wrapper_codegen::OverrideLookup {
+                            crate_idents: &attr.crate_idents,
+                            trait_ref: trait_ident,
+                            trait_fn,
+                            arguments: arguments.clone(),
+                            span,
+                        }
+                        .into_token_stream(),
+                    )
+                } else {
+                    None
+                },
+                opt_call_timer: None,
+                // Fully qualified, as `Impl<T>` may implement other traits with the same method names
+                call: quote! {
+                    <#impl_path as #trait_with_args>::#fn_ident(#as_impl, #(#arguments),*)
+                },
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(wrapper_codegen::FeatureGated {
+        wrapper,
+        crate_idents: &attr.crate_idents,
+        span,
+        tokens: quote! {
+            #opt_overridable_impl
+
+            #impl_header for #wrapper_path
+            where
+                #impl_path: #trait_with_args,
+                #opt_scope_c_bounds
+                #where_predicates
+            {
+                #(#impl_assoc_types)*
+                #(#method_items)*
+            }
+        },
+    }
+    .into_token_stream())
+}
+
 fn gen_impl_delegation_trait_defs(
     out_trait: &OutTrait,
     trait_dependency_mode: &TraitDependencyMode,
@@ -160,6 +481,10 @@ fn gen_impl_delegation_trait_defs(
     };
 
     match &attr.delegation_kind {
+        // The actor handle is generated separately, see `ActorCodegen`
+        Some(SpanOpt(Delegate::ByActor, _)) => Ok(None),
+        // The accessor trait is generated separately, see `FnFieldCodegen`
+        Some(SpanOpt(Delegate::ByFnField, _)) => Ok(None),
         Some(SpanOpt(Delegate::ByTrait(delegation_ident), _)) => {
             trait_copy.generics.params.insert(
                 0,
@@ -269,29 +594,35 @@ fn gen_impl_delegation_trait_defs(
 
 fn gen_delegation_method<'s>(
     trait_fn: &'s TraitFn,
+    trait_ident: &syn::Ident,
     generic_idents: &'s GenericIdents,
     attr: &'s EntraitTraitAttr,
     contains_async: ContainsAsync,
-) -> DelegatingMethod<'s> {
+) -> syn::Result<DelegatingMethod<'s>> {
     let fn_sig = &trait_fn.sig();
     let fn_ident = &fn_sig.ident;
     let impl_t = &generic_idents.impl_t;
 
-    let arguments = fn_sig.inputs.iter().filter_map(|arg| match arg {
-        syn::FnArg::Receiver(_) => None,
-        syn::FnArg::Typed(pat_type) => match pat_type.pat.as_ref() {
-            syn::Pat::Ident(pat_ident) => Some(pat_ident.ident.to_token_stream()),
-            _ => panic!("Found a non-ident pattern, this should be handled in signature.rs"),
-        },
-    });
+    let arguments = trait_fn.entrait_sig.arg_idents()?;
     let core = &generic_idents.crate_idents.core;
+    let opt_call_timer = attr.metrics.as_ref().map(|SpanOpt(_, span)| {
+        CallTimer {
+            crate_idents: &attr.crate_idents,
+            trait_name: trait_ident.to_string(),
+            fn_ident,
+            span: *span,
+        }
+        .into_token_stream()
+    });
 
-    match (&attr.impl_trait, &attr.delegation_kind) {
+    Ok(match (&attr.impl_trait, &attr.delegation_kind) {
         (Some(ImplTrait(_, impl_trait_ident)), Some(SpanOpt(Delegate::ByTrait(_), _))) => {
             DelegatingMethod {
                 attr,
                 trait_fn,
                 needs_async_move: true,
+                opt_override_lookup: None,
+                opt_call_timer,
                 call: quote! {
                     // TODO: pass additional generic arguments(?)
                     <#impl_t::Target as #impl_trait_ident<#impl_t>>::#fn_ident(self, #(#arguments),*)
@@ -326,13 +657,42 @@ fn gen_delegation_method<'s>(
                 attr,
                 trait_fn,
                 needs_async_move: false,
+                opt_override_lookup: None,
+                opt_call_timer,
                 call,
             }
         }
+        (Some(ImplTrait(_, handle_ident)), Some(SpanOpt(Delegate::ByActor, _))) => {
+            DelegatingMethod {
+                attr,
+                trait_fn,
+                needs_async_move: false,
+                opt_override_lookup: None,
+                opt_call_timer,
+                call: quote! {
+                    <#impl_t as ::#core::convert::AsRef<#handle_ident>>::as_ref(&*self)
+                        .#fn_ident(#(#arguments),*)
+                },
+            }
+        }
+        (Some(ImplTrait(_, accessor_ident)), Some(SpanOpt(Delegate::ByFnField, _))) => {
+            DelegatingMethod {
+                attr,
+                trait_fn,
+                needs_async_move: false,
+                opt_override_lookup: None,
+                opt_call_timer,
+                call: quote! {
+                    <#impl_t as #accessor_ident>::#fn_ident(&*self)(#(#arguments),*)
+                },
+            }
+        }
         (None, Some(SpanOpt(Delegate::ByRef(RefDelegate::AsRef), _))) => DelegatingMethod {
             attr,
             trait_fn,
             needs_async_move: false,
+            opt_override_lookup: None,
+            opt_call_timer,
             call: quote! {
                 self.as_ref().as_ref().#fn_ident(#(#arguments),*)
             },
@@ -341,6 +701,8 @@ fn gen_delegation_method<'s>(
             attr,
             trait_fn,
             needs_async_move: false,
+            opt_override_lookup: None,
+            opt_call_timer,
             call: quote! {
                 self.as_ref().borrow().#fn_ident(#(#arguments),*)
             },
@@ -349,17 +711,22 @@ fn gen_delegation_method<'s>(
             attr,
             trait_fn,
             needs_async_move: false,
+            opt_override_lookup: None,
+            opt_call_timer,
             call: quote! {
                 self.as_ref().#fn_ident(#(#arguments),*)
             },
         },
-    }
+    })
 }
 
 struct DelegatingMethod<'s> {
     attr: &'s EntraitTraitAttr,
     trait_fn: &'s TraitFn,
     needs_async_move: bool,
+    opt_override_lookup: Option<TokenStream>,
+    /// Metrics instrumentation, see the `metrics` option
+    opt_call_timer: Option<TokenStream>,
     call: TokenStream,
 }
 
@@ -367,7 +734,7 @@ impl<'s> DelegatingMethod<'s> {
     fn should_inline(&self) -> bool {
         if matches!(
             &self.attr.delegation_kind,
-            Some(SpanOpt(Delegate::ByRef(_), _))
+            Some(SpanOpt(Delegate::ByRef(_) | Delegate::ByActor, _))
         ) {
             return false;
         }
@@ -400,6 +767,8 @@ impl<'s> ToTokens for DelegatingMethod<'s> {
         }
         self.trait_fn.sig().to_tokens(stream);
         syn::token::Brace::default().surround(stream, |stream| {
+            push_tokens!(stream, self.opt_call_timer, self.opt_override_lookup);
+
             if self.needs_async_move && self.trait_fn.entrait_sig.associated_fut.is_some() {
                 push_tokens!(
                     stream,
@@ -482,6 +851,19 @@ impl<'g, 'c> ImplWhereClause<'g, 'c> {
                 }
                 push_tokens!(stream, self.plus_static());
             }
+            (Some(ImplTrait(_, handle_ident)), Some(SpanOpt(Delegate::ByActor, _))) => {
+                let core = &self.generic_idents.crate_idents.core;
+                push_tokens!(
+                    stream,
+                    quote! { ::#core::convert::AsRef<#handle_ident> },
+                    self.plus_send(),
+                    self.plus_sync(),
+                    self.plus_static()
+                );
+            }
+            (Some(ImplTrait(_, accessor_ident)), Some(SpanOpt(Delegate::ByFnField, _))) => {
+                push_tokens!(stream, accessor_ident);
+            }
             (None, Some(SpanOpt(Delegate::ByRef(ref_delegate), _))) => {
                 self.push_core_delegation_trait(stream, ref_delegate);
                 push_tokens!(
@@ -0,0 +1,367 @@
+//! Code generation for the `fake` option.
+//!
+//! Generates an in-memory fake of a repository trait, backed by an `entrait::fake::FakeStore`.
+//! The methods of the trait are implemented according to their names, which must start with `get`, `insert`, `update` or `delete`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+
+use crate::analyze_generics::TraitFn;
+use crate::generics::TraitGenerics;
+use crate::idents::CrateIdents;
+use crate::opt::{Delegate, FakeOpt};
+
+pub struct FakeCodegen<'s> {
+    pub crate_idents: &'s CrateIdents,
+    pub fake_visibility: &'s syn::Visibility,
+    pub trait_ident: &'s syn::Ident,
+    pub trait_generics: &'s TraitGenerics,
+    /// The generated implementation trait, and how `Impl<T>` delegates to it
+    pub delegation: Option<(&'s syn::Ident, &'s Delegate)>,
+    pub fake: &'s FakeOpt,
+    pub span: Span,
+}
+
+#[derive(Clone, Copy)]
+enum FakeOp {
+    Get,
+    Insert,
+    Update,
+    Delete,
+}
+
+/// An argument of a fake method, which may be passed by reference
+struct FakeArg<'t> {
+    ident: &'t syn::Ident,
+    by_ref: bool,
+}
+
+struct FakeMethod<'t> {
+    trait_fn: &'t TraitFn,
+    op: FakeOp,
+    key: FakeArg<'t>,
+    value: Option<FakeArg<'t>>,
+}
+
+impl<'s> FakeCodegen<'s> {
+    ///
+    /// Generate code like
+    ///
+    /// ```no_compile
+    /// #[derive(Default)]
+    /// pub struct InMemoryUsers(::entrait::fake::FakeStore<UserId, User>);
+    ///
+    /// impl UserRepository for InMemoryUsers {
+    ///     fn get_user(&self, id: UserId) -> Option<User> {
+    ///         ::entrait::fake::FakeOutput::from_fake(self.0.get(&id))
+    ///     }
+    /// }
+    ///
+    /// // with `delegate_by = DelegateUserRepository`:
+    /// impl<EntraitT: AsRef<InMemoryUsers>> UserRepositoryImpl<EntraitT> for InMemoryUsers { .. }
+    /// ```
+    ///
+    pub fn gen_fake(
+        &self,
+        trait_fns: &[TraitFn],
+        impl_attrs: &TokenStream,
+    ) -> syn::Result<TokenStream> {
+        let span = self.span;
+
+        if !self.trait_generics.params.is_empty() {
+            return Err(syn::Error::new(
+                span,
+                "fake does not support generic traits",
+            ));
+        }
+
+        let methods = trait_fns
+            .iter()
+            .map(analyze_method)
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        let entrait = &self.crate_idents.entrait;
+        let core = &self.crate_idents.core;
+        let vis = self.fake_visibility;
+        let trait_ident = self.trait_ident;
+        let fake_ident = &self.fake.ident;
+        let key = &self.fake.key;
+        let value = &self.fake.value;
+        let doc = format!("An in-memory fake implementation of [{trait_ident}].");
+
+        let trait_items = methods.iter().map(|method| {
+            let attrs = &method.trait_fn.attrs;
+            let sig = method.trait_fn.sig();
+            let body = self.gen_body(method, quote_spanned! { span=> self.0 });
+
+            quote_spanned! { span=>
+                #(#attrs)*
+                #sig {
+                    #body
+                }
+            }
+        });
+
+        let opt_delegation_impl = self.gen_delegation_impl(&methods, trait_fns, impl_attrs);
+
+        Ok(quote_spanned! { span=>
+            #[doc = #doc]
+            #[derive(Default)]
+            #vis struct #fake_ident(::#entrait::fake::FakeStore<#key, #value>);
+
+            impl #fake_ident {
+                /// A fake without entries.
+                pub fn new() -> Self {
+                    ::#core::default::Default::default()
+                }
+            }
+
+            impl ::#core::ops::Deref for #fake_ident {
+                type Target = ::#entrait::fake::FakeStore<#key, #value>;
+
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+
+            impl ::#core::iter::FromIterator<(#key, #value)> for #fake_ident {
+                fn from_iter<I: ::#core::iter::IntoIterator<Item = (#key, #value)>>(iter: I) -> Self {
+                    Self(::#core::iter::FromIterator::from_iter(iter))
+                }
+            }
+
+            #impl_attrs
+            impl #trait_ident for #fake_ident {
+                #(#trait_items)*
+            }
+
+            #opt_delegation_impl
+        })
+    }
+
+    /// Implement the generated implementation trait, so the fake can be delegated to
+    fn gen_delegation_impl(
+        &self,
+        methods: &[FakeMethod],
+        trait_fns: &[TraitFn],
+        impl_attrs: &TokenStream,
+    ) -> Option<TokenStream> {
+        let span = self.span;
+        let entrait = &self.crate_idents.entrait;
+        let core = &self.crate_idents.core;
+        let fake_ident = &self.fake.ident;
+
+        let (impl_trait_ident, delegate) = self.delegation?;
+        // Async methods borrow `Impl<T>` in a `Send` future
+        let contains_async = trait_fns.iter().any(|trait_fn| trait_fn.originally_async);
+
+        let (where_clause, store) = match delegate {
+            // Static functions receiving `Impl<T>`, where `T` owns the fake
+            Delegate::ByTrait(_) => {
+                let opt_sync = if contains_async {
+                    Some(quote_spanned! { span=> + Sync })
+                } else {
+                    None
+                };
+                (
+                    Some(quote_spanned! { span=>
+                        where EntraitT: ::#core::convert::AsRef<#fake_ident> #opt_sync
+                    }),
+                    quote_spanned! { span=>
+                        <EntraitT as ::#core::convert::AsRef<#fake_ident>>::as_ref(&**__impl).0
+                    },
+                )
+            }
+            // Methods receiving `&self` and `Impl<T>`
+            Delegate::ByRef(_) => (
+                if contains_async {
+                    Some(quote_spanned! { span=> where EntraitT: Sync })
+                } else {
+                    None
+                },
+                quote_spanned! { span=> self.0 },
+            ),
+            _ => return None,
+        };
+
+        let items = methods.iter().map(|method| {
+            let attrs = &method.trait_fn.attrs;
+            let mut sig = method.trait_fn.sig().clone();
+            match delegate {
+                Delegate::ByTrait(_) => {
+                    if let Some(first_arg) = sig.inputs.first_mut() {
+                        if let syn::FnArg::Receiver(receiver) = first_arg {
+                            let (and, lifetime) =
+                                receiver.reference.clone().expect("fake methods take &self");
+                            *first_arg = syn::parse_quote! {
+                                __impl: #and #lifetime ::#entrait::Impl<EntraitT>
+                            };
+                        }
+                    }
+                }
+                _ => {
+                    sig.inputs.insert(
+                        1,
+                        syn::parse_quote! {
+                            __impl: &::#entrait::Impl<EntraitT>
+                        },
+                    );
+                }
+            }
+            let body = self.gen_body(method, store.clone());
+
+            quote_spanned! { span=>
+                #(#attrs)*
+                #sig {
+                    #body
+                }
+            }
+        });
+
+        Some(quote_spanned! { span=>
+            #impl_attrs
+            impl<EntraitT> #impl_trait_ident<EntraitT> for #fake_ident #where_clause {
+                #(#items)*
+            }
+        })
+    }
+
+    fn gen_body(&self, method: &FakeMethod, store: TokenStream) -> TokenStream {
+        let span = self.span;
+        let entrait = &self.crate_idents.entrait;
+
+        let borrowed = |arg: &FakeArg| {
+            let ident = arg.ident;
+            if arg.by_ref {
+                quote! { #ident }
+            } else {
+                quote! { &#ident }
+            }
+        };
+        let owned = |arg: &FakeArg| {
+            let ident = arg.ident;
+            if arg.by_ref {
+                quote! { ::#entrait::__fake::ToOwned::to_owned(#ident) }
+            } else {
+                quote! { #ident }
+            }
+        };
+
+        let key = &method.key;
+        let call = match (method.op, &method.value) {
+            (FakeOp::Get, _) => {
+                let key = borrowed(key);
+                quote_spanned! { span=> #store.get(#key) }
+            }
+            (FakeOp::Delete, _) => {
+                let key = borrowed(key);
+                quote_spanned! { span=> #store.delete(#key) }
+            }
+            (FakeOp::Insert, Some(value)) => {
+                let key = owned(key);
+                let value = owned(value);
+                quote_spanned! { span=> #store.insert(#key, #value) }
+            }
+            (FakeOp::Update, Some(value)) => {
+                let key = borrowed(key);
+                let value = owned(value);
+                quote_spanned! { span=> #store.update(#key, #value) }
+            }
+            (FakeOp::Insert | FakeOp::Update, None) => unreachable!(),
+        };
+
+        quote_spanned! { span=>
+            ::#entrait::fake::FakeOutput::from_fake(#call)
+        }
+    }
+}
+
+fn analyze_method(trait_fn: &TraitFn) -> syn::Result<FakeMethod<'_>> {
+    let sig = trait_fn.sig();
+    let span = sig.ident.span();
+    let name = sig.ident.to_string();
+
+    let op = [
+        ("get", FakeOp::Get),
+        ("insert", FakeOp::Insert),
+        ("update", FakeOp::Update),
+        ("delete", FakeOp::Delete),
+    ]
+    .into_iter()
+    .find_map(|(prefix, op)| match name.strip_prefix(prefix) {
+        Some(rest) if rest.is_empty() || rest.starts_with('_') => Some(op),
+        _ => None,
+    })
+    .ok_or_else(|| {
+        syn::Error::new(
+            span,
+            "fake requires method names starting with `get`, `insert`, `update` or `delete`",
+        )
+    })?;
+
+    if trait_fn.entrait_sig.associated_fut.is_some() {
+        return Err(syn::Error::new(
+            span,
+            "fake does not support associated futures",
+        ));
+    }
+    if !sig.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            span,
+            "fake does not support generic methods",
+        ));
+    }
+
+    let mut inputs = sig.inputs.iter();
+    match inputs.next() {
+        Some(syn::FnArg::Receiver(receiver))
+            if receiver.reference.is_some() && receiver.mutability.is_none() => {}
+        _ => {
+            return Err(syn::Error::new(
+                span,
+                "fake requires methods taking `&self`",
+            ))
+        }
+    }
+
+    let args = inputs
+        .map(|fn_arg| match fn_arg {
+            syn::FnArg::Typed(pat_type) => match pat_type.pat.as_ref() {
+                syn::Pat::Ident(pat_ident) => Ok(FakeArg {
+                    ident: &pat_ident.ident,
+                    by_ref: matches!(pat_type.ty.as_ref(), syn::Type::Reference(_)),
+                }),
+                _ => Err(syn::Error::new(
+                    span,
+                    "fake requires arguments to be plain identifiers",
+                )),
+            },
+            syn::FnArg::Receiver(_) => Err(syn::Error::new(span, "Unexpected receiver")),
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let mut args = args.into_iter();
+    let (key, value) = match (op, args.next(), args.next(), args.next()) {
+        (FakeOp::Get | FakeOp::Delete, Some(key), None, None) => (key, None),
+        (FakeOp::Insert | FakeOp::Update, Some(key), Some(value), None) => (key, Some(value)),
+        (FakeOp::Get | FakeOp::Delete, ..) => {
+            return Err(syn::Error::new(
+                span,
+                format!("fake requires `{name}` to take the key as its only argument"),
+            ))
+        }
+        (FakeOp::Insert | FakeOp::Update, ..) => {
+            return Err(syn::Error::new(
+                span,
+                format!("fake requires `{name}` to take a key and a value as arguments"),
+            ))
+        }
+    };
+
+    Ok(FakeMethod {
+        trait_fn,
+        op,
+        key,
+        value,
+    })
+}
@@ -11,12 +11,17 @@ use crate::generics::ImplIndirection;
 use crate::generics::TraitDependencyMode;
 use crate::idents::CrateIdents;
 use crate::input::FnInputMode;
+use crate::metrics_codegen::CallTimer;
 use crate::opt::AsyncStrategy;
+use crate::opt::Backoff;
+use crate::opt::CacheOpt;
 use crate::opt::Mockable;
 use crate::opt::Opts;
+use crate::opt::RetryOpt;
 use crate::opt::SpanOpt;
 use crate::token_util::push_tokens;
-use crate::token_util::TokenPair;
+use crate::wrapper_codegen;
+use crate::wrapper_codegen::ImplWrapper;
 
 /// Generate impls that call standalone generic functions
 pub struct FnDelegationCodegen<'s, TR> {
@@ -29,6 +34,14 @@ pub struct FnDelegationCodegen<'s, TR> {
     pub fn_input_mode: &'s FnInputMode<'s>,
     pub trait_dependency_mode: &'s TraitDependencyMode<'s, 's>,
     pub use_associated_future: generics::UseAssociatedFuture,
+    /// Caching of the delegation from `Impl<T>`, see the `cache` option
+    pub cache: Option<&'s CacheOpt>,
+    /// Retrying of the delegation, see the `retry` option
+    pub retry: Option<&'s SpanOpt<RetryOpt>>,
+    /// Timeout of the delegation, see the `timeout_ms` option
+    pub timeout_ms: Option<&'s SpanOpt<syn::LitInt>>,
+    /// Metrics instrumentation of the delegation from `Impl<T>`, see the `metrics` option
+    pub metrics: Option<&'s SpanOpt<bool>>,
 }
 
 impl<'s, TR: ToTokens> FnDelegationCodegen<'s, TR> {
@@ -43,27 +56,114 @@ impl<'s, TR: ToTokens> FnDelegationCodegen<'s, TR> {
     /// }
     /// ```
     ///
-    pub fn gen_impl_block(&self, trait_fns: &[TraitFn]) -> TokenStream {
+    pub fn gen_impl_block(&self, trait_fns: &[TraitFn]) -> syn::Result<TokenStream> {
+        self.gen_impl_block_for_target(trait_fns, DelegationTarget::Impl)
+    }
+
+    ///
+    /// Generate the feature-gated impls for the wrappers around `Impl<T>`,
+    /// `::entrait::overrides::Overrides<T>` and `::entrait::scoped::Scoped<T, C>`.
+    ///
+    /// These are only needed when the trait is implemented for `Impl<T>`, i.e. when it is mockable.
+    /// Otherwise the trait is implemented generically for any `T`, including the wrappers.
+    ///
+    pub fn gen_wrapper_impl_blocks(
+        &self,
+        trait_fns: &[TraitFn],
+    ) -> syn::Result<Option<TokenStream>> {
+        if !matches!(self.trait_dependency_mode, TraitDependencyMode::Generic(_))
+            || !matches!(self.impl_indirection, ImplIndirection::None)
+            || !self.opts.mockable().yes()
+        {
+            return Ok(None);
+        }
+
+        let is_overridable = wrapper_codegen::detect_overridable(
+            self.opts.async_strategy(),
+            self.trait_generics,
+            trait_fns,
+        );
+
+        let mut stream = TokenStream::new();
+        for wrapper in ImplWrapper::ALL {
+            let (target, opt_overridable_impl) = match wrapper {
+                ImplWrapper::Overrides => (
+                    DelegationTarget::Overrides(is_overridable),
+                    if is_overridable.0 {
+                        Some(wrapper_codegen::OverridableImpl {
+                            crate_idents: self.crate_idents,
+                            trait_ref: self.trait_ref,
+                            span: self.trait_span,
+                        })
+                    } else {
+                        None
+                    },
+                ),
+                ImplWrapper::Scoped => (DelegationTarget::Scoped, None),
+            };
+            let impl_block = self.gen_impl_block_for_target(trait_fns, target)?;
+
+            push_tokens!(
+                &mut stream,
+                wrapper_codegen::FeatureGated {
+                    wrapper,
+                    crate_idents: self.crate_idents,
+                    span: self.trait_span,
+                    tokens: quote! {
+                        #opt_overridable_impl
+                        #impl_block
+                    },
+                }
+            );
+        }
+
+        Ok(Some(stream))
+    }
+
+    fn gen_impl_block_for_target(
+        &self,
+        trait_fns: &[TraitFn],
+        target: DelegationTarget,
+    ) -> syn::Result<TokenStream> {
         let async_trait_attribute =
             attributes::opt_async_trait_attr(self.opts, self.crate_idents, trait_fns.iter());
-        let params = self.trait_generics.impl_params(
+        let mut params = self.trait_generics.impl_params(
             self.trait_dependency_mode,
             self.use_associated_future,
             generics::has_any_self_by_value(trait_fns.iter().map(|trait_fn| trait_fn.sig())),
         );
+        if let (DelegationTarget::Scoped, TraitDependencyMode::Generic(idents)) =
+            (target, self.trait_dependency_mode)
+        {
+            params = params.with_scope_c(&idents.scope_c);
+        }
         let args = self.trait_generics.arguments(&self.impl_indirection);
         let self_ty = SelfTy {
             trait_dependency_mode: self.trait_dependency_mode,
             impl_indirection: &self.impl_indirection,
             mockable: self.opts.mockable(),
+            target,
             span: self.trait_span,
         };
-        let where_clause = self.trait_generics.impl_where_clause(
+        let (opt_cache, _) = self.impl_only_opts(target);
+        let mut where_clause = self.trait_generics.impl_where_clause(
             trait_fns,
             self.trait_dependency_mode,
             &self.impl_indirection,
             self.trait_span,
         );
+        if opt_cache.is_some() {
+            let entrait = &self.crate_idents.entrait;
+            where_clause = where_clause.with_predicate(quote_spanned! { self.trait_span=>
+                Self: ::#entrait::cache::HasCache
+            });
+        }
+        if self.retry.is_some() || self.timeout_ms.is_some() {
+            let entrait = &self.crate_idents.entrait;
+            where_clause = where_clause.with_predicate(quote_spanned! { self.trait_span=>
+                Self: ::#entrait::runtime::HasRuntime
+            });
+        }
 
         let opt_inline_attr = if !matches!(&self.impl_indirection, ImplIndirection::Dynamic { .. })
         {
@@ -72,43 +172,58 @@ impl<'s, TR: ToTokens> FnDelegationCodegen<'s, TR> {
             None
         };
 
-        let opt_self_scoping = if let FnInputMode::ImplBlock(ty) = self.fn_input_mode {
-            Some(TokenPair(
-                syn::token::SelfType(ty.span()),
-                syn::token::PathSep(ty.span()),
-            ))
-        } else {
-            None
+        let opt_self_scoping = match self.fn_input_mode {
+            FnInputMode::ImplBlock(ty) => Some(quote_spanned! { ty.span()=> Self:: }),
+            FnInputMode::ImplBlockApi(self_path) => Some(quote! { #self_path:: }),
+            _ => None,
         };
 
-        let items = trait_fns.iter().map(|trait_fn| {
-            let associated_fut_impl = &trait_fn.entrait_sig.associated_fut_impl(
-                self.impl_indirection.to_trait_indirection(),
-                self.crate_idents,
-            );
+        let items = trait_fns
+            .iter()
+            .map(|trait_fn| {
+                let associated_fut_impl = &trait_fn.entrait_sig.associated_fut_impl(
+                    self.impl_indirection.to_trait_indirection(),
+                    self.crate_idents,
+                );
 
-            let fn_item = self.gen_delegating_fn_item(
-                trait_fn,
-                self.trait_span,
-                opt_inline_attr.as_ref(),
-                &opt_self_scoping,
-            );
+                let opt_override_lookup = match target {
+                    DelegationTarget::Overrides(overridable) if overridable.0 => {
+                        Some(wrapper_codegen::OverrideLookup {
+                            crate_idents: self.crate_idents,
+                            trait_ref: self.trait_ref,
+                            trait_fn,
+                            arguments: trait_fn.entrait_sig.arg_idents()?,
+                            span: self.trait_span,
+                        })
+                    }
+                    _ => None,
+                };
 
-            quote! {
-                #associated_fut_impl
-                #fn_item
-            }
-        });
+                let fn_item = self.gen_delegating_fn_item(
+                    trait_fn,
+                    self.trait_span,
+                    opt_inline_attr.as_ref(),
+                    &opt_self_scoping,
+                    &opt_override_lookup,
+                    target,
+                )?;
+
+                Ok(quote! {
+                    #associated_fut_impl
+                    #fn_item
+                })
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
 
         let trait_span = self.trait_span;
         let trait_ref = &self.trait_ref;
 
-        quote_spanned! { trait_span=>
+        Ok(quote_spanned! { trait_span=>
             #async_trait_attribute
             impl #params #trait_ref #args for #self_ty #where_clause {
                 #(#items)*
             }
-        }
+        })
     }
 
     /// Generate the fn (in the impl block) that calls the entraited fn
@@ -118,7 +233,10 @@ impl<'s, TR: ToTokens> FnDelegationCodegen<'s, TR> {
         span: Span,
         mut opt_inline_attr: Option<&TokenStream>,
         opt_self_scoping: &impl ToTokens,
-    ) -> TokenStream {
+        opt_override_lookup: &impl ToTokens,
+        target: DelegationTarget,
+    ) -> syn::Result<TokenStream> {
+        let (opt_cache, opt_metrics) = self.impl_only_opts(target);
         let entrait_sig = &trait_fn.entrait_sig;
         let trait_fn_sig = &trait_fn.sig();
         let deps = &trait_fn.deps;
@@ -132,19 +250,7 @@ impl<'s, TR: ToTokens> FnDelegationCodegen<'s, TR> {
             (_, Some(_), _) => Some(SelfArgComma(&self.impl_indirection, span)),
         };
 
-        let arguments = entrait_sig
-            .sig
-            .inputs
-            .iter()
-            .filter_map(|fn_arg| match fn_arg {
-                syn::FnArg::Receiver(_) => None,
-                syn::FnArg::Typed(pat_type) => match pat_type.pat.as_ref() {
-                    syn::Pat::Ident(pat_ident) => Some(&pat_ident.ident),
-                    _ => {
-                        panic!("Found a non-ident pattern, this should be handled in signature.rs")
-                    }
-                },
-            });
+        let arguments = entrait_sig.arg_idents()?;
 
         let mut opt_dot_await = trait_fn.opt_dot_await(span);
         if entrait_sig.associated_fut.is_some() {
@@ -160,11 +266,175 @@ impl<'s, TR: ToTokens> FnDelegationCodegen<'s, TR> {
             opt_inline_attr = None;
         }
 
-        quote_spanned! { span=>
+        let mut call = quote_spanned! { span=>
+            #opt_self_scoping #fn_ident(#opt_self_comma #(#arguments),*) #opt_dot_await
+        };
+        if self.retry.is_some() || self.timeout_ms.is_some() {
+            call = self.gen_resilient_call(
+                quote_spanned! { span=>
+                    #opt_self_scoping #fn_ident(#opt_self_comma #(#arguments),*)
+                },
+                &arguments,
+                span,
+            );
+        }
+        if let Some(boxed_return) = entrait_sig.boxed_return {
+            let entrait = &self.crate_idents.entrait;
+            let box_ident = boxed_return.box_ident();
+            call = quote_spanned! { span=>
+                ::#entrait::boxed::#box_ident::new(#call)
+            };
+        }
+        let body = match opt_cache {
+            Some(cache) => {
+                let entrait = &self.crate_idents.entrait;
+                let core = &self.crate_idents.core;
+                let fn_name = syn::LitStr::new(&fn_ident.to_string(), span);
+                let ttl = match &cache.ttl_secs {
+                    Some(ttl_secs) => quote_spanned! { cache.span=>
+                        ::#core::option::Option::Some(::#core::time::Duration::from_secs(#ttl_secs))
+                    },
+                    None => quote_spanned! { cache.span=> ::#core::option::Option::None },
+                };
+
+                quote_spanned! { span=>
+                    let __cache_key = ::#entrait::cache::CacheKey::new(
+                        ::#core::concat!(::#core::module_path!(), "::", #fn_name),
+                        (#(#arguments.to_owned(),)*),
+                    );
+                    if let ::#core::option::Option::Some(value) =
+                        ::#entrait::cache::HasCache::entrait_cache(self).get(&__cache_key)
+                    {
+                        return value;
+                    }
+                    let value = #call;
+                    ::#entrait::cache::HasCache::entrait_cache(self).insert(
+                        __cache_key,
+                        ::#core::clone::Clone::clone(&value),
+                        #ttl,
+                    );
+                    value
+                }
+            }
+            None => call,
+        };
+        let opt_call_timer = opt_metrics.map(|SpanOpt(_, metrics_span)| CallTimer {
+            crate_idents: self.crate_idents,
+            trait_name: self.trait_ref.to_token_stream().to_string(),
+            fn_ident: &trait_fn.sig().ident,
+            span: *metrics_span,
+        });
+
+        Ok(quote_spanned! { span=>
             #opt_inline_attr
             #trait_fn_sig {
-                #opt_self_scoping #fn_ident(#opt_self_comma #(#arguments),*) #opt_dot_await
+                #opt_call_timer
+                #opt_override_lookup
+                #body
             }
+        })
+    }
+}
+
+impl<'s, TR> FnDelegationCodegen<'s, TR> {
+    /// The `cache` and `metrics` options, which only apply to the delegation from `Impl<T>`
+    fn impl_only_opts(
+        &self,
+        target: DelegationTarget,
+    ) -> (Option<&'s CacheOpt>, Option<&'s SpanOpt<bool>>) {
+        match target {
+            DelegationTarget::Impl => (self.cache, self.metrics),
+            _ => (None, None),
+        }
+    }
+
+    /// Wrap the future returned by `invocation` according to the `timeout_ms` and `retry` options
+    fn gen_resilient_call(
+        &self,
+        invocation: TokenStream,
+        arguments: &[&syn::Ident],
+        span: Span,
+    ) -> TokenStream {
+        let entrait = &self.crate_idents.entrait;
+        let core = &self.crate_idents.core;
+
+        let attempt = match self.timeout_ms {
+            Some(SpanOpt(timeout_ms, timeout_span)) => quote_spanned! { *timeout_span=>
+                ::#entrait::runtime::timeout(
+                    __runtime,
+                    ::#core::time::Duration::from_millis(#timeout_ms),
+                    #invocation,
+                ).await
+            },
+            None => quote_spanned! { span=> #invocation.await },
+        };
+
+        let call = match self.retry {
+            Some(SpanOpt(retry, retry_span)) => {
+                let max = &retry.max;
+                let delay = match &retry.delay_ms {
+                    Some(delay_ms) => quote_spanned! { *retry_span=>
+                        ::#core::time::Duration::from_millis(#delay_ms)
+                    },
+                    None => quote_spanned! { *retry_span=>
+                        ::#core::time::Duration::from_millis(100)
+                    },
+                };
+                let backoff = match retry.backoff {
+                    Backoff::None => quote_spanned! { *retry_span=>
+                        ::#entrait::runtime::Backoff::None
+                    },
+                    Backoff::Fixed => quote_spanned! { *retry_span=>
+                        ::#entrait::runtime::Backoff::Fixed(#delay)
+                    },
+                    Backoff::Exponential => quote_spanned! { *retry_span=>
+                        ::#entrait::runtime::Backoff::Exponential(#delay)
+                    },
+                };
+
+                quote_spanned! { *retry_span=>
+                    ::#entrait::runtime::retry(
+                        __runtime,
+                        ::#entrait::runtime::RetryPolicy {
+                            max_retries: #max,
+                            backoff: #backoff,
+                        },
+                        || {
+                            #(let #arguments = ::#core::clone::Clone::clone(&#arguments);)*
+                            async move { #attempt }
+                        },
+                    ).await
+                }
+            }
+            None => attempt,
+        };
+
+        quote_spanned! { span=>
+            {
+                let __runtime = ::#entrait::runtime::HasRuntime::entrait_runtime(self);
+                #call
+            }
+        }
+    }
+}
+
+/// The type receiving the delegating impl.
+#[derive(Clone, Copy)]
+enum DelegationTarget {
+    /// `Impl<T>`, or `T` in case the trait is not mockable
+    Impl,
+    /// `::entrait::overrides::Overrides<T>`
+    Overrides(wrapper_codegen::IsOverridable),
+    /// `::entrait::scoped::Scoped<T, C>`
+    Scoped,
+}
+
+impl DelegationTarget {
+    fn wrapper(self) -> Option<ImplWrapper> {
+        match self {
+            Self::Impl => None,
+            Self::Overrides(_) => Some(ImplWrapper::Overrides),
+            Self::Scoped => Some(ImplWrapper::Scoped),
         }
     }
 }
@@ -173,6 +443,7 @@ struct SelfTy<'g, 'c> {
     trait_dependency_mode: &'g TraitDependencyMode<'g, 'c>,
     impl_indirection: &'g ImplIndirection<'g>,
     mockable: Mockable,
+    target: DelegationTarget,
     span: Span,
 }
 
@@ -181,7 +452,9 @@ impl<'g, 'c> quote::ToTokens for SelfTy<'g, 'c> {
         match &self.trait_dependency_mode {
             TraitDependencyMode::Generic(idents) => match self.impl_indirection {
                 ImplIndirection::None => {
-                    if self.mockable.yes() {
+                    if let Some(wrapper) = self.target.wrapper() {
+                        push_tokens!(stream, wrapper.path(idents, self.span))
+                    } else if self.mockable.yes() {
                         push_tokens!(stream, idents.impl_path(self.span))
                     } else {
                         push_tokens!(stream, idents.impl_t)
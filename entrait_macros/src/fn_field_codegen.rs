@@ -0,0 +1,156 @@
+//! Code generation for the `delegate_by = fn_field` delegation mode.
+//!
+//! Generates an accessor trait with one method per method of the delegating trait,
+//! returning the closure the application stores for it.
+
+use proc_macro2::TokenStream;
+use quote::quote_spanned;
+use syn::spanned::Spanned;
+use syn::visit_mut::VisitMut;
+
+use crate::analyze_generics::TraitFn;
+use crate::generics::TraitGenerics;
+use crate::idents::CrateIdents;
+
+pub struct FnFieldCodegen<'s> {
+    pub crate_idents: &'s CrateIdents,
+    pub accessor_visibility: &'s syn::Visibility,
+    pub trait_ident: &'s syn::Ident,
+    pub trait_generics: &'s TraitGenerics,
+    pub accessor_ident: &'s syn::Ident,
+}
+
+impl<'s> FnFieldCodegen<'s> {
+    ///
+    /// Generate code like
+    ///
+    /// ```no_compile
+    /// pub trait ClockFns {
+    ///     fn now(&self) -> &dyn Fn(Arg) -> Output;
+    /// }
+    /// ```
+    ///
+    pub fn gen_accessor_trait(&self, trait_fns: &[TraitFn]) -> syn::Result<TokenStream> {
+        let span = self.accessor_ident.span();
+
+        if !self.trait_generics.params.is_empty() {
+            return Err(syn::Error::new(
+                span,
+                "delegate_by = fn_field does not support generic traits",
+            ));
+        }
+
+        let accessor_fns = trait_fns
+            .iter()
+            .map(|trait_fn| self.gen_accessor_fn(trait_fn))
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        let vis = self.accessor_visibility;
+        let accessor_ident = self.accessor_ident;
+        let doc = format!(
+            "Access to the closures implementing [{}] for `Impl<Self>`.",
+            self.trait_ident
+        );
+
+        Ok(quote_spanned! { span=>
+            #[doc = #doc]
+            #vis trait #accessor_ident {
+                #(#accessor_fns)*
+            }
+        })
+    }
+
+    fn gen_accessor_fn(&self, trait_fn: &TraitFn) -> syn::Result<TokenStream> {
+        let sig = trait_fn.sig();
+        let span = sig.ident.span();
+
+        if trait_fn.originally_async {
+            return Err(syn::Error::new(
+                span,
+                "delegate_by = fn_field does not support async methods",
+            ));
+        }
+        if !sig.generics.params.is_empty() {
+            return Err(syn::Error::new(
+                sig.generics.span(),
+                "delegate_by = fn_field does not support generic methods",
+            ));
+        }
+
+        let mut inputs = sig.inputs.iter();
+        match inputs.next() {
+            Some(syn::FnArg::Receiver(receiver))
+                if receiver.reference.is_some() && receiver.mutability.is_none() => {}
+            _ => {
+                return Err(syn::Error::new(
+                    span,
+                    "delegate_by = fn_field requires methods taking `&self`",
+                ))
+            }
+        }
+
+        let arg_types = inputs
+            .map(|fn_arg| match fn_arg {
+                syn::FnArg::Typed(pat_type) => match pat_type.ty.as_ref() {
+                    syn::Type::ImplTrait(impl_trait) => Err(syn::Error::new(
+                        impl_trait.span(),
+                        "delegate_by = fn_field does not support `impl Trait` arguments",
+                    )),
+                    ty => Ok(ty),
+                },
+                syn::FnArg::Receiver(receiver) => {
+                    Err(syn::Error::new(receiver.span(), "Unexpected receiver"))
+                }
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        let core = &self.crate_idents.core;
+        let attrs = &trait_fn.attrs;
+        let fn_ident = &sig.ident;
+        let output = &sig.output;
+
+        // Elided output lifetimes of the method borrow from `self`,
+        // which a closure stored by the application can't return
+        let mut elided_lifetimes = ElidedLifetimes(None);
+        elided_lifetimes.visit_return_type_mut(&mut output.clone());
+        if let Some(span) = elided_lifetimes.0 {
+            return Err(syn::Error::new(
+                span,
+                "delegate_by = fn_field does not support outputs borrowing from `self`. Use an explicit lifetime like `'static`",
+            ));
+        }
+
+        Ok(quote_spanned! { span=>
+            #(#attrs)*
+            fn #fn_ident(&self) -> &dyn ::#core::ops::Fn(#(#arg_types),*) #output;
+        })
+    }
+}
+
+/// Finds the first elided lifetime of a type
+struct ElidedLifetimes(Option<proc_macro2::Span>);
+
+impl VisitMut for ElidedLifetimes {
+    fn visit_lifetime_mut(&mut self, lifetime: &mut syn::Lifetime) {
+        if lifetime.ident == "_" {
+            self.0.get_or_insert(lifetime.span());
+        }
+    }
+
+    fn visit_type_reference_mut(&mut self, reference: &mut syn::TypeReference) {
+        if reference.lifetime.is_none() {
+            self.0.get_or_insert(reference.span());
+        }
+        syn::visit_mut::visit_type_reference_mut(self, reference);
+    }
+
+    // Lifetimes of function types are higher-ranked, and left alone
+
+    fn visit_type_bare_fn_mut(&mut self, _: &mut syn::TypeBareFn) {}
+
+    fn visit_parenthesized_generic_arguments_mut(
+        &mut self,
+        _: &mut syn::ParenthesizedGenericArguments,
+    ) {
+    }
+}
@@ -5,6 +5,7 @@ use crate::{
     idents::GenericIdents,
     input::InputFn,
     opt::{AsyncStrategy, Opts, SpanOpt},
+    signature::impl_return::ImplReturn,
     token_util::{push_tokens, EmptyToken, Punctuator, TokenPair},
 };
 
@@ -42,15 +43,16 @@ pub struct UseAssociatedFuture(pub bool);
 
 pub fn detect_use_associated_future<'i>(
     opts: &Opts,
-    input_fns: impl Iterator<Item = &'i InputFn>,
+    mut input_fns: impl Iterator<Item = &'i InputFn>,
 ) -> UseAssociatedFuture {
-    UseAssociatedFuture(matches!(
-        (
-            opts.async_strategy(),
-            has_any_async(input_fns.map(|input_fn| &input_fn.fn_sig))
-        ),
-        (SpanOpt(AsyncStrategy::AssociatedFuture, _), true)
-    ))
+    UseAssociatedFuture(match opts.async_strategy() {
+        // `impl Iterator` and `impl Stream` return types also become associated types
+        SpanOpt(AsyncStrategy::AssociatedFuture, _) => input_fns.any(|input_fn| {
+            input_fn.fn_sig.asyncness.is_some()
+                || ImplReturn::detect(&input_fn.fn_sig.output).is_some()
+        }),
+        _ => false,
+    })
 }
 
 pub fn has_any_async<'s>(mut signatures: impl Iterator<Item = &'s syn::Signature>) -> bool {
@@ -79,6 +81,37 @@ pub enum FnDeps {
     NoDeps,
 }
 
+impl FnDeps {
+    /// Whether the deps only consist of the request-scoped context (`impl entrait::scoped::ScopeContext<C>`),
+    /// which can't be provided by a mock, so such functions can't be unmocked.
+    ///
+    /// The trait is recognized by its full path, as imports are not visible to the macro.
+    pub fn is_scope_context(&self) -> bool {
+        match self {
+            Self::Generic { trait_bounds, .. } => {
+                !trait_bounds.is_empty()
+                    && trait_bounds.iter().all(|bound| match bound {
+                        syn::TypeParamBound::Trait(trait_bound) => {
+                            is_scope_context_path(&trait_bound.path)
+                        }
+                        _ => true,
+                    })
+            }
+            _ => false,
+        }
+    }
+}
+
+/// `entrait::scoped::ScopeContext`, with or without a leading `::`
+fn is_scope_context_path(path: &syn::Path) -> bool {
+    let mut idents = path.segments.iter().map(|segment| &segment.ident);
+    matches!(
+        (idents.next(), idents.next(), idents.next(), idents.next()),
+        (Some(krate), Some(module), Some(ident), None)
+            if krate == "entrait" && module == "scoped" && ident == "ScopeContext"
+    )
+}
+
 pub enum TraitDependencyMode<'t, 'c> {
     Generic(GenericIdents<'c>),
     Concrete(&'t syn::Type),
@@ -95,6 +128,7 @@ impl TraitGenerics {
         ParamsGenerator {
             params: &self.params,
             impl_t: None,
+            scope_c: None,
             use_associated_future: UseAssociatedFuture(false),
             takes_self_by_value: TakesSelfByValue(false),
         }
@@ -118,6 +152,7 @@ impl TraitGenerics {
                 TraitDependencyMode::Generic(idents) => Some(&idents.impl_t),
                 TraitDependencyMode::Concrete(_) => None,
             },
+            scope_c: None,
             use_associated_future,
             takes_self_by_value,
         }
@@ -132,6 +167,7 @@ impl TraitGenerics {
         ParamsGenerator {
             params: &self.params,
             impl_t: Some(&idents.impl_t),
+            scope_c: None,
             use_associated_future,
             takes_self_by_value,
         }
@@ -149,6 +185,7 @@ impl TraitGenerics {
             trait_dependency_mode,
             impl_indirection,
             trait_fns,
+            extra_predicates: vec![],
             span,
         }
     }
@@ -192,13 +229,25 @@ impl<'g, 'c> quote::ToTokens for ImplPath<'g, 'c> {
 }
 
 // Params as in impl<..Param>
+#[derive(Clone)]
 pub struct ParamsGenerator<'g> {
     params: &'g syn::punctuated::Punctuated<syn::GenericParam, syn::token::Comma>,
     impl_t: Option<&'g syn::Ident>,
+    scope_c: Option<&'g syn::Ident>,
     use_associated_future: UseAssociatedFuture,
     takes_self_by_value: TakesSelfByValue,
 }
 
+impl<'g> ParamsGenerator<'g> {
+    /// Also declare the context param of `Scoped<T, C>`, with the same bounds as `T`.
+    pub fn with_scope_c(self, scope_c: &'g syn::Ident) -> Self {
+        Self {
+            scope_c: Some(scope_c),
+            ..self
+        }
+    }
+}
+
 impl<'g> quote::ToTokens for ParamsGenerator<'g> {
     fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
         let mut punctuator = Punctuator::new(
@@ -208,7 +257,7 @@ impl<'g> quote::ToTokens for ParamsGenerator<'g> {
             syn::token::Gt::default(),
         );
 
-        if let Some(impl_t) = &self.impl_t {
+        for impl_t in self.impl_t.iter().chain(self.scope_c.iter()) {
             punctuator.push_fn(|stream| {
                 push_tokens!(
                     stream,
@@ -304,9 +353,18 @@ pub struct ImplWhereClauseGenerator<'g, 's, 'c> {
     trait_dependency_mode: &'s TraitDependencyMode<'s, 'c>,
     impl_indirection: &'s ImplIndirection<'s>,
     trait_fns: &'s [TraitFn],
+    extra_predicates: Vec<TokenStream>,
     span: proc_macro2::Span,
 }
 
+impl<'g, 's, 'c> ImplWhereClauseGenerator<'g, 's, 'c> {
+    /// Add a predicate required by the generated impl, not by the trait
+    pub fn with_predicate(mut self, predicate: TokenStream) -> Self {
+        self.extra_predicates.push(predicate);
+        self
+    }
+}
+
 impl<'g, 's, 'c> quote::ToTokens for ImplWhereClauseGenerator<'g, 's, 'c> {
     fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
         let mut punctuator = Punctuator::new(
@@ -356,6 +414,10 @@ impl<'g, 's, 'c> quote::ToTokens for ImplWhereClauseGenerator<'g, 's, 'c> {
         for predicate in self.trait_where_predicates {
             punctuator.push(predicate);
         }
+
+        for predicate in &self.extra_predicates {
+            punctuator.push(predicate);
+        }
     }
 }
 
@@ -0,0 +1,359 @@
+//! The `graph!` macro, checking a group of entraited items for dependency cycles.
+//!
+//! A cycle between entrait traits makes the trait solver overflow when proving that `Impl<T>`
+//! implements one of them, which results in cryptic errors far away from the actual problem.
+//! The macro records the bounds of each dependency parameter, and reports cycles
+//! at the bound closing them, with the full path of traits and functions involved.
+
+use std::collections::HashMap;
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+
+use crate::analyze_generics::GenericsAnalyzer;
+use crate::entrait_fn::input_attr::EntraitFnAttr;
+use crate::generics::FnDeps;
+use crate::signature::InputSig;
+
+pub struct GraphInput {
+    items: Vec<syn::Item>,
+}
+
+impl Parse for GraphInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut items = vec![];
+        while !input.is_empty() {
+            items.push(input.parse()?);
+        }
+        Ok(Self { items })
+    }
+}
+
+/// A dependency of an entraited function on a trait, through a bound of its dependency parameter
+struct Edge {
+    from_trait: String,
+    fn_ident: syn::Ident,
+    to_trait: String,
+    span: Span,
+}
+
+#[derive(Default)]
+struct Graph {
+    /// Traits in order of definition, with the indices of their outgoing edges
+    traits: Vec<(String, Vec<usize>)>,
+    edges: Vec<Edge>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Visit {
+    NotVisited,
+    OnPath,
+    Done,
+}
+
+pub fn output_tokens(input: GraphInput) -> TokenStream {
+    let GraphInput { items } = input;
+    let mut graph = Graph::default();
+
+    let errors = match graph.collect(&items) {
+        Ok(()) => graph.find_cycles(),
+        Err(error) => Some(error),
+    };
+    let opt_compile_error = errors.map(syn::Error::into_compile_error);
+
+    quote! {
+        #(#items)*
+        #opt_compile_error
+    }
+}
+
+impl Graph {
+    fn collect(&mut self, items: &[syn::Item]) -> syn::Result<()> {
+        for item in items {
+            match item {
+                syn::Item::Fn(item_fn) => {
+                    if let Some(attr) = find_entrait_attr(&item_fn.attrs) {
+                        let attr: EntraitFnAttr = attr.parse_args()?;
+                        let trait_name = self.add_trait(&attr.trait_ident);
+                        self.add_fn(trait_name, &attr, &item_fn.sig)?;
+                    }
+                }
+                syn::Item::Mod(item_mod) => {
+                    let content = match &item_mod.content {
+                        Some((_, content)) => content,
+                        None => continue,
+                    };
+
+                    match find_entrait_attr(&item_mod.attrs) {
+                        Some(attr) => {
+                            let attr: EntraitFnAttr = attr.parse_args()?;
+                            let trait_name = self.add_trait(&attr.trait_ident);
+                            for item in content {
+                                match item {
+                                    syn::Item::Fn(item_fn)
+                                        if !matches!(item_fn.vis, syn::Visibility::Inherited) =>
+                                    {
+                                        self.add_fn(trait_name, &attr, &item_fn.sig)?;
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        }
+                        None => self.collect(content)?,
+                    }
+                }
+                syn::Item::Trait(item_trait) if find_entrait_attr(&item_trait.attrs).is_some() => {
+                    self.add_trait(&item_trait.ident);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn add_trait(&mut self, trait_ident: &syn::Ident) -> usize {
+        let trait_name = trait_ident.to_string();
+        match self.traits.iter().position(|(name, _)| *name == trait_name) {
+            Some(index) => index,
+            None => {
+                self.traits.push((trait_name, vec![]));
+                self.traits.len() - 1
+            }
+        }
+    }
+
+    fn add_fn(
+        &mut self,
+        trait_index: usize,
+        attr: &EntraitFnAttr,
+        sig: &syn::Signature,
+    ) -> syn::Result<()> {
+        let trait_bounds =
+            match GenericsAnalyzer::new().analyze_fn_deps(InputSig::new(sig), &attr.opts)? {
+                FnDeps::Generic { trait_bounds, .. } => trait_bounds,
+                FnDeps::Concrete(_) | FnDeps::NoDeps => return Ok(()),
+            };
+
+        for bound in trait_bounds {
+            if let syn::TypeParamBound::Trait(trait_bound) = &bound {
+                if let Some(last_segment) = trait_bound.path.segments.last() {
+                    self.edges.push(Edge {
+                        from_trait: self.traits[trait_index].0.clone(),
+                        fn_ident: sig.ident.clone(),
+                        to_trait: last_segment.ident.to_string(),
+                        span: bound.span(),
+                    });
+                    self.traits[trait_index].1.push(self.edges.len() - 1);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Depth-first search, reporting every edge back to a trait on the current path.
+    fn find_cycles(&self) -> Option<syn::Error> {
+        let trait_indices = self
+            .traits
+            .iter()
+            .enumerate()
+            .map(|(index, (name, _))| (name.as_str(), index))
+            .collect::<HashMap<_, _>>();
+        let mut visits = vec![Visit::NotVisited; self.traits.len()];
+        let mut path = vec![];
+        let mut errors: Option<syn::Error> = None;
+
+        for start in 0..self.traits.len() {
+            self.visit(start, &trait_indices, &mut visits, &mut path, &mut errors);
+        }
+
+        errors
+    }
+
+    fn visit(
+        &self,
+        trait_index: usize,
+        trait_indices: &HashMap<&str, usize>,
+        visits: &mut [Visit],
+        path: &mut Vec<usize>,
+        errors: &mut Option<syn::Error>,
+    ) {
+        if visits[trait_index] != Visit::NotVisited {
+            return;
+        }
+        visits[trait_index] = Visit::OnPath;
+
+        for edge_index in &self.traits[trait_index].1 {
+            let edge = &self.edges[*edge_index];
+            let to_index = match trait_indices.get(edge.to_trait.as_str()) {
+                Some(to_index) => *to_index,
+                // Not an entrait trait in this graph
+                None => continue,
+            };
+
+            path.push(*edge_index);
+            match visits[to_index] {
+                Visit::NotVisited => self.visit(to_index, trait_indices, visits, path, errors),
+                Visit::OnPath => {
+                    let cycle_start = path
+                        .iter()
+                        .position(|index| self.edges[*index].from_trait == edge.to_trait)
+                        .unwrap_or(0);
+                    let error =
+                        syn::Error::new(edge.span, self.cycle_message(&path[cycle_start..]));
+                    match errors {
+                        Some(errors) => errors.combine(error),
+                        None => *errors = Some(error),
+                    }
+                }
+                Visit::Done => {}
+            }
+            path.pop();
+        }
+
+        visits[trait_index] = Visit::Done;
+    }
+
+    fn cycle_message(&self, cycle: &[usize]) -> String {
+        let mut traits = cycle
+            .iter()
+            .map(|index| format!("`{}`", self.edges[*index].from_trait))
+            .collect::<Vec<_>>();
+        traits.push(format!("`{}`", self.edges[cycle[0]].from_trait));
+
+        let mut message = format!("dependency cycle: {}", traits.join(" -> "));
+        for index in cycle {
+            let edge = &self.edges[*index];
+            message.push_str(&format!(
+                "\n  `{}` ({}) depends on `{}`",
+                edge.fn_ident, edge.from_trait, edge.to_trait
+            ));
+        }
+        message.push_str("\nentrait traits can't depend on themselves, as `Impl<T>` would need to implement a trait in order to implement it");
+
+        message
+    }
+}
+
+/// The `#[entrait]` or `#[entrait_export]` attribute, also by its path like `#[entrait::entrait]`
+fn find_entrait_attr(attrs: &[syn::Attribute]) -> Option<&syn::Attribute> {
+    attrs.iter().find(|attr| is_entrait_path(attr.path()))
+}
+
+fn is_entrait_path(path: &syn::Path) -> bool {
+    let segments = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>();
+    let segments = segments.iter().map(String::as_str).collect::<Vec<_>>();
+
+    match (path.leading_colon.is_some(), segments.as_slice()) {
+        (false, [name]) | (_, ["entrait", name]) => matches!(*name, "entrait" | "entrait_export"),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cycle_messages(items: Vec<syn::Item>) -> Vec<String> {
+        let mut graph = Graph::default();
+        graph.collect(&items).unwrap();
+
+        match graph.find_cycles() {
+            Some(errors) => errors.into_iter().map(|error| error.to_string()).collect(),
+            None => vec![],
+        }
+    }
+
+    #[test]
+    fn should_report_self_cycle() {
+        let messages = cycle_messages(vec![syn::parse_quote! {
+            #[entrait(Foo)]
+            fn foo(deps: &impl Foo) {}
+        }]);
+
+        assert_eq!(1, messages.len());
+        assert!(messages[0]
+            .starts_with("dependency cycle: `Foo` -> `Foo`\n  `foo` (Foo) depends on `Foo`\n"));
+    }
+
+    #[test]
+    fn should_report_cycle_of_three_traits() {
+        let messages = cycle_messages(vec![
+            syn::parse_quote! {
+                #[entrait(Foo)]
+                fn foo(deps: &(impl Bar + Qux)) {}
+            },
+            syn::parse_quote! {
+                #[entrait(Bar)]
+                fn bar(deps: &impl Baz) {}
+            },
+            syn::parse_quote! {
+                #[entrait(Qux)]
+                fn qux(deps: &impl Baz) {}
+            },
+            syn::parse_quote! {
+                #[entrait(Baz)]
+                pub mod baz {
+                    pub fn baz<D: super::Foo>(deps: &D) {}
+                }
+            },
+        ]);
+
+        assert_eq!(1, messages.len());
+        assert!(messages[0].starts_with(
+            "dependency cycle: `Foo` -> `Bar` -> `Baz` -> `Foo`\n  `foo` (Foo) depends on `Bar`\n  `bar` (Bar) depends on `Baz`\n  `baz` (Baz) depends on `Foo`\n"
+        ));
+    }
+
+    #[test]
+    fn should_report_each_cycle_closing_bound() {
+        let messages = cycle_messages(vec![
+            syn::parse_quote! {
+                #[entrait(Foo)]
+                fn foo(deps: &(impl Foo + Bar)) {}
+            },
+            syn::parse_quote! {
+                #[entrait(Bar)]
+                fn bar(deps: &impl Foo) {}
+            },
+        ]);
+
+        assert_eq!(2, messages.len());
+        assert!(messages[0].starts_with("dependency cycle: `Foo` -> `Foo`\n"));
+        assert!(messages[1].starts_with("dependency cycle: `Foo` -> `Bar` -> `Foo`\n"));
+    }
+
+    #[test]
+    fn should_only_follow_entrait_attributes() {
+        let messages = cycle_messages(vec![
+            syn::parse_quote! {
+                #[entrait_like(Foo)]
+                fn foo(deps: &impl Bar) {}
+            },
+            syn::parse_quote! {
+                #[other::entrait(Bar)]
+                fn bar(deps: &impl Foo) {}
+            },
+        ]);
+        assert!(messages.is_empty());
+
+        let messages = cycle_messages(vec![
+            syn::parse_quote! {
+                #[::entrait::entrait_export(Foo)]
+                fn foo(deps: &impl Bar) {}
+            },
+            syn::parse_quote! {
+                #[entrait_export(Bar)]
+                fn bar(deps: &impl Foo) {}
+            },
+        ]);
+        assert_eq!(1, messages.len());
+    }
+}
@@ -0,0 +1,202 @@
+//! Code generation for the `grpc` option.
+//!
+//! Generates a gRPC server adapter forwarding to any deps type implementing the entraited trait,
+//! and a gRPC client implementing the trait.
+//! Both use the runtime support in `::entrait::__tonic`, which encodes messages using serde.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote_spanned};
+
+use crate::analyze_generics::TraitFn;
+use crate::generics::TraitGenerics;
+use crate::idents::CrateIdents;
+use crate::opt::Opts;
+use crate::remote_codegen::{RemoteMethod, RemoteMethodAnalyzer};
+
+pub struct GrpcCodegen<'s> {
+    pub opts: &'s Opts,
+    pub crate_idents: &'s CrateIdents,
+    pub trait_visibility: &'s syn::Visibility,
+    pub trait_ident: &'s syn::Ident,
+    pub trait_generics: &'s TraitGenerics,
+    pub prefix: &'s syn::Ident,
+    /// Whether the trait has a user-supplied `#[async_trait]` attribute
+    pub has_async_trait_attr: bool,
+}
+
+/// A trait method that can be called over gRPC.
+struct GrpcMethod<'s> {
+    method: RemoteMethod<'s>,
+    path: syn::LitStr,
+}
+
+impl<'s> GrpcCodegen<'s> {
+    ///
+    /// Generate code like
+    ///
+    /// ```no_compile
+    /// #[derive(Clone)]
+    /// pub struct FooGrpcServer<EntraitD>(pub EntraitD);
+    ///
+    /// impl<EntraitD: Foo + ..., EntraitB> Service<http::Request<EntraitB>> for FooGrpcServer<EntraitD> { .. }
+    ///
+    /// #[derive(Clone)]
+    /// pub struct FooGrpcClient<EntraitT>(pub EntraitT);
+    ///
+    /// impl<EntraitT: ::entrait::__tonic::GrpcTransport> Foo for FooGrpcClient<EntraitT> { .. }
+    /// ```
+    ///
+    pub fn gen_server_and_client(
+        &self,
+        trait_fns: &'s [TraitFn],
+        impl_attrs: &TokenStream,
+    ) -> syn::Result<TokenStream> {
+        let span = self.prefix.span();
+        let methods = self.analyze_methods(trait_fns)?;
+
+        let server = self.gen_server(&methods, span);
+        let client = self.gen_client(&methods, impl_attrs, span);
+
+        Ok(quote_spanned! { span=>
+            #server
+            #client
+        })
+    }
+
+    fn analyze_methods(&self, trait_fns: &'s [TraitFn]) -> syn::Result<Vec<GrpcMethod<'s>>> {
+        Ok(RemoteMethodAnalyzer {
+            option_name: "grpc",
+            transport_error: "tonic::Status",
+            opts: self.opts,
+            trait_generics: self.trait_generics,
+            has_async_trait_attr: self.has_async_trait_attr,
+            span: self.prefix.span(),
+        }
+        .analyze(trait_fns)?
+        .into_iter()
+        .map(|method| {
+            let fn_ident = &method.trait_fn.sig().ident;
+            GrpcMethod {
+                path: syn::LitStr::new(
+                    &format!("/{}/{}", self.trait_ident, fn_ident),
+                    fn_ident.span(),
+                ),
+                method,
+            }
+        })
+        .collect())
+    }
+
+    fn gen_server(&self, methods: &[GrpcMethod], span: Span) -> TokenStream {
+        let entrait = &self.crate_idents.entrait;
+        let core = &self.crate_idents.core;
+        let vis = self.trait_visibility;
+        let trait_ident = self.trait_ident;
+        let server_ident = format_ident!("{}Server", self.prefix);
+        let service_name = syn::LitStr::new(&self.trait_ident.to_string(), span);
+
+        let arms = methods.iter().map(|method| {
+            let path = &method.path;
+            let fn_ident = &method.method.trait_fn.sig().ident;
+            let arg_idents = &method.method.arg_idents;
+            let arg_types = &method.method.arg_types;
+
+            quote_spanned! { span=>
+                #path => ::#entrait::__tonic::serve_unary(
+                    request,
+                    move |(#(#arg_idents,)*): (#(#arg_types,)*)| async move {
+                        <EntraitD as #trait_ident>::#fn_ident(&deps, #(#arg_idents),*).await
+                    },
+                ),
+            }
+        });
+
+        quote_spanned! { span=>
+            #[derive(Clone)]
+            #vis struct #server_ident<EntraitD>(pub EntraitD);
+
+            impl<EntraitD> ::#entrait::__tonic::NamedService for #server_ident<EntraitD> {
+                const NAME: &'static str = #service_name;
+            }
+
+            impl<EntraitD, EntraitB> ::#entrait::__tonic::Service<::#entrait::__tonic::http::Request<EntraitB>>
+                for #server_ident<EntraitD>
+            where
+                EntraitD: #trait_ident
+                    + ::#core::clone::Clone
+                    + ::#core::marker::Send
+                    + ::#core::marker::Sync
+                    + 'static,
+                EntraitB: ::#entrait::__tonic::Body + ::#core::marker::Send + 'static,
+                EntraitB::Error: ::#core::convert::Into<::#entrait::__tonic::StdError> + ::#core::marker::Send + 'static,
+            {
+                type Response = ::#entrait::__tonic::http::Response<::#entrait::__tonic::BoxBody>;
+                type Error = ::#core::convert::Infallible;
+                type Future = ::#entrait::__tonic::BoxFuture<Self::Response, Self::Error>;
+
+                fn poll_ready(
+                    &mut self,
+                    _: &mut ::#core::task::Context<'_>,
+                ) -> ::#core::task::Poll<::#core::result::Result<(), Self::Error>> {
+                    ::#core::task::Poll::Ready(::#core::result::Result::Ok(()))
+                }
+
+                fn call(&mut self, request: ::#entrait::__tonic::http::Request<EntraitB>) -> Self::Future {
+                    let deps = ::#core::clone::Clone::clone(&self.0);
+                    match request.uri().path() {
+                        #(#arms)*
+                        _ => ::#entrait::__tonic::unimplemented(),
+                    }
+                }
+            }
+        }
+    }
+
+    fn gen_client(
+        &self,
+        methods: &[GrpcMethod],
+        impl_attrs: &TokenStream,
+        span: Span,
+    ) -> TokenStream {
+        let entrait = &self.crate_idents.entrait;
+        let core = &self.crate_idents.core;
+        let vis = self.trait_visibility;
+        let trait_ident = self.trait_ident;
+        let client_ident = format_ident!("{}Client", self.prefix);
+
+        let items = methods.iter().map(|method| {
+            let sig = method.method.trait_fn.sig();
+            let attrs = &method.method.trait_fn.attrs;
+            let path = &method.path;
+            let arg_idents = &method.method.arg_idents;
+            let arg_types = &method.method.arg_types;
+            let output_ty = method.method.output_ty(span);
+
+            quote_spanned! { span=>
+                #(#attrs)*
+                #sig {
+                    match ::#entrait::__tonic::GrpcTransport::unary::<(#(#arg_types,)*), #output_ty>(
+                        &self.0,
+                        #path,
+                        (#(#arg_idents,)*),
+                    ).await {
+                        ::#core::result::Result::Ok(output) => output,
+                        ::#core::result::Result::Err(status) => {
+                            ::#core::result::Result::Err(::#core::convert::From::from(status))
+                        }
+                    }
+                }
+            }
+        });
+
+        quote_spanned! { span=>
+            #[derive(Clone)]
+            #vis struct #client_ident<EntraitT>(pub EntraitT);
+
+            #impl_attrs
+            impl<EntraitT: ::#entrait::__tonic::GrpcTransport> #trait_ident for #client_ident<EntraitT> {
+                #(#items)*
+            }
+        }
+    }
+}
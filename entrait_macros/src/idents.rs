@@ -24,6 +24,9 @@ pub struct GenericIdents<'c> {
 
     /// The "T" in `Impl<T>`
     pub impl_t: syn::Ident,
+
+    /// The "C" in `Scoped<T, C>`
+    pub scope_c: syn::Ident,
 }
 
 impl<'c> GenericIdents<'c> {
@@ -32,6 +35,51 @@ impl<'c> GenericIdents<'c> {
             crate_idents,
             impl_self: syn::Ident::new("Impl", span),
             impl_t: syn::Ident::new("EntraitT", span),
+            scope_c: syn::Ident::new("EntraitC", span),
+        }
+    }
+}
+
+/// The upper camel case of a snake case identifier, i.e. `fetch_planet` becomes `FetchPlanet`
+pub fn upper_camel_case(ident: &syn::Ident) -> syn::Ident {
+    let name = ident.to_string();
+    let camel_name = name
+        .trim_start_matches("r#")
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect::<String>();
+
+    syn::Ident::new(&camel_name, ident.span())
+}
+
+/// The snake case of an upper camel case identifier, i.e. `InMemoryRepository` becomes `in_memory_repository`
+pub fn snake_case(ident: &syn::Ident) -> syn::Ident {
+    let name = ident.to_string();
+    let chars = name.trim_start_matches("r#").chars().collect::<Vec<_>>();
+    let mut snake_name = String::new();
+
+    for (index, char) in chars.iter().enumerate() {
+        if char.is_uppercase() && index > 0 {
+            let prev = chars[index - 1];
+            let next_is_lower = chars
+                .get(index + 1)
+                .map_or(false, |next| next.is_lowercase());
+            if prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_is_lower)
+            {
+                snake_name.push('_');
+            }
         }
+        snake_name.extend(char.to_lowercase());
     }
+
+    syn::Ident::new(&snake_name, ident.span())
 }
@@ -0,0 +1,213 @@
+//! The `inject_deps` attribute, letting async-graphql resolvers take `deps: &impl Trait`.
+//!
+//! The attribute is put on a generic impl block of an object type, like `impl<D> Query<D>`, above `#[Object]`.
+//! Each `deps: &impl Trait` parameter is removed from its resolver, and the deps are instead looked up
+//! as `&D` in the resolver context, with `D: Trait` added to the where clause of the impl block.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+
+use crate::idents::CrateIdents;
+
+pub struct InjectDepsAttr {
+    /// The type parameter of the deps, like `D`
+    deps_param: Option<syn::Ident>,
+}
+
+impl Parse for InjectDepsAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let deps_param = if input.is_empty() {
+            None
+        } else {
+            Some(input.parse()?)
+        };
+
+        Ok(Self { deps_param })
+    }
+}
+
+pub fn output_tokens(
+    attr: InjectDepsAttr,
+    mut item_impl: syn::ItemImpl,
+) -> syn::Result<TokenStream> {
+    let span = item_impl.impl_token.span;
+    let deps_param = match attr.deps_param {
+        Some(deps_param) => deps_param,
+        None => single_type_param(&item_impl.generics).ok_or_else(|| {
+            syn::Error::new(
+                item_impl.generics.span(),
+                "inject_deps requires a type parameter for the deps, like `impl<D> Query<D>`, or naming it, like `#[inject_deps(D)]`",
+            )
+        })?,
+    };
+
+    let crate_idents = CrateIdents::new(span);
+    let mut predicates = vec![];
+
+    for item in &mut item_impl.items {
+        if let syn::ImplItem::Fn(method) = item {
+            if let Some(bounds) = inject_deps(method, &deps_param, &crate_idents)? {
+                predicates.push(quote! { #deps_param: #bounds });
+            }
+        }
+    }
+
+    if predicates.is_empty() {
+        return Err(syn::Error::new(
+            span,
+            "inject_deps requires resolvers taking `deps: &impl Trait`",
+        ));
+    }
+
+    let core = &crate_idents.core;
+    let where_clause = item_impl.generics.make_where_clause();
+    for predicate in predicates {
+        where_clause
+            .predicates
+            .push(syn::parse_quote! { #predicate });
+    }
+    where_clause.predicates.push(syn::parse_quote! {
+        #deps_param: ::#core::marker::Send + ::#core::marker::Sync + 'static
+    });
+
+    Ok(quote! { #item_impl })
+}
+
+fn single_type_param(generics: &syn::Generics) -> Option<syn::Ident> {
+    let mut type_params = generics.type_params();
+    match (type_params.next(), type_params.next()) {
+        (Some(type_param), None) => Some(type_param.ident.clone()),
+        _ => None,
+    }
+}
+
+/// Replace the `deps: &impl Trait` parameter of the resolver, returning the bounds of the deps
+fn inject_deps(
+    method: &mut syn::ImplItemFn,
+    deps_param: &syn::Ident,
+    crate_idents: &CrateIdents,
+) -> syn::Result<Option<syn::punctuated::Punctuated<syn::TypeParamBound, syn::token::Plus>>> {
+    let mut deps = None;
+    let mut inputs = syn::punctuated::Punctuated::<syn::FnArg, syn::token::Comma>::new();
+
+    for fn_arg in std::mem::take(&mut method.sig.inputs) {
+        match deps_impl_trait(&fn_arg) {
+            Some((pat, bounds)) if deps.is_none() => deps = Some((pat, bounds)),
+            Some(_) => {
+                return Err(syn::Error::new(
+                    fn_arg.span(),
+                    "inject_deps supports only one `&impl Trait` parameter per resolver",
+                ))
+            }
+            None => inputs.push(fn_arg),
+        }
+    }
+    method.sig.inputs = inputs;
+
+    let (deps_pat, bounds) = match deps {
+        Some(deps) => deps,
+        None => return Ok(None),
+    };
+
+    let entrait = &crate_idents.entrait;
+    let span = deps_pat.span();
+
+    // async-graphql recognizes the context as the parameter after `self`, by its `Context` type name
+    let ctx_ident = match method.sig.inputs.iter().nth(1) {
+        Some(syn::FnArg::Typed(pat_type)) if is_context(&pat_type.ty) => {
+            match pat_type.pat.as_ref() {
+                syn::Pat::Ident(pat_ident) => pat_ident.ident.clone(),
+                pat => {
+                    return Err(syn::Error::new(
+                        pat.span(),
+                        "inject_deps requires the context parameter to be named",
+                    ))
+                }
+            }
+        }
+        _ => {
+            let ctx_ident = syn::Ident::new("__entrait_ctx", Span::call_site());
+            method.sig.inputs.insert(
+                1,
+                syn::parse_quote_spanned! { span=>
+                    #ctx_ident: &::#entrait::integration::async_graphql::__private::Context<'_>
+                },
+            );
+            ctx_ident
+        }
+    };
+
+    // Resolvers returning `async_graphql::Result<T>` report unregistered deps as an error
+    let lookup = if returns_result(&method.sig.output) {
+        quote_spanned! { span=>
+            ::#entrait::integration::async_graphql::ContextDepsExt::deps::<#deps_param>(#ctx_ident)?
+        }
+    } else {
+        quote_spanned! { span=>
+            ::#entrait::integration::async_graphql::ContextDepsExt::deps_unchecked::<#deps_param>(#ctx_ident)
+        }
+    };
+
+    let stmts = &method.block.stmts;
+    method.block = syn::parse_quote_spanned! { span=>
+        {
+            let #deps_pat: &#deps_param = #lookup;
+            #(#stmts)*
+        }
+    };
+
+    Ok(Some(bounds))
+}
+
+/// `deps: &impl Trait`
+fn deps_impl_trait(
+    fn_arg: &syn::FnArg,
+) -> Option<(
+    syn::Pat,
+    syn::punctuated::Punctuated<syn::TypeParamBound, syn::token::Plus>,
+)> {
+    match fn_arg {
+        syn::FnArg::Typed(pat_type) => match pat_type.ty.as_ref() {
+            syn::Type::Reference(reference) => match reference.elem.as_ref() {
+                syn::Type::ImplTrait(impl_trait) => {
+                    Some((pat_type.pat.as_ref().clone(), impl_trait.bounds.clone()))
+                }
+                _ => None,
+            },
+            _ => None,
+        },
+        syn::FnArg::Receiver(_) => None,
+    }
+}
+
+fn is_context(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Reference(reference) => matches!(
+            reference.elem.as_ref(),
+            syn::Type::Path(type_path) if matches!(type_path.path.segments.last(), Some(segment) if segment.ident == "Context")
+        ),
+        _ => false,
+    }
+}
+
+/// Whether the output is a `Result` alias with only the ok type, like `async_graphql::Result<T>`,
+/// whose error type the `async_graphql::Error` of the lookup can be converted into
+fn returns_result(output: &syn::ReturnType) -> bool {
+    let segment = match output {
+        syn::ReturnType::Type(_, ty) => match ty.as_ref() {
+            syn::Type::Path(type_path) => type_path.path.segments.last(),
+            _ => None,
+        },
+        syn::ReturnType::Default => None,
+    };
+
+    match segment {
+        Some(segment) if segment.ident.to_string().ends_with("Result") => matches!(
+            &segment.arguments,
+            syn::PathArguments::AngleBracketed(arguments) if arguments.args.len() == 1
+        ),
+        _ => false,
+    }
+}
@@ -17,6 +17,10 @@ pub enum FnInputMode<'a> {
     SingleFn(&'a syn::Ident),
     Module(&'a syn::Ident),
     ImplBlock(&'a syn::Type),
+    /// The functions of an impl block, exposed through an API trait
+    ImplBlockApi(&'a syn::Path),
+    /// The synchronous variant of a single async function
+    BlockingVariant,
     RawTrait(LiteralAttrs<'a>),
 }
 
@@ -8,20 +8,33 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 
+mod actor_codegen;
 mod analyze_generics;
 mod attributes;
+mod blocking_codegen;
+mod check_deps_codegen;
+mod contract;
 mod entrait_fn;
 mod entrait_impl;
 mod entrait_trait;
+mod fake_codegen;
 mod fn_delegation_codegen;
+mod fn_field_codegen;
 mod generics;
+mod graph;
+mod grpc_codegen;
 mod idents;
+mod inject_deps;
 mod input;
+mod metrics_codegen;
 mod opt;
+mod remote_codegen;
 mod signature;
 mod static_async_trait;
 mod token_util;
+mod tower_codegen;
 mod trait_codegen;
+mod wrapper_codegen;
 
 use input::Input;
 use opt::AsyncStrategy;
@@ -127,6 +140,10 @@ pub fn entrait_export_unimock_use_associated_futures(
     })
 }
 
+/// Rewrites the async methods of a trait or trait impl to use associated futures.
+///
+/// Async methods with a default body return an opaque future instead,
+/// and implementations overriding them mark the method as `#[provided]`.
 #[proc_macro_attribute]
 pub fn static_async_trait(_: TokenStream, input: TokenStream) -> TokenStream {
     let item = syn::parse_macro_input!(input as syn::Item);
@@ -136,6 +153,34 @@ pub fn static_async_trait(_: TokenStream, input: TokenStream) -> TokenStream {
     }
 }
 
+#[proc_macro]
+pub fn graph(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as graph::GraphInput);
+    graph::output_tokens(input).into()
+}
+
+/// Runs the `#[test]` functions of a module against every listed delegation target.
+#[proc_macro_attribute]
+pub fn contract(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let attr = syn::parse_macro_input!(attr as contract::ContractAttr);
+    let item_mod = syn::parse_macro_input!(input as syn::ItemMod);
+    match contract::output_tokens(attr, item_mod) {
+        Ok(stream) => stream.into(),
+        Err(error) => error.into_compile_error().into(),
+    }
+}
+
+/// Lets async-graphql resolvers take `deps: &impl Trait`, looked up in the resolver context.
+#[proc_macro_attribute]
+pub fn inject_deps(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let attr = syn::parse_macro_input!(attr as inject_deps::InjectDepsAttr);
+    let item_impl = syn::parse_macro_input!(input as syn::ItemImpl);
+    match inject_deps::output_tokens(attr, item_impl) {
+        Ok(stream) => stream.into(),
+        Err(error) => error.into_compile_error().into(),
+    }
+}
+
 fn set_fallbacks<const N: usize>(opts: [&mut Option<opt::SpanOpt<bool>>; N]) {
     for opt in opts.into_iter() {
         opt.get_or_insert(opt::SpanOpt::of(true));
@@ -0,0 +1,55 @@
+//! Code generation for the `metrics` option.
+//!
+//! The instrumentation is wrapped in the feature gating macro `::entrait::__metrics! {}`,
+//! which expands to nothing unless the `metrics` feature of entrait is enabled.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote_spanned, ToTokens};
+
+use crate::generics::UseAssociatedFuture;
+use crate::idents::CrateIdents;
+use crate::opt::SpanOpt;
+
+/// A statement starting a `::entrait::metrics::CallTimer` at the beginning of a delegating method.
+///
+/// The timer records the call when created and its latency when dropped at the end of the method body,
+/// so it has to be placed inside the future of an async method.
+pub struct CallTimer<'s> {
+    pub crate_idents: &'s CrateIdents,
+    /// The name of the trait, without generic arguments
+    pub trait_name: String,
+    pub fn_ident: &'s syn::Ident,
+    pub span: Span,
+}
+
+impl<'s> ToTokens for CallTimer<'s> {
+    fn to_tokens(&self, stream: &mut TokenStream) {
+        let entrait = &self.crate_idents.entrait;
+        let trait_name = syn::LitStr::new(self.trait_name.trim_start_matches("r#"), self.span);
+        let method_name = syn::LitStr::new(
+            self.fn_ident.to_string().trim_start_matches("r#"),
+            self.span,
+        );
+
+        quote_spanned! { self.span=>
+            ::#entrait::__metrics! {
+                let __entrait_call_timer = ::#entrait::metrics::CallTimer::start(#trait_name, #method_name);
+            }
+        }
+        .to_tokens(stream);
+    }
+}
+
+/// The timer can't be placed inside associated futures, which are returned without an async body.
+pub fn check_metrics(
+    metrics: Option<&SpanOpt<bool>>,
+    use_associated_future: UseAssociatedFuture,
+) -> syn::Result<()> {
+    match metrics {
+        Some(SpanOpt(_, span)) if use_associated_future.0 => Err(syn::Error::new(
+            *span,
+            "metrics is not supported with associated futures",
+        )),
+        _ => Ok(()),
+    }
+}
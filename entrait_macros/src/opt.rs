@@ -57,6 +57,23 @@ impl Opts {
     }
 }
 
+/// `mock_api` names a single mock, so it can't be used when both unimock and mockall are explicitly enabled.
+///
+/// When unimock is only enabled through the cargo feature, `mock_api` names the mockall mock.
+pub fn check_mock_api(
+    mock_api: Option<&MockApiIdent>,
+    unimock: Option<&SpanOpt<bool>>,
+    mockall: Option<&SpanOpt<bool>>,
+) -> syn::Result<()> {
+    match (mock_api, unimock, mockall) {
+        (Some(mock_api), Some(SpanOpt(true, _)), Some(SpanOpt(true, _))) => Err(syn::Error::new(
+            mock_api.0.span(),
+            "mock_api is ambiguous with both unimock and mockall: it would name both mocks. Remove one of the mock options, or remove mock_api to use `Mock{Trait}` for mockall",
+        )),
+        _ => Ok(()),
+    }
+}
+
 #[derive(Clone, Copy)]
 pub enum Mockable {
     Yes,
@@ -82,6 +99,10 @@ pub enum Delegate {
     BySelf,
     ByRef(RefDelegate),
     ByTrait(syn::Ident),
+    /// Delegate to an actor handle, by sending messages over a channel
+    ByActor,
+    /// Delegate to closures stored by the application, see the `fn_field` delegation mode
+    ByFnField,
 }
 
 #[derive(Clone)]
@@ -90,6 +111,43 @@ pub enum RefDelegate {
     Borrow,
 }
 
+/// Caching of the `Impl<T>` delegation, see the `cache` option
+pub struct CacheOpt {
+    pub ttl_secs: Option<syn::LitInt>,
+    pub span: Span,
+}
+
+/// Retrying of the `Impl<T>` delegation, see the `retry` option
+pub struct RetryOpt {
+    pub max: syn::LitInt,
+    pub backoff: Backoff,
+    pub delay_ms: Option<syn::LitInt>,
+}
+
+/// An in-memory fake of a repository trait, see the `fake` option
+pub struct FakeOpt {
+    pub ident: syn::Ident,
+    pub key: Box<syn::Type>,
+    pub value: Box<syn::Type>,
+}
+
+#[derive(Clone, Copy)]
+pub enum Backoff {
+    None,
+    Fixed,
+    Exponential,
+}
+
+/// How to report unused dependency bounds, see the `check_deps` option
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CheckDeps {
+    Off,
+    /// Emit a warning
+    Warn,
+    /// Emit a compile error
+    Strict,
+}
+
 #[derive(Copy, Clone)]
 pub struct SpanOpt<T>(pub T, pub Span);
 
@@ -120,6 +178,28 @@ pub enum EntraitOpt {
     Unimock(SpanOpt<bool>),
     /// Whether to generate mockall impl
     Mockall(SpanOpt<bool>),
+    /// Name of a generated tower `Service` type
+    TowerService(SpanOpt<syn::Ident>),
+    /// Name of a generated synchronous trait, blocking on the async function
+    BlockingVariant(SpanOpt<syn::Ident>),
+    /// Prefix of generated gRPC server and client types
+    Grpc(SpanOpt<syn::Ident>),
+    /// Name of a generated remote client type
+    Remote(SpanOpt<syn::Ident>),
+    /// Whether to cache results of the `Impl<T>` delegation
+    Cache(SpanOpt<bool>),
+    /// Time to live of cached results, in seconds
+    CacheTtlSecs(SpanOpt<syn::LitInt>),
+    /// Retry policy of the `Impl<T>` delegation
+    Retry(SpanOpt<RetryOpt>),
+    /// Timeout of the `Impl<T>` delegation, in milliseconds
+    TimeoutMs(SpanOpt<syn::LitInt>),
+    /// Whether to record metrics of the `Impl<T>` delegation
+    Metrics(SpanOpt<bool>),
+    /// Whether to check the body for unused dependency bounds
+    CheckDeps(SpanOpt<CheckDeps>),
+    /// In-memory fake implementation of a repository trait
+    Fake(SpanOpt<FakeOpt>),
 }
 
 impl EntraitOpt {
@@ -134,6 +214,17 @@ impl EntraitOpt {
             Self::MockApi(ident) => ident.0.span(),
             Self::Unimock(opt) => opt.1,
             Self::Mockall(opt) => opt.1,
+            Self::TowerService(opt) => opt.1,
+            Self::BlockingVariant(opt) => opt.1,
+            Self::Grpc(opt) => opt.1,
+            Self::Remote(opt) => opt.1,
+            Self::Cache(opt) => opt.1,
+            Self::CacheTtlSecs(opt) => opt.1,
+            Self::Retry(opt) => opt.1,
+            Self::TimeoutMs(opt) => opt.1,
+            Self::Metrics(opt) => opt.1,
+            Self::CheckDeps(opt) => opt.1,
+            Self::Fake(opt) => opt.1,
         }
     }
 }
@@ -163,6 +254,35 @@ impl Parse for EntraitOpt {
             }
             "unimock" => Ok(Unimock(parse_eq_bool(input, true, span)?)),
             "mockall" => Ok(Mockall(parse_eq_bool(input, true, span)?)),
+            "tower_service" => {
+                let _: syn::token::Eq = input.parse()?;
+                Ok(TowerService(SpanOpt(input.parse()?, span)))
+            }
+            "blocking_variant" => {
+                let _: syn::token::Eq = input.parse()?;
+                Ok(BlockingVariant(SpanOpt(input.parse()?, span)))
+            }
+            "grpc" => {
+                let _: syn::token::Eq = input.parse()?;
+                Ok(Grpc(SpanOpt(input.parse()?, span)))
+            }
+            "remote" => {
+                let _: syn::token::Eq = input.parse()?;
+                Ok(Remote(SpanOpt(input.parse()?, span)))
+            }
+            "cache" => Ok(Cache(parse_eq_bool(input, true, span)?)),
+            "cache_ttl_secs" => {
+                let _: syn::token::Eq = input.parse()?;
+                Ok(CacheTtlSecs(SpanOpt(input.parse()?, span)))
+            }
+            "retry" => Ok(Retry(SpanOpt(parse_retry(input, span)?, span))),
+            "timeout_ms" => {
+                let _: syn::token::Eq = input.parse()?;
+                Ok(TimeoutMs(SpanOpt(input.parse()?, span)))
+            }
+            "metrics" => Ok(Metrics(parse_eq_bool(input, true, span)?)),
+            "check_deps" => Ok(CheckDeps(parse_eq_check_deps(input, span)?)),
+            "fake" => Ok(Fake(SpanOpt(parse_fake(input, span)?, span))),
             _ => Err(syn::Error::new(
                 span,
                 format!("Unkonwn entrait option \"{ident_string}\""),
@@ -200,12 +320,134 @@ fn parse_eq_delegate_by(
         match ident.to_string().as_str() {
             "Self" => Delegate::BySelf,
             "Borrow" => Delegate::ByRef(RefDelegate::Borrow),
+            "actor" => Delegate::ByActor,
+            "fn_field" => Delegate::ByFnField,
             _ => Delegate::ByTrait(ident),
         },
         span,
     ))
 }
 
+/// Parse `check_deps`, `check_deps = true/false` or `check_deps = strict`
+fn parse_eq_check_deps(input: ParseStream, span: Span) -> syn::Result<SpanOpt<CheckDeps>> {
+    if !input.peek(syn::token::Eq) {
+        return Ok(SpanOpt(CheckDeps::Warn, span));
+    }
+
+    input.parse::<syn::token::Eq>()?;
+
+    if input.peek(syn::LitBool) {
+        let lit: syn::LitBool = input.parse()?;
+        return Ok(SpanOpt(
+            if lit.value() {
+                CheckDeps::Warn
+            } else {
+                CheckDeps::Off
+            },
+            span,
+        ));
+    }
+
+    let ident: syn::Ident = input.parse()?;
+    match ident.to_string().as_str() {
+        "strict" => Ok(SpanOpt(CheckDeps::Strict, span)),
+        _ => Err(syn::Error::new(
+            ident.span(),
+            "Expected `true`, `false` or `strict`",
+        )),
+    }
+}
+
+/// Parse `(max = 3, backoff = "exp", delay_ms = 100)`
+fn parse_retry(input: ParseStream, span: Span) -> syn::Result<RetryOpt> {
+    let content;
+    syn::parenthesized!(content in input);
+
+    let mut max = None;
+    let mut backoff = Backoff::Exponential;
+    let mut delay_ms = None;
+
+    while !content.is_empty() {
+        let ident: syn::Ident = content.parse()?;
+        let _: syn::token::Eq = content.parse()?;
+
+        match ident.to_string().as_str() {
+            "max" => max = Some(content.parse()?),
+            "backoff" => {
+                let lit: syn::LitStr = content.parse()?;
+                backoff = match lit.value().as_str() {
+                    "none" => Backoff::None,
+                    "fixed" => Backoff::Fixed,
+                    "exp" => Backoff::Exponential,
+                    _ => {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "Expected \"none\", \"fixed\" or \"exp\"",
+                        ))
+                    }
+                };
+            }
+            "delay_ms" => delay_ms = Some(content.parse()?),
+            other => {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    format!("Unknown retry parameter \"{other}\""),
+                ))
+            }
+        }
+
+        if content.is_empty() {
+            break;
+        }
+        let _: syn::token::Comma = content.parse()?;
+    }
+
+    match max {
+        Some(max) => Ok(RetryOpt {
+            max,
+            backoff,
+            delay_ms,
+        }),
+        None => Err(syn::Error::new(span, "retry requires `max`")),
+    }
+}
+
+/// Parse `fake(Name, key = K, value = V)`
+fn parse_fake(input: ParseStream, span: Span) -> syn::Result<FakeOpt> {
+    let content;
+    syn::parenthesized!(content in input);
+
+    let ident: syn::Ident = content.parse()?;
+    let mut key = None;
+    let mut value = None;
+
+    while !content.is_empty() {
+        let _: syn::token::Comma = content.parse()?;
+        if content.is_empty() {
+            break;
+        }
+
+        let param: syn::Ident = content.parse()?;
+        let _: syn::token::Eq = content.parse()?;
+
+        match param.to_string().as_str() {
+            "key" => key = Some(content.parse()?),
+            "value" => value = Some(content.parse()?),
+            other => {
+                return Err(syn::Error::new(
+                    param.span(),
+                    format!("Unknown fake parameter \"{other}\""),
+                ))
+            }
+        }
+    }
+
+    match (key, value) {
+        (Some(key), Some(value)) => Ok(FakeOpt { ident, key, value }),
+        _ => Err(syn::Error::new(span, "fake requires `key` and `value`")),
+    }
+}
+
 fn parse_eq_value_or_default<V, F, O>(
     input: ParseStream,
     default_value: O,
@@ -0,0 +1,356 @@
+//! Code generation for calling trait methods remotely.
+//!
+//! [RemoteMethodAnalyzer] validates the methods of a trait for the options generating remote clients (`remote` and `grpc`).
+//! [RemoteCodegen] implements the `remote` option, which generates serializable request and response enums,
+//! a dispatcher executing requests against any deps implementing the trait,
+//! and a client implementing the trait over an `::entrait::remote::Transport`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote_spanned, ToTokens};
+
+use crate::analyze_generics::TraitFn;
+use crate::generics::TraitGenerics;
+use crate::idents::{upper_camel_case, CrateIdents};
+use crate::opt::{AsyncStrategy, Opts, SpanOpt};
+use crate::signature::ResultParts;
+
+/// A trait method that can be called remotely, taking `&self` and all its arguments by value.
+pub struct RemoteMethod<'s> {
+    pub trait_fn: &'s TraitFn,
+    pub arg_idents: Vec<&'s syn::Ident>,
+    pub arg_types: Vec<&'s syn::Type>,
+}
+
+impl<'s> RemoteMethod<'s> {
+    /// The return type, `()` if not specified
+    pub fn output_ty(&self, span: Span) -> TokenStream {
+        match &self.trait_fn.sig().output {
+            syn::ReturnType::Default => quote_spanned! { span=> () },
+            syn::ReturnType::Type(_, ty) => ty.to_token_stream(),
+        }
+    }
+
+    /// The enum variant of the method, i.e. `fetch_planet` becomes `FetchPlanet`
+    pub fn variant_ident(&self) -> syn::Ident {
+        upper_camel_case(&self.trait_fn.sig().ident)
+    }
+}
+
+pub struct RemoteMethodAnalyzer<'s> {
+    /// The option used, for error messages
+    pub option_name: &'static str,
+    /// The error of a failed call, which must convert into the error of each method
+    pub transport_error: &'static str,
+    pub opts: &'s Opts,
+    pub trait_generics: &'s TraitGenerics,
+    /// Whether the trait has a user-supplied `#[async_trait]` attribute
+    pub has_async_trait_attr: bool,
+    pub span: Span,
+}
+
+impl<'s> RemoteMethodAnalyzer<'s> {
+    pub fn analyze<'t>(&self, trait_fns: &'t [TraitFn]) -> syn::Result<Vec<RemoteMethod<'t>>> {
+        let option_name = self.option_name;
+        let transport_error = self.transport_error;
+
+        if !self.trait_generics.params.is_empty() {
+            return Err(syn::Error::new(
+                self.span,
+                format!("{option_name} does not support generic traits"),
+            ));
+        }
+        let boxed_futures = self.has_async_trait_attr
+            || matches!(
+                self.opts.async_strategy(),
+                SpanOpt(AsyncStrategy::BoxFuture, _)
+            );
+
+        trait_fns
+            .iter()
+            .map(|trait_fn| {
+                let sig = trait_fn.sig();
+
+                if !trait_fn.originally_async || !boxed_futures {
+                    return Err(syn::Error::new(
+                        sig.ident.span(),
+                        format!("{option_name} requires all methods to be async, using boxed futures (`box_future`)"),
+                    ));
+                }
+                if !sig.generics.params.is_empty() {
+                    return Err(syn::Error::new(
+                        sig.ident.span(),
+                        format!("{option_name} does not support generic methods"),
+                    ));
+                }
+                // A failed call is returned as the error of the method
+                if ResultParts::detect(&sig.output).is_none() {
+                    return Err(syn::Error::new(
+                        sig.ident.span(),
+                        format!("{option_name} requires methods to return `Result<_, E>`, where `E: From<{transport_error}>`"),
+                    ));
+                }
+
+                let mut arg_idents = vec![];
+                let mut arg_types = vec![];
+
+                for fn_arg in &sig.inputs {
+                    match fn_arg {
+                        syn::FnArg::Receiver(receiver)
+                            if receiver.reference.is_some() && receiver.mutability.is_none() => {}
+                        syn::FnArg::Receiver(receiver) => {
+                            return Err(syn::Error::new(
+                                receiver.self_token.span,
+                                format!("{option_name} requires methods to take `&self`"),
+                            ))
+                        }
+                        syn::FnArg::Typed(pat_type) => {
+                            let ident = match pat_type.pat.as_ref() {
+                                syn::Pat::Ident(pat_ident) => &pat_ident.ident,
+                                _ => {
+                                    return Err(syn::Error::new(
+                                        sig.ident.span(),
+                                        format!("{option_name} requires arguments to be plain identifiers"),
+                                    ))
+                                }
+                            };
+                            if let syn::Type::Reference(_) = pat_type.ty.as_ref() {
+                                return Err(syn::Error::new(
+                                    ident.span(),
+                                    format!("{option_name} requires arguments to be passed by value"),
+                                ));
+                            }
+                            arg_idents.push(ident);
+                            arg_types.push(pat_type.ty.as_ref());
+                        }
+                    }
+                }
+
+                Ok(RemoteMethod {
+                    trait_fn,
+                    arg_idents,
+                    arg_types,
+                })
+            })
+            .collect()
+    }
+}
+
+pub struct RemoteCodegen<'s> {
+    pub opts: &'s Opts,
+    pub crate_idents: &'s CrateIdents,
+    pub trait_visibility: &'s syn::Visibility,
+    pub trait_ident: &'s syn::Ident,
+    pub trait_generics: &'s TraitGenerics,
+    pub client_ident: &'s syn::Ident,
+    /// Whether the trait has a user-supplied `#[async_trait]` attribute
+    pub has_async_trait_attr: bool,
+}
+
+/// Identifiers of the generated items
+struct RemoteIdents {
+    request: syn::Ident,
+    response: syn::Ident,
+    dispatcher: syn::Ident,
+}
+
+impl<'s> RemoteCodegen<'s> {
+    ///
+    /// Generate code like
+    ///
+    /// ```no_compile
+    /// pub enum FooRemoteRequest { Method { arg: Arg } }
+    /// pub enum FooRemoteResponse { Method(Output) }
+    ///
+    /// pub struct FooRemoteDispatcher<EntraitD>(pub EntraitD);
+    /// impl<EntraitD: Foo + Send + Sync> ::entrait::remote::Dispatch for FooRemoteDispatcher<EntraitD> { .. }
+    ///
+    /// pub struct FooRemote<EntraitT>(pub EntraitT);
+    /// impl<EntraitT: ::entrait::remote::Transport> Foo for FooRemote<EntraitT> { .. }
+    /// ```
+    ///
+    pub fn gen_remote(
+        &self,
+        trait_fns: &[TraitFn],
+        impl_attrs: &TokenStream,
+    ) -> syn::Result<TokenStream> {
+        let span = self.client_ident.span();
+        let methods = RemoteMethodAnalyzer {
+            option_name: "remote",
+            transport_error: "entrait::remote::RemoteError",
+            opts: self.opts,
+            trait_generics: self.trait_generics,
+            has_async_trait_attr: self.has_async_trait_attr,
+            span,
+        }
+        .analyze(trait_fns)?;
+        let idents = RemoteIdents {
+            request: format_ident!("{}Request", self.client_ident),
+            response: format_ident!("{}Response", self.client_ident),
+            dispatcher: format_ident!("{}Dispatcher", self.client_ident),
+        };
+
+        let enums = self.gen_enums(&methods, &idents, span);
+        let dispatcher = self.gen_dispatcher(&methods, &idents, span);
+        let client = self.gen_client(&methods, &idents, impl_attrs, span);
+
+        Ok(quote_spanned! { span=>
+            #enums
+            #dispatcher
+            #client
+        })
+    }
+
+    fn gen_enums(
+        &self,
+        methods: &[RemoteMethod],
+        idents: &RemoteIdents,
+        span: Span,
+    ) -> TokenStream {
+        let entrait = &self.crate_idents.entrait;
+        let vis = self.trait_visibility;
+        let RemoteIdents {
+            request, response, ..
+        } = idents;
+        let serde_crate = syn::LitStr::new(&format!("::{entrait}::__remote::serde"), span);
+
+        let request_variants = methods.iter().map(|method| {
+            let variant = method.variant_ident();
+            let arg_idents = &method.arg_idents;
+            let arg_types = &method.arg_types;
+
+            quote_spanned! { span=>
+                #variant { #(#arg_idents: #arg_types),* }
+            }
+        });
+        let response_variants = methods.iter().map(|method| {
+            let variant = method.variant_ident();
+            let output_ty = method.output_ty(span);
+
+            quote_spanned! { span=>
+                #variant(#output_ty)
+            }
+        });
+
+        quote_spanned! { span=>
+            #[derive(::#entrait::__remote::serde::Serialize, ::#entrait::__remote::serde::Deserialize)]
+            #[serde(crate = #serde_crate)]
+            #vis enum #request {
+                #(#request_variants,)*
+            }
+
+            #[derive(::#entrait::__remote::serde::Serialize, ::#entrait::__remote::serde::Deserialize)]
+            #[serde(crate = #serde_crate)]
+            #vis enum #response {
+                #(#response_variants,)*
+            }
+        }
+    }
+
+    fn gen_dispatcher(
+        &self,
+        methods: &[RemoteMethod],
+        idents: &RemoteIdents,
+        span: Span,
+    ) -> TokenStream {
+        let entrait = &self.crate_idents.entrait;
+        let core = &self.crate_idents.core;
+        let vis = self.trait_visibility;
+        let trait_ident = self.trait_ident;
+        let RemoteIdents {
+            request,
+            response,
+            dispatcher,
+        } = idents;
+
+        let arms = methods.iter().map(|method| {
+            let variant = method.variant_ident();
+            let fn_ident = &method.trait_fn.sig().ident;
+            let arg_idents = &method.arg_idents;
+
+            quote_spanned! { span=>
+                #request::#variant { #(#arg_idents),* } => #response::#variant(
+                    <EntraitD as #trait_ident>::#fn_ident(deps, #(#arg_idents),*).await
+                ),
+            }
+        });
+
+        quote_spanned! { span=>
+            impl #request {
+                /// Execute the request against `deps`.
+                pub async fn dispatch<EntraitD: #trait_ident>(self, deps: &EntraitD) -> #response {
+                    match self {
+                        #(#arms)*
+                    }
+                }
+            }
+
+            #[derive(Clone)]
+            #vis struct #dispatcher<EntraitD>(pub EntraitD);
+
+            impl<EntraitD> ::#entrait::remote::Dispatch for #dispatcher<EntraitD>
+            where
+                EntraitD: #trait_ident + ::#core::marker::Send + ::#core::marker::Sync,
+            {
+                fn dispatch(
+                    &self,
+                    request: ::#entrait::__remote::Vec<u8>,
+                ) -> ::#entrait::remote::BoxFuture<
+                    '_,
+                    ::#core::result::Result<::#entrait::__remote::Vec<u8>, ::#entrait::remote::RemoteError>,
+                > {
+                    ::#entrait::__remote::box_future(async move {
+                        let request: #request = ::#entrait::__remote::decode(&request)?;
+                        ::#entrait::__remote::encode(&request.dispatch(&self.0).await)
+                    })
+                }
+            }
+        }
+    }
+
+    fn gen_client(
+        &self,
+        methods: &[RemoteMethod],
+        idents: &RemoteIdents,
+        impl_attrs: &TokenStream,
+        span: Span,
+    ) -> TokenStream {
+        let entrait = &self.crate_idents.entrait;
+        let core = &self.crate_idents.core;
+        let vis = self.trait_visibility;
+        let trait_ident = self.trait_ident;
+        let client_ident = self.client_ident;
+        let RemoteIdents {
+            request, response, ..
+        } = idents;
+
+        let items = methods.iter().map(|method| {
+            let sig = method.trait_fn.sig();
+            let attrs = &method.trait_fn.attrs;
+            let variant = method.variant_ident();
+            let arg_idents = &method.arg_idents;
+
+            quote_spanned! { span=>
+                #(#attrs)*
+                #sig {
+                    let request = #request::#variant { #(#arg_idents),* };
+                    let error = match ::#entrait::__remote::call(&self.0, &request).await {
+                        ::#core::result::Result::Ok(#response::#variant(output)) => return output,
+                        ::#core::result::Result::Ok(_) => ::#entrait::remote::RemoteError::UnexpectedResponse,
+                        ::#core::result::Result::Err(error) => error,
+                    };
+                    ::#core::result::Result::Err(::#core::convert::From::from(error))
+                }
+            }
+        });
+
+        quote_spanned! { span=>
+            #[derive(Clone)]
+            #vis struct #client_ident<EntraitT>(pub EntraitT);
+
+            #impl_attrs
+            impl<EntraitT: ::#entrait::remote::Transport> #trait_ident for #client_ident<EntraitT> {
+                #(#items)*
+            }
+        }
+    }
+}
@@ -1,6 +1,11 @@
+use super::impl_return::ImplReturn;
 use super::{fn_params, ReceiverGeneration};
 use super::{EntraitSignature, ImplReceiverKind, InputSig};
-use crate::{generics::FnDeps, idents::CrateIdents, opt::Opts};
+use crate::{
+    generics::FnDeps,
+    idents::CrateIdents,
+    opt::{AsyncStrategy, Opts},
+};
 
 use proc_macro2::Span;
 use syn::spanned::Spanned;
@@ -39,9 +44,34 @@ impl<'a> SignatureConverter<'a> {
 
         fn_params::fix_fn_param_idents(&mut entrait_sig.sig);
 
+        // The traits of impl blocks are defined elsewhere, with their own return types
+        if matches!(self.impl_receiver_kind, ImplReceiverKind::SelfRef) {
+            self.convert_impl_return(&mut entrait_sig);
+        }
+
         entrait_sig
     }
 
+    /// Name an `impl Iterator` or `impl Stream` return type according to the async strategy.
+    /// Without boxed or associated futures, it stays a return-position `impl Trait` in the trait.
+    fn convert_impl_return(&self, entrait_sig: &mut EntraitSignature) {
+        let impl_return = match ImplReturn::detect(&entrait_sig.sig.output) {
+            Some(impl_return) => impl_return,
+            None => return,
+        };
+
+        match self.opts.async_strategy().0 {
+            AsyncStrategy::BoxFuture => {
+                entrait_sig.convert_to_boxed_return(impl_return, self.crate_idents);
+            }
+            AsyncStrategy::AssociatedFuture => {
+                entrait_sig
+                    .convert_to_associated_type(ReceiverGeneration::Rewrite, self.trait_span);
+            }
+            AsyncStrategy::NoHack => {}
+        }
+    }
+
     fn detect_receiver_generation(&self, sig: &syn::Signature) -> ReceiverGeneration {
         match self.deps {
             FnDeps::NoDeps { .. } => ReceiverGeneration::Insert,
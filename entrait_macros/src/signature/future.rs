@@ -8,6 +8,7 @@ use crate::idents::CrateIdents;
 use crate::token_util::EmptyToken;
 use crate::token_util::Punctuator;
 
+use super::impl_return::ImplReturn;
 use super::lifetimes;
 use super::AssociatedFut;
 use super::EntraitSignature;
@@ -21,6 +22,24 @@ impl EntraitSignature {
         &mut self,
         receiver_generation: ReceiverGeneration,
         trait_span: Span,
+    ) {
+        self.convert_to_associated(receiver_generation, trait_span, AssociatedKind::Future);
+    }
+
+    /// Name an `impl Iterator` or `impl Stream` return type by an associated type, in the same way as a future.
+    pub fn convert_to_associated_type(
+        &mut self,
+        receiver_generation: ReceiverGeneration,
+        trait_span: Span,
+    ) {
+        self.convert_to_associated(receiver_generation, trait_span, AssociatedKind::ImplReturn);
+    }
+
+    fn convert_to_associated(
+        &mut self,
+        receiver_generation: ReceiverGeneration,
+        trait_span: Span,
+        kind: AssociatedKind,
     ) {
         lifetimes::de_elide_lifetimes(self, receiver_generation);
 
@@ -33,6 +52,34 @@ impl EntraitSignature {
         });
 
         let output = clone_output_type(&self.sig.output);
+        let impl_bounds = match kind {
+            AssociatedKind::Future => None,
+            AssociatedKind::ImplReturn => {
+                ImplReturn::detect(&self.sig.output).map(|impl_return| impl_return.bounds)
+            }
+        };
+        let generic_params = self
+            .sig
+            .generics
+            .params
+            .iter()
+            .filter(|param| !matches!(param, syn::GenericParam::Lifetime(_)))
+            .cloned()
+            .collect::<Vec<_>>();
+        let where_predicates = match &self.sig.generics.where_clause {
+            Some(where_clause) => where_clause
+                .predicates
+                .iter()
+                .filter(|predicate| match predicate {
+                    syn::WherePredicate::Type(predicate_type) => generic_params
+                        .iter()
+                        .any(|param| is_type_param(param, &predicate_type.bounded_ty)),
+                    _ => false,
+                })
+                .cloned()
+                .collect(),
+            None => vec![],
+        };
 
         // make the function generic if it wasn't already
         let sig = &mut self.sig;
@@ -53,15 +100,20 @@ impl EntraitSignature {
                 }));
         }
 
-        let fut_ident = quote::format_ident!("Fut__{}", sig.ident);
+        let fut_ident = match kind {
+            AssociatedKind::Future => quote::format_ident!("Fut__{}", sig.ident),
+            AssociatedKind::ImplReturn => quote::format_ident!("Ret__{}", sig.ident),
+        };
 
         let fut_lifetimes = self
             .et_lifetimes_in_assoc_future()
             .map(|et| &et.lifetime)
             .collect::<Vec<_>>();
 
+        let fut_generic_args = generic_params.iter().filter_map(generic_param_arg);
+
         self.sig.output = syn::parse_quote_spanned! { trait_span =>
-            -> Self::#fut_ident<#(#fut_lifetimes),*>
+            -> Self::#fut_ident<#(#fut_lifetimes,)* #(#fut_generic_args),*>
         };
 
         let sig_where_clause = self.sig.generics.make_where_clause();
@@ -74,6 +126,15 @@ impl EntraitSignature {
                 });
             }
         }
+        for param in &generic_params {
+            if let syn::GenericParam::Type(type_param) = param {
+                let ident = &type_param.ident;
+
+                sig_where_clause.predicates.push(syn::parse_quote! {
+                    #ident: #base_lifetime
+                });
+            }
+        }
         sig_where_clause.predicates.push(syn::parse_quote! {
             Self: #base_lifetime
         });
@@ -81,11 +142,37 @@ impl EntraitSignature {
         self.associated_fut = Some(AssociatedFut {
             ident: fut_ident,
             output,
+            impl_bounds,
             base_lifetime,
+            generic_params,
+            where_predicates,
         });
     }
 }
 
+enum AssociatedKind {
+    Future,
+    ImplReturn,
+}
+
+/// The generic argument passing on a type or const param, e.g. `T` for `T: Display`
+fn generic_param_arg(param: &syn::GenericParam) -> Option<&syn::Ident> {
+    match param {
+        syn::GenericParam::Type(type_param) => Some(&type_param.ident),
+        syn::GenericParam::Const(const_param) => Some(&const_param.ident),
+        syn::GenericParam::Lifetime(_) => None,
+    }
+}
+
+fn is_type_param(param: &syn::GenericParam, ty: &syn::Type) -> bool {
+    match (param, ty) {
+        (syn::GenericParam::Type(type_param), syn::Type::Path(type_path)) => {
+            type_path.qself.is_none() && type_path.path.is_ident(&type_param.ident)
+        }
+        _ => false,
+    }
+}
+
 fn clone_output_type(return_type: &syn::ReturnType) -> syn::Type {
     match return_type {
         syn::ReturnType::Default => syn::parse_quote! { () },
@@ -116,9 +203,15 @@ impl<'s> ToTokens for FutDecl<'s> {
             associated_fut: self.associated_fut,
         };
 
-        let tokens = quote! {
-            #[allow(non_camel_case_types)]
-            type #ident #params: ::#core::future::Future<Output = #output> + Send + #base_lifetime #where_clause;
+        let tokens = match &self.associated_fut.impl_bounds {
+            Some(impl_bounds) => quote! {
+                #[allow(non_camel_case_types)]
+                type #ident #params: #impl_bounds + #base_lifetime #where_clause;
+            },
+            None => quote! {
+                #[allow(non_camel_case_types)]
+                type #ident #params: ::#core::future::Future<Output = #output> + Send + #base_lifetime #where_clause;
+            },
         };
 
         tokens.to_tokens(stream);
@@ -150,9 +243,18 @@ impl<'s> ToTokens for FutImpl<'s> {
             associated_fut: self.associated_fut,
         };
 
-        let tokens = quote! {
-            #[allow(non_camel_case_types)]
-            type #ident #params = impl ::#core::future::Future<Output = #output> #fut_bounds #where_clause;
+        let tokens = match &self.associated_fut.impl_bounds {
+            Some(impl_bounds) => {
+                let base_lifetime = &self.associated_fut.base_lifetime;
+                quote! {
+                    #[allow(non_camel_case_types)]
+                    type #ident #params = impl #impl_bounds + #base_lifetime #where_clause;
+                }
+            }
+            None => quote! {
+                #[allow(non_camel_case_types)]
+                type #ident #params = impl ::#core::future::Future<Output = #output> #fut_bounds #where_clause;
+            },
         };
         tokens.to_tokens(stream);
     }
@@ -174,6 +276,12 @@ impl<'s> ToTokens for FutParams<'s> {
         for lt in self.signature.et_lifetimes_in_assoc_future() {
             punctuator.push(&lt.lifetime);
         }
+
+        if let Some(associated_fut) = &self.signature.associated_fut {
+            for param in &associated_fut.generic_params {
+                punctuator.push(param);
+            }
+        }
     }
 }
 
@@ -201,6 +309,20 @@ impl<'s> ToTokens for FutWhereClause<'s> {
             });
         }
 
+        for param in &self.associated_fut.generic_params {
+            if let syn::GenericParam::Type(type_param) = param {
+                let ident = &type_param.ident;
+
+                punctuator.push(quote! {
+                    #ident: #base_lifetime
+                });
+            }
+        }
+
+        for predicate in &self.associated_fut.where_predicates {
+            punctuator.push(predicate);
+        }
+
         punctuator.push_fn(|stream| {
             let bound_target = match self.trait_indirection {
                 TraitIndirection::StaticImpl | TraitIndirection::DynamicImpl => quote! { EntraitT },
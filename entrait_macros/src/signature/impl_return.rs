@@ -0,0 +1,98 @@
+//! `impl Iterator` and `impl Stream` return types, which need to be named in the trait.
+
+use proc_macro2::Span;
+use quote::quote;
+
+use crate::idents::CrateIdents;
+
+use super::EntraitSignature;
+
+#[derive(Clone, Copy)]
+pub enum ImplReturnKind {
+    Iterator,
+    Stream,
+}
+
+impl ImplReturnKind {
+    /// The boxed type in `::entrait::boxed`
+    pub fn box_ident(self) -> syn::Ident {
+        match self {
+            Self::Iterator => syn::Ident::new("BoxIter", Span::call_site()),
+            Self::Stream => syn::Ident::new("BoxStream", Span::call_site()),
+        }
+    }
+}
+
+/// A return type like `impl Iterator<Item = T> + Send + 'a`
+pub struct ImplReturn {
+    pub kind: ImplReturnKind,
+    pub item: syn::Type,
+    pub bounds: syn::punctuated::Punctuated<syn::TypeParamBound, syn::token::Plus>,
+}
+
+impl ImplReturn {
+    pub fn detect(output: &syn::ReturnType) -> Option<Self> {
+        let impl_trait = match output {
+            syn::ReturnType::Type(_, ty) => match ty.as_ref() {
+                syn::Type::ImplTrait(impl_trait) => impl_trait,
+                _ => return None,
+            },
+            syn::ReturnType::Default => return None,
+        };
+
+        impl_trait.bounds.iter().find_map(|bound| {
+            let segment = match bound {
+                syn::TypeParamBound::Trait(trait_bound) => trait_bound.path.segments.last()?,
+                _ => return None,
+            };
+            let kind = match segment.ident.to_string().as_str() {
+                "Iterator" => ImplReturnKind::Iterator,
+                "Stream" => ImplReturnKind::Stream,
+                _ => return None,
+            };
+            let item = match &segment.arguments {
+                syn::PathArguments::AngleBracketed(arguments) => {
+                    arguments.args.iter().find_map(|arg| match arg {
+                        syn::GenericArgument::AssocType(assoc) if assoc.ident == "Item" => {
+                            Some(assoc.ty.clone())
+                        }
+                        _ => None,
+                    })?
+                }
+                _ => return None,
+            };
+
+            Some(ImplReturn {
+                kind,
+                item,
+                bounds: impl_trait.bounds.clone(),
+            })
+        })
+    }
+
+    /// The lifetime bound of the return type, if any
+    fn lifetime(&self) -> Option<&syn::Lifetime> {
+        self.bounds.iter().find_map(|bound| match bound {
+            syn::TypeParamBound::Lifetime(lifetime) => Some(lifetime),
+            _ => None,
+        })
+    }
+}
+
+impl EntraitSignature {
+    /// Return a boxed iterator or stream instead, e.g. `::entrait::boxed::BoxIter<'_, T>`.
+    pub fn convert_to_boxed_return(&mut self, impl_return: ImplReturn, crate_idents: &CrateIdents) {
+        let entrait = &crate_idents.entrait;
+        let item = &impl_return.item;
+        let lifetime = match impl_return.lifetime() {
+            Some(lifetime) => quote! { #lifetime },
+            None => quote! { '_ },
+        };
+        let box_ident = impl_return.kind.box_ident();
+
+        self.sig.output = syn::parse_quote! {
+            -> ::#entrait::boxed::#box_ident<#lifetime, #item>
+        };
+        self.boxed_return = Some(impl_return.kind);
+    }
+}
@@ -1,11 +1,14 @@
 pub mod converter;
 pub mod future;
+pub mod impl_return;
 pub mod lifetimes;
 
 mod fn_params;
 
 use std::ops::Deref;
 
+use syn::spanned::Spanned;
+
 use crate::generics::TraitIndirection;
 use crate::idents::CrateIdents;
 
@@ -43,6 +46,8 @@ pub struct EntraitSignature {
     pub sig: syn::Signature,
     pub associated_fut: Option<AssociatedFut>,
     pub et_lifetimes: Vec<EntraitLifetime>,
+    /// An `impl Iterator` or `impl Stream` return type that is boxed in the trait
+    pub boxed_return: Option<impl_return::ImplReturnKind>,
 }
 
 impl EntraitSignature {
@@ -51,9 +56,30 @@ impl EntraitSignature {
             sig,
             associated_fut: None,
             et_lifetimes: vec![],
+            boxed_return: None,
         }
     }
 
+    /// The identifiers of the arguments after the receiver, for forwarding them in a call.
+    ///
+    /// Patterns have been replaced by identifiers when converting the signature.
+    pub fn arg_idents(&self) -> syn::Result<Vec<&syn::Ident>> {
+        self.sig
+            .inputs
+            .iter()
+            .filter_map(|fn_arg| match fn_arg {
+                syn::FnArg::Receiver(_) => None,
+                syn::FnArg::Typed(pat_type) => Some(match pat_type.pat.as_ref() {
+                    syn::Pat::Ident(pat_ident) => Ok(&pat_ident.ident),
+                    pat => Err(syn::Error::new(
+                        pat.span(),
+                        "Expected an identifier, found a pattern",
+                    )),
+                }),
+            })
+            .collect()
+    }
+
     pub fn associated_fut_decl<'s>(
         &'s self,
         trait_indirection: TraitIndirection,
@@ -101,7 +127,13 @@ impl EntraitSignature {
 pub struct AssociatedFut {
     pub ident: syn::Ident,
     pub output: syn::Type,
+    /// The bounds of an `impl Trait` return type that is not a future, like `Iterator<Item = T>`
+    pub impl_bounds: Option<syn::punctuated::Punctuated<syn::TypeParamBound, syn::token::Plus>>,
     pub base_lifetime: syn::Lifetime,
+    /// Type and const params of the method, captured by the future
+    pub generic_params: Vec<syn::GenericParam>,
+    /// Where predicates of the method that bound its type params
+    pub where_predicates: Vec<syn::WherePredicate>,
 }
 
 /// Only used for associated future:
@@ -133,3 +165,36 @@ pub enum ReceiverGeneration {
     Rewrite,
     None,
 }
+
+/// The `T` and `E` of a return type written as `Result<T, E>`.
+pub struct ResultParts<'s> {
+    pub ok: &'s syn::Type,
+    pub err: &'s syn::Type,
+}
+
+impl<'s> ResultParts<'s> {
+    pub fn detect(output: &'s syn::ReturnType) -> Option<Self> {
+        let last_segment = match output {
+            syn::ReturnType::Type(_, ty) => match ty.as_ref() {
+                syn::Type::Path(type_path) => type_path.path.segments.last()?,
+                _ => return None,
+            },
+            syn::ReturnType::Default => return None,
+        };
+        let generic_args = match &last_segment.arguments {
+            syn::PathArguments::AngleBracketed(generic_args) if last_segment.ident == "Result" => {
+                generic_args
+            }
+            _ => return None,
+        };
+
+        let mut types = generic_args.args.iter().filter_map(|arg| match arg {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        });
+        match (types.next(), types.next(), types.next()) {
+            (Some(ok), Some(err), None) => Some(Self { ok, err }),
+            _ => None,
+        }
+    }
+}
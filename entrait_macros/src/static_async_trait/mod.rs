@@ -41,6 +41,22 @@ fn process_trait(item_trait: syn::ItemTrait) -> syn::Result<TokenStream> {
 
     for item in items.into_iter() {
         match item {
+            syn::TraitItem::Fn(syn::TraitItemFn {
+                attrs,
+                sig,
+                default: Some(syn::Block { stmts, .. }),
+                ..
+            }) if sig.asyncness.is_some() => {
+                let sig = provided_sig(sig, &crate_idents);
+
+                quote! {
+                    #(#attrs)*
+                    #sig {
+                        async move { #(#stmts)* }
+                    }
+                }
+                .to_tokens(&mut new_items);
+            }
             syn::TraitItem::Fn(method) if method.sig.asyncness.is_some() => {
                 let (sig, trait_indirection) = convert_sig(method.sig, trait_span);
                 let fut = sig.associated_fut_decl(trait_indirection, &crate_idents);
@@ -85,6 +101,15 @@ fn process_impl(item_impl: syn::ItemImpl) -> syn::Result<TokenStream> {
         let crate_idents = CrateIdents::new(impl_token.span());
         for item in items.into_iter() {
             match item {
+                syn::ImplItem::Fn(mut method)
+                    if method.sig.asyncness.is_some() && is_provided(&method.attrs) =>
+                {
+                    // Overrides a default body, which has no associated future
+                    method
+                        .attrs
+                        .retain(|attr| !attr.path().is_ident("provided"));
+                    method.to_tokens(&mut new_items);
+                }
                 syn::ImplItem::Fn(method) if method.sig.asyncness.is_some() => {
                     let ImplItemFn {
                         attrs,
@@ -128,6 +153,43 @@ fn process_impl(item_impl: syn::ItemImpl) -> syn::Result<TokenStream> {
     })
 }
 
+/// The signature of an async method with a default body, returning an opaque future.
+///
+/// An associated future can't have a default, so the future of a default body is a return-position `impl Future`.
+/// Implementations overriding it must mark the method as `#[provided]`.
+fn provided_sig(mut sig: syn::Signature, crate_idents: &CrateIdents) -> syn::Signature {
+    let core = &crate_idents.core;
+    let output = match &sig.output {
+        syn::ReturnType::Default => syn::parse_quote! { () },
+        syn::ReturnType::Type(_, ty) => ty.as_ref().clone(),
+    };
+    let span = sig.asyncness.take().span();
+
+    // The future holds on to the receiver
+    let self_bound: Option<syn::WherePredicate> = match sig.inputs.first() {
+        Some(syn::FnArg::Receiver(syn::Receiver {
+            reference: Some(_),
+            mutability: None,
+            ..
+        })) => Some(syn::parse_quote! { Self: Sync }),
+        Some(syn::FnArg::Receiver(_)) => Some(syn::parse_quote! { Self: Send }),
+        _ => None,
+    };
+    if let Some(self_bound) = self_bound {
+        sig.generics.make_where_clause().predicates.push(self_bound);
+    }
+
+    sig.output = syn::parse_quote_spanned! { span=>
+        -> impl ::#core::future::Future<Output = #output> + Send
+    };
+    sig
+}
+
+/// Whether a method is marked `#[provided]`, overriding a default body.
+fn is_provided(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| attr.path().is_ident("provided"))
+}
+
 fn convert_sig(sig: syn::Signature, span: Span) -> (EntraitSignature, TraitIndirection) {
     let trait_indirection = if matches!(sig.inputs.first(), Some(syn::FnArg::Receiver(_))) {
         TraitIndirection::Plain
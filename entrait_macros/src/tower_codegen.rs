@@ -0,0 +1,142 @@
+//! Code generation for the `tower_service` option.
+//!
+//! Generates a `tower::Service` wrapping any deps type implementing the entraited trait.
+
+use proc_macro2::TokenStream;
+use quote::quote_spanned;
+
+use crate::analyze_generics::TraitFn;
+use crate::generics::TraitGenerics;
+use crate::idents::CrateIdents;
+use crate::opt::{AsyncStrategy, Opts, SpanOpt};
+use crate::signature::ResultParts;
+
+pub struct TowerServiceCodegen<'s> {
+    pub opts: &'s Opts,
+    pub crate_idents: &'s CrateIdents,
+    pub trait_visibility: &'s syn::Visibility,
+    pub trait_ident: &'s syn::Ident,
+    pub service_ident: &'s syn::Ident,
+    pub trait_generics: &'s TraitGenerics,
+}
+
+impl<'s> TowerServiceCodegen<'s> {
+    ///
+    /// Generate code like
+    ///
+    /// ```no_compile
+    /// #[derive(Clone)]
+    /// pub struct FooService<EntraitD>(pub EntraitD);
+    ///
+    /// impl<EntraitD: Foo + Clone + Send + Sync + 'static> ::entrait::__tower::Service<Request> for FooService<EntraitD> {
+    ///     type Response = T;
+    ///     type Error = E;
+    ///     ...
+    /// }
+    /// ```
+    ///
+    pub fn gen_service(&self, trait_fn: &TraitFn) -> syn::Result<TokenStream> {
+        let span = self.service_ident.span();
+        let sig = trait_fn.sig();
+
+        if !self.trait_generics.params.is_empty() {
+            return Err(syn::Error::new(
+                span,
+                "tower_service does not support generic functions",
+            ));
+        }
+        if !trait_fn.originally_async
+            || !matches!(
+                self.opts.async_strategy(),
+                SpanOpt(AsyncStrategy::BoxFuture, _)
+            )
+        {
+            return Err(syn::Error::new(
+                span,
+                "tower_service requires an async function using boxed futures (`box_future`)",
+            ));
+        }
+
+        let mut arguments = sig.inputs.iter().filter_map(|fn_arg| match fn_arg {
+            syn::FnArg::Receiver(_) => None,
+            syn::FnArg::Typed(pat_type) => Some(pat_type),
+        });
+        let request_ty = match (arguments.next(), arguments.next()) {
+            (Some(pat_type), None) => match pat_type.ty.as_ref() {
+                syn::Type::Reference(_) => {
+                    return Err(syn::Error::new(
+                        span,
+                        "tower_service requires the request argument to be passed by value",
+                    ))
+                }
+                ty => ty,
+            },
+            _ => {
+                return Err(syn::Error::new(
+                    span,
+                    "tower_service requires exactly one argument besides the dependencies",
+                ))
+            }
+        };
+
+        let entrait = &self.crate_idents.entrait;
+        let core = &self.crate_idents.core;
+        let vis = self.trait_visibility;
+        let trait_ident = self.trait_ident;
+        let service_ident = self.service_ident;
+        let fn_ident = &sig.ident;
+
+        let output_ty = match &sig.output {
+            syn::ReturnType::Default => quote_spanned! { span=> () },
+            syn::ReturnType::Type(_, ty) => quote_spanned! { span=> #ty },
+        };
+        let (response_ty, error_ty, call) = match ResultParts::detect(&sig.output) {
+            Some(ResultParts { ok, err }) => (
+                quote_spanned! { span=> #ok },
+                quote_spanned! { span=> #err },
+                quote_spanned! { span=>
+                    <EntraitD as #trait_ident>::#fn_ident(&deps, request).await
+                },
+            ),
+            None => (
+                output_ty,
+                quote_spanned! { span=> ::#core::convert::Infallible },
+                quote_spanned! { span=>
+                    ::#core::result::Result::Ok(<EntraitD as #trait_ident>::#fn_ident(&deps, request).await)
+                },
+            ),
+        };
+
+        Ok(quote_spanned! { span=>
+            #[derive(Clone)]
+            #vis struct #service_ident<EntraitD>(pub EntraitD);
+
+            impl<EntraitD> ::#entrait::__tower::Service<#request_ty> for #service_ident<EntraitD>
+            where
+                EntraitD: #trait_ident
+                    + ::#core::clone::Clone
+                    + ::#core::marker::Send
+                    + ::#core::marker::Sync
+                    + 'static,
+            {
+                type Response = #response_ty;
+                type Error = #error_ty;
+                type Future = ::#entrait::__tower::BoxFuture<
+                    ::#core::result::Result<Self::Response, Self::Error>
+                >;
+
+                fn poll_ready(
+                    &mut self,
+                    _: &mut ::#core::task::Context<'_>,
+                ) -> ::#core::task::Poll<::#core::result::Result<(), Self::Error>> {
+                    ::#core::task::Poll::Ready(::#core::result::Result::Ok(()))
+                }
+
+                fn call(&mut self, request: #request_ty) -> Self::Future {
+                    let deps = ::#core::clone::Clone::clone(&self.0);
+                    ::#entrait::__tower::box_future(async move { #call })
+                }
+            }
+        })
+    }
+}
@@ -30,11 +30,23 @@ impl<'s> TraitCodegen<'s> {
     ) -> syn::Result<TokenStream> {
         let span = trait_ident.span();
 
+        let opt_mockall_automock_attr = match self.opts.default_option(self.opts.mockall, false) {
+            SpanOpt(true, span) => Some(attributes::ExportGatedAttr {
+                params: attributes::MockallAutomockParams { span },
+                opts: self.opts,
+            }),
+            _ => None,
+        };
         let opt_unimock_attr = match self.opts.default_option(self.opts.unimock, false) {
             SpanOpt(true, span) => Some(attributes::ExportGatedAttr {
                 params: attributes::UnimockAttrParams {
                     trait_ident,
-                    mock_api: self.opts.mock_api.as_ref(),
+                    // With mockall, `mock_api` names the mockall mock instead
+                    mock_api: self
+                        .opts
+                        .mock_api
+                        .as_ref()
+                        .filter(|_| opt_mockall_automock_attr.is_none()),
                     trait_indirection: self.trait_indirection,
                     crate_idents: self.crate_idents,
                     trait_fns,
@@ -55,13 +67,6 @@ impl<'s> TraitCodegen<'s> {
             _ => None,
         };
 
-        let opt_mockall_automock_attr = match self.opts.default_option(self.opts.mockall, false) {
-            SpanOpt(true, span) => Some(attributes::ExportGatedAttr {
-                params: attributes::MockallAutomockParams { span },
-                opts: self.opts,
-            }),
-            _ => None,
-        };
         let opt_async_trait_attr =
             attributes::opt_async_trait_attr(self.opts, self.crate_idents, trait_fns.iter());
 
@@ -90,6 +95,16 @@ impl<'s> TraitCodegen<'s> {
             }
         });
 
+        let opt_mockall_alias = match (&opt_mockall_automock_attr, &self.opts.mock_api) {
+            (Some(_), Some(mock_api)) => Some(attributes::MockallAlias {
+                visibility: &trait_visibility,
+                trait_ident,
+                mock_api,
+                opts: self.opts,
+            }),
+            _ => None,
+        };
+
         let params = trait_generics.trait_params();
         let where_clause = trait_generics.trait_where_clause();
 
@@ -102,6 +117,7 @@ impl<'s> TraitCodegen<'s> {
             #trait_visibility trait #trait_ident #params #supertraits #where_clause {
                 #(#fn_defs)*
             }
+            #opt_mockall_alias
         })
     }
 }
@@ -153,7 +169,10 @@ impl<'a> ToTokens for TraitVisibility<'a> {
                     }
                 }
             }
-            FnInputMode::SingleFn(_) | FnInputMode::RawTrait(_) => {
+            FnInputMode::SingleFn(_)
+            | FnInputMode::ImplBlockApi(_)
+            | FnInputMode::BlockingVariant
+            | FnInputMode::RawTrait(_) => {
                 push_tokens!(stream, self.visibility);
             }
         }
@@ -0,0 +1,221 @@
+//! Code generation for the wrappers around `Impl<T>` that entrait implements traits for,
+//! `::entrait::overrides::Overrides<T>` and `::entrait::scoped::Scoped<T, C>`.
+//!
+//! Everything generated for a wrapper is wrapped in a feature gating macro like `::entrait::__overrides! {}`,
+//! which expands to nothing unless the corresponding feature of entrait is enabled.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote_spanned, ToTokens};
+
+use crate::analyze_generics::TraitFn;
+use crate::generics::TraitGenerics;
+use crate::idents::{CrateIdents, GenericIdents};
+use crate::opt::{AsyncStrategy, SpanOpt};
+
+/// A wrapper type around `Impl<T>`, which gets its own implementation of each trait.
+#[derive(Clone, Copy)]
+pub enum ImplWrapper {
+    /// `::entrait::overrides::Overrides<T>`
+    Overrides,
+    /// `::entrait::scoped::Scoped<T, C>`
+    Scoped,
+}
+
+impl ImplWrapper {
+    pub const ALL: [ImplWrapper; 2] = [ImplWrapper::Overrides, ImplWrapper::Scoped];
+
+    fn gate_macro(self, span: Span) -> syn::Ident {
+        match self {
+            Self::Overrides => syn::Ident::new("__overrides", span),
+            Self::Scoped => syn::Ident::new("__scoped", span),
+        }
+    }
+
+    fn module_and_type(self, span: Span) -> (syn::Ident, syn::Ident) {
+        match self {
+            Self::Overrides => (
+                syn::Ident::new("overrides", span),
+                syn::Ident::new("Overrides", span),
+            ),
+            Self::Scoped => (
+                syn::Ident::new("scoped", span),
+                syn::Ident::new("Scoped", span),
+            ),
+        }
+    }
+
+    /// Whether the wrapper's own type parameter for the context (`C`) has to be declared.
+    pub fn has_scope_c(self) -> bool {
+        matches!(self, Self::Scoped)
+    }
+
+    /// `::entrait::overrides::Overrides<EntraitT>`
+    pub fn path<'s, 'c>(
+        self,
+        generic_idents: &'s GenericIdents<'c>,
+        span: Span,
+    ) -> WrapperPath<'s, 'c> {
+        WrapperPath {
+            wrapper: self,
+            generic_idents,
+            span,
+        }
+    }
+
+    /// `::entrait::overrides::Overrides::as_impl(self)`
+    pub fn as_impl_expr(self, crate_idents: &CrateIdents, span: Span) -> TokenStream {
+        let entrait = &crate_idents.entrait;
+        let (module, ty) = self.module_and_type(span);
+
+        quote_spanned! { span=>
+            ::#entrait::#module::#ty::as_impl(self)
+        }
+    }
+}
+
+/// Whether a `dyn Trait` can be made out of the trait, so that it can be stored in the override table.
+#[derive(Clone, Copy)]
+pub struct IsOverridable(pub bool);
+
+pub fn detect_overridable(
+    async_strategy: SpanOpt<AsyncStrategy>,
+    trait_generics: &TraitGenerics,
+    trait_fns: &[TraitFn],
+) -> IsOverridable {
+    if !trait_generics.params.is_empty() {
+        return IsOverridable(false);
+    }
+
+    IsOverridable(trait_fns.iter().all(|trait_fn| {
+        let sig = trait_fn.sig();
+
+        let has_type_params = sig
+            .generics
+            .params
+            .iter()
+            .any(|param| !matches!(param, syn::GenericParam::Lifetime(_)));
+        let takes_self_by_value = matches!(
+            sig.inputs.first(),
+            Some(syn::FnArg::Receiver(receiver)) if receiver.reference.is_none()
+        );
+        let async_ok = !trait_fn.originally_async
+            || matches!(async_strategy, SpanOpt(AsyncStrategy::BoxFuture, _));
+        // `impl Trait` return types and associated types can't be made into a `dyn Trait`
+        let output_ok = trait_fn.entrait_sig.associated_fut.is_none()
+            && !matches!(
+                &sig.output,
+                syn::ReturnType::Type(_, ty) if matches!(ty.as_ref(), syn::Type::ImplTrait(_))
+            );
+
+        !has_type_params && !takes_self_by_value && async_ok && output_ok
+    }))
+}
+
+/// `::entrait::__overrides! { ... }`
+pub struct FeatureGated<'s, T> {
+    pub wrapper: ImplWrapper,
+    pub crate_idents: &'s CrateIdents,
+    pub span: Span,
+    pub tokens: T,
+}
+
+impl<'s, T: ToTokens> ToTokens for FeatureGated<'s, T> {
+    fn to_tokens(&self, stream: &mut TokenStream) {
+        let entrait = &self.crate_idents.entrait;
+        let gate_macro = self.wrapper.gate_macro(self.span);
+        let tokens = &self.tokens;
+
+        quote_spanned! { self.span=>
+            ::#entrait::#gate_macro! {
+                #tokens
+            }
+        }
+        .to_tokens(stream);
+    }
+}
+
+/// `::entrait::overrides::Overrides<EntraitT>` or `::entrait::scoped::Scoped<EntraitT, EntraitC>`
+pub struct WrapperPath<'s, 'c> {
+    wrapper: ImplWrapper,
+    generic_idents: &'s GenericIdents<'c>,
+    span: Span,
+}
+
+impl<'s, 'c> ToTokens for WrapperPath<'s, 'c> {
+    fn to_tokens(&self, stream: &mut TokenStream) {
+        let entrait = &self.generic_idents.crate_idents.entrait;
+        let (module, ty) = self.wrapper.module_and_type(self.span);
+        let impl_t = &self.generic_idents.impl_t;
+
+        if self.wrapper.has_scope_c() {
+            let scope_c = &self.generic_idents.scope_c;
+            quote_spanned! { self.span=>
+                ::#entrait::#module::#ty<#impl_t, #scope_c>
+            }
+            .to_tokens(stream);
+        } else {
+            quote_spanned! { self.span=>
+                ::#entrait::#module::#ty<#impl_t>
+            }
+            .to_tokens(stream);
+        }
+    }
+}
+
+/// `impl ::entrait::overrides::Overridable for dyn Trait { .. }`
+pub struct OverridableImpl<'s, TR> {
+    pub crate_idents: &'s CrateIdents,
+    pub trait_ref: &'s TR,
+    pub span: Span,
+}
+
+impl<'s, TR: ToTokens> ToTokens for OverridableImpl<'s, TR> {
+    fn to_tokens(&self, stream: &mut TokenStream) {
+        let entrait = &self.crate_idents.entrait;
+        let core = &self.crate_idents.core;
+        let trait_ref = self.trait_ref;
+
+        quote_spanned! { self.span=>
+            impl ::#entrait::overrides::Overridable for dyn #trait_ref {
+                type Object = dyn #trait_ref + ::#core::marker::Send + ::#core::marker::Sync;
+            }
+        }
+        .to_tokens(stream);
+    }
+}
+
+/// The statement that looks up and calls an overriding stub, e.g.
+///
+/// ```no_compile
+/// if let Some(__stub) = ::entrait::overrides::Overrides::lookup::<dyn Trait>(self) {
+///     return __stub.method(args);
+/// }
+/// ```
+pub struct OverrideLookup<'s, TR> {
+    pub crate_idents: &'s CrateIdents,
+    pub trait_ref: &'s TR,
+    pub trait_fn: &'s TraitFn,
+    /// The arguments forwarded to the stub, see `EntraitSignature::arg_idents`
+    pub arguments: Vec<&'s syn::Ident>,
+    pub span: Span,
+}
+
+impl<'s, TR: ToTokens> ToTokens for OverrideLookup<'s, TR> {
+    fn to_tokens(&self, stream: &mut TokenStream) {
+        let entrait = &self.crate_idents.entrait;
+        let core = &self.crate_idents.core;
+        let trait_ref = self.trait_ref;
+        let fn_ident = &self.trait_fn.sig().ident;
+        let opt_dot_await = self.trait_fn.opt_dot_await(self.span);
+        let arguments = &self.arguments;
+
+        quote_spanned! { self.span=>
+            if let ::#core::option::Option::Some(__stub) =
+                ::#entrait::overrides::Overrides::lookup::<dyn #trait_ref>(self)
+            {
+                return __stub.#fn_ident(#(#arguments),*) #opt_dot_await;
+            }
+        }
+        .to_tokens(stream);
+    }
+}
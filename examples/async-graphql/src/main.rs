@@ -9,18 +9,16 @@ mod db {
 
 mod graphql {
     use super::db;
+    use entrait::integration::async_graphql::*;
     use std::marker::PhantomData;
 
     pub struct Query<A>(PhantomData<A>);
 
+    #[inject_deps]
     #[async_graphql::Object]
-    impl<A> Query<A>
-    where
-        A: db::FetchSomeValue + Send + Sync + 'static,
-    {
-        async fn some_value(&self, ctx: &async_graphql::Context<'_>) -> Result<String, String> {
-            let app = ctx.data_unchecked::<A>();
-            Ok(app.fetch_some_value().await)
+    impl<A> Query<A> {
+        async fn some_value(&self, deps: &impl db::FetchSomeValue) -> Result<String, String> {
+            Ok(deps.fetch_some_value().await)
         }
     }
 
@@ -42,7 +40,7 @@ mod graphql {
             EmptyMutation,
             EmptySubscription,
         )
-        .data(deps.clone())
+        .deps(deps.clone())
         .finish()
         .execute("{ someValue }")
         .await;
@@ -66,7 +64,7 @@ mod graphql {
             EmptyMutation,
             EmptySubscription,
         )
-        .data(app)
+        .deps(app)
         .finish()
         .execute("{ someValue }")
         .await;
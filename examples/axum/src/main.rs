@@ -22,23 +22,19 @@ mod business {
 /// Axum specific
 mod rest {
     use super::*;
-    use axum::extract::Extension;
     use axum::routing::get;
     use axum::Json;
+    use entrait::integration::axum::{AxumDeps, Deps};
 
-    pub struct Routes<A>(std::marker::PhantomData<A>);
-
-    impl<A> Routes<A>
+    pub fn routes<A>(router: axum::Router<A>) -> axum::Router<A>
     where
-        A: business::GetFoo + Send + Sync + Sized + Clone + 'static,
+        A: AxumDeps + business::GetFoo,
     {
-        pub fn router() -> axum::Router {
-            axum::Router::new().route("/foo", get(Self::get_foo))
-        }
+        router.route("/foo", get(get_foo::<A>))
+    }
 
-        async fn get_foo(Extension(app): Extension<A>) -> Json<Foo> {
-            Json(app.get_foo().await)
-        }
+    async fn get_foo<A: business::GetFoo>(Deps(app): Deps<A>) -> Json<Foo> {
+        Json(app.get_foo().await)
     }
 
     #[tokio::test]
@@ -50,7 +46,7 @@ mod rest {
         let deps = Unimock::new(business::GetFooMock.each_call(matching!()).returns(Foo {
             value: "mocked".to_string(),
         }));
-        let router = Routes::<Unimock>::router().layer(Extension(deps.clone()));
+        let router = entrait::integration::axum::router(deps.clone(), routes);
         let response = router
             .oneshot(
                 Request::get("/foo")
@@ -68,15 +64,13 @@ mod rest {
 
 #[tokio::main]
 async fn main() {
-    use axum::extract::Extension;
     use entrait::Impl;
     use std::net::SocketAddr;
 
     #[derive(Clone)]
     struct App;
 
-    let app = Impl::new(App);
-    let router = rest::Routes::<Impl<App>>::router().layer(Extension(app));
+    let router = entrait::integration::axum::router(Impl::new(App), rest::routes);
 
     axum::Server::bind(&SocketAddr::from(([127, 0, 0, 1], 3000)))
         .serve(router.into_make_service())
@@ -0,0 +1,67 @@
+//! Actor delegation, for the `delegate_by = actor` entrait option.
+//!
+//! State owned by a single task can be exposed through an entraited trait.
+//! `#[entrait(FooActor, delegate_by = actor)]` on a trait `Foo` generates:
+//!
+//! * `FooActorMessage`: An enum with one variant per method, holding its arguments and a reply channel.
+//! * `FooActor`: A cloneable handle implementing `Foo` by sending messages over an mpsc channel and awaiting the reply.
+//!   `FooActor::channel(buffer)` creates a handle and its receiver,
+//!   and `FooActor::run(receiver, target)` executes messages against any `target: Foo`, one at a time.
+//! * An implementation of `Foo` for `Impl<T>` where `T: AsRef<FooActor>`, so the application calls the actor
+//!   like any other dependency.
+//!
+//! ```
+//! # use entrait::*;
+//! # use entrait::actor::ActorStopped;
+//! # use std::sync::atomic::{AtomicU32, Ordering};
+//! #[entrait(CounterActor, delegate_by = actor, box_future)]
+//! pub trait Counter {
+//!     async fn increment(&self, amount: u32) -> Result<u32, ActorStopped>;
+//! }
+//!
+//! struct Count(AtomicU32);
+//!
+//! #[async_trait::async_trait]
+//! impl Counter for Count {
+//!     async fn increment(&self, amount: u32) -> Result<u32, ActorStopped> {
+//!         Ok(self.0.fetch_add(amount, Ordering::SeqCst) + amount)
+//!     }
+//! }
+//!
+//! struct App {
+//!     counter: CounterActor,
+//! }
+//!
+//! impl AsRef<CounterActor> for App {
+//!     fn as_ref(&self) -> &CounterActor {
+//!         &self.counter
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")] async fn main() {
+//! let (counter, receiver) = CounterActor::channel(16);
+//! tokio::spawn(CounterActor::run(receiver, Count(AtomicU32::new(0))));
+//!
+//! let app = Impl::new(App { counter });
+//! assert_eq!(Ok(2), app.increment(2).await);
+//! assert_eq!(Ok(5), app.increment(3).await);
+//! # }
+//! ```
+//!
+//! All methods must be async using boxed futures, take `&self`, and take their arguments by value.
+//! They must return `Result<T, E>` where `E: From<ActorStopped>`:
+//! If the actor has stopped, the handle returns [ActorStopped] converted into `E`.
+
+use core::fmt;
+
+/// The error of calling an actor that is no longer running.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ActorStopped;
+
+impl fmt::Display for ActorStopped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the actor has stopped")
+    }
+}
+
+impl std::error::Error for ActorStopped {}
@@ -0,0 +1,144 @@
+//! Boxed iterators and streams, for entraited functions returning `impl Iterator` or `impl Stream`.
+//!
+//! The return type of such a function can't be named in the generated trait.
+//! With boxed futures (the `box_future` option), entrait boxes it instead:
+//!
+//! * `impl Iterator<Item = T>` becomes [BoxIter<'_, T>](BoxIter).
+//! * `impl Stream<Item = T>` becomes [BoxStream<'_, T>](BoxStream), which requires the `stream` feature.
+//!
+//! Like boxed futures, the boxed values must be [Send].
+//!
+//! ```
+//! # use entrait::*;
+//! #[entrait(Numbers, box_future)]
+//! fn numbers(_deps: &impl std::any::Any, count: u32) -> impl Iterator<Item = u32> {
+//!     0..count
+//! }
+//!
+//! let app = Impl::new(());
+//! let numbers: entrait::boxed::BoxIter<'_, u32> = app.numbers(3);
+//! assert_eq!(vec![0, 1, 2], numbers.collect::<Vec<_>>());
+//! ```
+//!
+//! Both types can be created from a `Vec` of items, so mocks can respond with pre-built items.
+//! With unimock, which needs to create a new response for every call, use `answers`:
+//!
+//! ```
+//! # #[cfg(feature = "unimock")]
+//! # {
+//! # use entrait::entrait_export as entrait;
+//! # use unimock::*;
+//! #[entrait(Numbers, box_future, mock_api = NumbersMock)]
+//! fn numbers(_deps: &impl std::any::Any, count: u32) -> impl Iterator<Item = u32> {
+//!     0..count
+//! }
+//!
+//! let mock = Unimock::new(
+//!     NumbersMock
+//!         .each_call(matching!(_))
+//!         .answers(|_| vec![42, 43]),
+//! );
+//! assert_eq!(vec![42, 43], mock.numbers(100).collect::<Vec<_>>());
+//! # }
+//! ```
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "stream")]
+use core::pin::Pin;
+#[cfg(feature = "stream")]
+use core::task::{Context, Poll};
+
+/// A boxed, sendable iterator.
+pub struct BoxIter<'a, T>(Box<dyn Iterator<Item = T> + Send + 'a>);
+
+impl<'a, T> BoxIter<'a, T> {
+    /// Box an iterator.
+    pub fn new(iter: impl Iterator<Item = T> + Send + 'a) -> Self {
+        Self(Box::new(iter))
+    }
+}
+
+impl<'a, T> Iterator for BoxIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, T: Send + 'a> From<Vec<T>> for BoxIter<'a, T> {
+    fn from(items: Vec<T>) -> Self {
+        Self::new(items.into_iter())
+    }
+}
+
+impl<'a, T> fmt::Debug for BoxIter<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoxIter").finish_non_exhaustive()
+    }
+}
+
+/// A boxed, sendable stream.
+#[cfg(feature = "stream")]
+pub struct BoxStream<'a, T>(Pin<Box<dyn ::futures_core::Stream<Item = T> + Send + 'a>>);
+
+#[cfg(feature = "stream")]
+impl<'a, T> BoxStream<'a, T> {
+    /// Box a stream.
+    pub fn new(stream: impl ::futures_core::Stream<Item = T> + Send + 'a) -> Self {
+        Self(Box::pin(stream))
+    }
+}
+
+#[cfg(feature = "stream")]
+impl<'a, T> ::futures_core::Stream for BoxStream<'a, T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.0.as_mut().poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+#[cfg(feature = "stream")]
+impl<'a, T: Send + 'a> From<Vec<T>> for BoxStream<'a, T> {
+    fn from(items: Vec<T>) -> Self {
+        Self::new(Items(items.into_iter()))
+    }
+}
+
+#[cfg(feature = "stream")]
+impl<'a, T> fmt::Debug for BoxStream<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoxStream").finish_non_exhaustive()
+    }
+}
+
+/// A stream of items that are ready immediately.
+#[cfg(feature = "stream")]
+struct Items<T>(alloc::vec::IntoIter<T>);
+
+#[cfg(feature = "stream")]
+impl<T> Unpin for Items<T> {}
+
+#[cfg(feature = "stream")]
+impl<T> ::futures_core::Stream for Items<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<T>> {
+        Poll::Ready(self.0.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
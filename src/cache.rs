@@ -0,0 +1,205 @@
+//! Memoization of entraited functions, for the `cache` entrait option.
+//!
+//! Pure but expensive functions, like configuration lookups, may be cached per application.
+//! With the `cache` option, the generated delegation from `Impl<T>` to the function first looks up
+//! the function's arguments in the [Cache] of the application, which is provided by implementing [HasCache] for `T`:
+//!
+//! ```
+//! # use entrait::*;
+//! # use entrait::cache::*;
+//! # use std::sync::atomic::{AtomicU32, Ordering};
+//! static LOOKUPS: AtomicU32 = AtomicU32::new(0);
+//!
+//! #[entrait(LookupFlag, no_deps, cache, cache_ttl_secs = 60)]
+//! fn lookup_flag(name: &str) -> bool {
+//!     LOOKUPS.fetch_add(1, Ordering::SeqCst);
+//!     name == "enabled"
+//! }
+//!
+//! #[derive(Default)]
+//! struct App {
+//!     cache: Cache,
+//! }
+//!
+//! impl HasCache for App {
+//!     fn entrait_cache(&self) -> &Cache {
+//!         &self.cache
+//!     }
+//! }
+//!
+//! let app = Impl::new(App::default());
+//! assert!(app.lookup_flag("enabled"));
+//! assert!(app.lookup_flag("enabled"));
+//! assert!(!app.lookup_flag("disabled"));
+//! assert_eq!(2, LOOKUPS.load(Ordering::SeqCst));
+//! ```
+//!
+//! Entries are keyed by the path of the function and an owned copy of its arguments, created using [ToOwned].
+//! The owned arguments must implement [Hash] and [Eq], and be `Send + Sync + 'static`,
+//! and the return value must implement [Clone].
+//! `cache_ttl_secs = N` makes entries expire after `N` seconds, and implies `cache`.
+//!
+//! Only the implementation for `Impl<T>` (or for the concrete deps type) is cached.
+//! Mocks implement the trait directly, so they always bypass the cache,
+//! as do the implementations for wrappers like [`Scoped`](crate::scoped::Scoped), whose results may depend on more than the arguments.
+
+use std::any::Any;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::Impl;
+
+/// Provides the [Cache] used by cached entraited functions.
+///
+/// Implement this for the application type `T`, and `Impl<T>` implements it as well.
+pub trait HasCache {
+    /// The cache of the application.
+    fn entrait_cache(&self) -> &Cache;
+}
+
+impl<T: HasCache> HasCache for Impl<T> {
+    fn entrait_cache(&self) -> &Cache {
+        (**self).entrait_cache()
+    }
+}
+
+/// The key of a cached value: a function and its arguments.
+///
+/// The arguments are stored in the key, so that calls with different arguments never share a value,
+/// even if the hashes of their arguments collide.
+pub struct CacheKey {
+    function: &'static str,
+    hash: u64,
+    args: Box<dyn CacheArgs>,
+}
+
+impl CacheKey {
+    /// Create the key for calling `function` with `args`, usually a tuple of the owned arguments.
+    pub fn new<A: Hash + Eq + Send + Sync + 'static>(function: &'static str, args: A) -> Self {
+        let mut hasher = DefaultHasher::new();
+        args.hash(&mut hasher);
+
+        Self {
+            function,
+            hash: hasher.finish(),
+            args: Box::new(args),
+        }
+    }
+
+    /// The path of the cached function.
+    pub fn function(&self) -> &'static str {
+        self.function
+    }
+}
+
+impl PartialEq for CacheKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.function == other.function
+            && self.hash == other.hash
+            && self.args.eq_args(other.args.as_ref())
+    }
+}
+
+impl Eq for CacheKey {}
+
+impl Hash for CacheKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.function.hash(state);
+        self.hash.hash(state);
+    }
+}
+
+impl std::fmt::Debug for CacheKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CacheKey")
+            .field("function", &self.function)
+            .field("hash", &self.hash)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Type-erased arguments of a [CacheKey], comparable to the arguments of other keys.
+trait CacheArgs: Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+
+    fn eq_args(&self, other: &dyn CacheArgs) -> bool;
+}
+
+impl<A: Eq + Send + Sync + 'static> CacheArgs for A {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn eq_args(&self, other: &dyn CacheArgs) -> bool {
+        other.as_any().downcast_ref::<A>() == Some(self)
+    }
+}
+
+struct Entry {
+    value: Box<dyn Any + Send + Sync>,
+    expires_at: Option<Instant>,
+}
+
+/// A thread-safe store of cached function results.
+#[derive(Default)]
+pub struct Cache {
+    entries: Mutex<HashMap<CacheKey, Entry>>,
+}
+
+impl Cache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the value cached for `key`, unless it has expired.
+    pub fn get<V: Clone + 'static>(&self, key: &CacheKey) -> Option<V> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.get(key)?;
+
+        match entry.expires_at {
+            Some(expires_at) if expires_at <= Instant::now() => {
+                entries.remove(key);
+                None
+            }
+            _ => entry.value.downcast_ref::<V>().cloned(),
+        }
+    }
+
+    /// Cache `value` for `key`, optionally expiring after `ttl`.
+    pub fn insert<V: Send + Sync + 'static>(&self, key: CacheKey, value: V, ttl: Option<Duration>) {
+        self.entries.lock().unwrap().insert(
+            key,
+            Entry {
+                value: Box::new(value),
+                expires_at: ttl.map(|ttl| Instant::now() + ttl),
+            },
+        );
+    }
+
+    /// Remove all values cached for the function at the path `function`.
+    ///
+    /// The path is the one returned by [CacheKey::function], i.e. `module::path::function_name`.
+    pub fn invalidate(&self, function: &str) {
+        self.entries
+            .lock()
+            .unwrap()
+            .retain(|key, _| key.function != function);
+    }
+
+    /// Remove all cached values.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+impl std::fmt::Debug for Cache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cache")
+            .field("len", &self.entries.lock().unwrap().len())
+            .finish()
+    }
+}
@@ -0,0 +1,216 @@
+//! In-memory fakes of repository traits, for the `fake` entrait option.
+//!
+//! Mocks need expectations for every test. For CRUD-like repository traits, a fake is often more convenient:
+//! An implementation that actually stores entries, just not in a database.
+//!
+//! The `fake(Name, key = K, value = V)` option on an entraited trait generates a type `Name`,
+//! which implements the trait using a [FakeStore] of `K` to `V`.
+//! Each method of the trait must follow a naming convention, by starting with `get`, `insert`, `update` or `delete`:
+//!
+//! | Method                                  | Behaviour                                                        |
+//! | --------------------------------------- | ---------------------------------------------------------------- |
+//! | `get*(&self, key) -> R`                 | Looks up the value of `key`.                                     |
+//! | `insert*(&self, key, value) -> R`       | Inserts or replaces the value of `key`.                          |
+//! | `update*(&self, key, value) -> R`       | Replaces the value of `key`, only if it is present.              |
+//! | `delete*(&self, key) -> R`              | Removes the value of `key`.                                      |
+//!
+//! Keys and values may be passed by value or by reference.
+//! The return type `R` is created from the value that was found, replaced or removed, see [FakeOutput].
+//! It can be an `Option<V>`, a `bool` telling whether there was a value, `()`, or a `Result` wrapping one of those.
+//!
+//! ```
+//! # use entrait::*;
+//! #[derive(Clone, Debug, PartialEq)]
+//! pub struct User {
+//!     name: String,
+//! }
+//!
+//! #[entrait(fake(InMemoryUsers, key = u32, value = User))]
+//! pub trait UserRepository {
+//!     fn get_user(&self, id: u32) -> Option<User>;
+//!     fn insert_user(&self, id: u32, user: User);
+//!     fn delete_user(&self, id: u32) -> bool;
+//! }
+//!
+//! #[entrait(RenameUser)]
+//! fn rename_user(deps: &impl UserRepository, id: u32, name: &str) -> bool {
+//!     match deps.get_user(id) {
+//!         Some(_) => {
+//!             deps.insert_user(id, User { name: name.to_string() });
+//!             true
+//!         }
+//!         None => false,
+//!     }
+//! }
+//!
+//! let app = Impl::new(InMemoryUsers::new());
+//! app.insert_user(1, User { name: "Bob".to_string() });
+//!
+//! assert!(app.rename_user(1, "Alice"));
+//! assert!(!app.rename_user(2, "Alice"));
+//! assert_eq!(Some(User { name: "Alice".to_string() }), app.get_user(1));
+//! ```
+//!
+//! With `delegate_by = ref`, the fake also implements the generated dynamic dispatch trait.
+//! With a custom delegation trait, the fake implements the generated implementation trait for any `T: AsRef<Fake>`,
+//! so it can be used as the `Target` of the delegation, while the application owns the fake:
+//!
+//! ```
+//! # use entrait::*;
+//! #[entrait(RepositoryImpl, delegate_by = DelegateRepository, fake(InMemoryRepository, key = String, value = i32))]
+//! pub trait Repository {
+//!     fn get(&self, key: &str) -> Option<i32>;
+//!     fn insert(&self, key: &str, value: i32);
+//! }
+//!
+//! #[derive(Default)]
+//! struct App {
+//!     repository: InMemoryRepository,
+//! }
+//!
+//! impl DelegateRepository<Self> for App {
+//!     type Target = InMemoryRepository;
+//! }
+//!
+//! impl AsRef<InMemoryRepository> for App {
+//!     fn as_ref(&self) -> &InMemoryRepository {
+//!         &self.repository
+//!     }
+//! }
+//!
+//! let app = Impl::new(App::default());
+//! app.insert("answer", 42);
+//! assert_eq!(Some(42), app.get("answer"));
+//! assert_eq!(1, app.repository.len());
+//! ```
+//!
+//! The generated type dereferences to its [FakeStore], for seeding and inspecting entries in tests.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::Mutex;
+
+/// The entries of an in-memory fake, behind a lock.
+pub struct FakeStore<K, V>(Mutex<HashMap<K, V>>);
+
+impl<K, V> FakeStore<K, V> {
+    /// An empty store.
+    pub fn new() -> Self {
+        Self(Mutex::new(HashMap::new()))
+    }
+
+    /// The number of entries.
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+
+    /// Whether the store has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.0.lock().unwrap().is_empty()
+    }
+
+    /// Remove all entries.
+    pub fn clear(&self) {
+        self.0.lock().unwrap().clear();
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> FakeStore<K, V> {
+    /// A copy of the value of `key`.
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.0.lock().unwrap().get(key).cloned()
+    }
+
+    /// Insert `value` for `key`, returning the previous value.
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        self.0.lock().unwrap().insert(key, value)
+    }
+
+    /// Replace the value of `key`, if present, returning the previous value.
+    pub fn update<Q>(&self, key: &Q, value: V) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.0
+            .lock()
+            .unwrap()
+            .get_mut(key)
+            .map(|current| std::mem::replace(current, value))
+    }
+
+    /// Remove the value of `key`, returning it.
+    pub fn delete<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.0.lock().unwrap().remove(key)
+    }
+}
+
+impl<K: Clone, V: Clone> FakeStore<K, V> {
+    /// A copy of all entries, in arbitrary order.
+    pub fn entries(&self) -> Vec<(K, V)> {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
+    }
+}
+
+impl<K, V> Default for FakeStore<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Eq + Hash, V> FromIterator<(K, V)> for FakeStore<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(Mutex::new(iter.into_iter().collect()))
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for FakeStore<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.0.lock().unwrap().iter())
+            .finish()
+    }
+}
+
+/// Return types of fake methods, created from the value that was found, replaced or removed.
+pub trait FakeOutput<V> {
+    /// Create the return value.
+    fn from_fake(value: Option<V>) -> Self;
+}
+
+impl<V> FakeOutput<V> for Option<V> {
+    fn from_fake(value: Option<V>) -> Self {
+        value
+    }
+}
+
+impl<V> FakeOutput<V> for bool {
+    fn from_fake(value: Option<V>) -> Self {
+        value.is_some()
+    }
+}
+
+impl<V> FakeOutput<V> for () {
+    fn from_fake(_: Option<V>) -> Self {}
+}
+
+impl<V, T: FakeOutput<V>, E> FakeOutput<V> for Result<T, E> {
+    fn from_fake(value: Option<V>) -> Self {
+        Ok(T::from_fake(value))
+    }
+}
@@ -0,0 +1,106 @@
+//! The [Impl] type, which entraited traits are implemented for.
+
+use core::ops::{Deref, DerefMut};
+
+#[cfg(feature = "alloc")]
+use alloc::sync::Arc;
+
+/// Wrapper type for targeting and accessing the actual implementation of entraited traits.
+///
+/// [Impl] has smart-pointer capabilities, as it implements [Deref] and [DerefMut] to the wrapped `T`.
+///
+/// ```
+/// # use entrait::*;
+/// #[entrait(Greet, no_deps)]
+/// fn greet(name: &str) -> String {
+///     format!("Hello, {name}!")
+/// }
+///
+/// let app = Impl::new(());
+/// assert_eq!("Hello, world!", app.greet("world"));
+/// ```
+///
+/// # Sharing
+/// Frameworks like axum and async-graphql clone the application for every request.
+/// [Impl::shared] wraps the `T` in an [Arc](alloc::sync::Arc), so cloning the resulting `Impl<Arc<T>>` is cheap:
+///
+/// ```
+/// # use entrait::*;
+/// struct App {
+///     config: Vec<String>,
+/// }
+///
+/// let app = Impl::shared(App { config: vec![] });
+/// let handler_app = app.clone();
+///
+/// assert!(handler_app.config.is_empty());
+/// ```
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Impl<T>(T);
+
+impl<T> Impl<T> {
+    /// Construct a new [Impl].
+    pub fn new(value: T) -> Impl<T> {
+        Impl(value)
+    }
+
+    /// Take out the wrapped `T`.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    /// Transform the wrapped `T`, e.g. to add another layer of application state.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Impl<U> {
+        Impl(f(self.0))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Impl<Arc<T>> {
+    /// Construct a new [Impl] that is cheap to clone, by sharing `value` between all its clones.
+    pub fn shared(value: T) -> Impl<Arc<T>> {
+        Impl(Arc::new(value))
+    }
+}
+
+impl<T> From<T> for Impl<T> {
+    fn from(value: T) -> Impl<T> {
+        Impl(value)
+    }
+}
+
+impl<T> Deref for Impl<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Impl<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> AsRef<T> for Impl<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// Serializes the wrapped `T`.
+#[cfg(feature = "serde")]
+impl<T: ::serde::Serialize> ::serde::Serialize for Impl<T> {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize(serializer)
+    }
+}
+
+/// Deserializes the wrapped `T`.
+#[cfg(feature = "serde")]
+impl<'de, T: ::serde::Deserialize<'de>> ::serde::Deserialize<'de> for Impl<T> {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Impl)
+    }
+}
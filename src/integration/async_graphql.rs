@@ -0,0 +1,131 @@
+//! Integration with [async-graphql](::async_graphql).
+//!
+//! The deps of the application (e.g. `Impl<App>`, or `Unimock` in tests) are registered as data in the schema
+//! or in a single request, and resolvers obtain them from the [Context](::async_graphql::Context).
+//!
+//! With the [inject_deps] attribute, resolvers take their deps as a `deps: &impl Trait` parameter,
+//! like entraited functions.
+//! The object type is written generically over the deps type, and the attribute is put above `#[Object]`:
+//!
+//! ```
+//! # use entrait::*;
+//! # use entrait::integration::async_graphql::*;
+//! # use std::marker::PhantomData;
+//! #[entrait(FetchValue, no_deps)]
+//! fn fetch_value() -> i32 {
+//!     42
+//! }
+//!
+//! struct Query<D>(PhantomData<D>);
+//!
+//! #[inject_deps]
+//! #[async_graphql::Object]
+//! impl<D> Query<D> {
+//!     async fn value(&self, deps: &impl FetchValue) -> i32 {
+//!         deps.fetch_value()
+//!     }
+//! }
+//!
+//! struct App;
+//!
+//! let schema = async_graphql::Schema::build(
+//!     Query::<Impl<App>>(PhantomData),
+//!     async_graphql::EmptyMutation,
+//!     async_graphql::EmptySubscription,
+//! )
+//! .deps(Impl::new(App))
+//! .finish();
+//! ```
+//!
+//! In tests, the same object type can be built with a mock as `D`, e.g. `Query::<Unimock>`.
+//!
+//! The `deps` parameter is removed from the resolver, and the deps are looked up as `&D` in its context,
+//! adding `D: Trait` to the where clause of the impl block.
+//! Resolvers returning `async_graphql::Result<T>` return an error when no deps of type `D` are registered, other resolvers panic.
+//! The deps type parameter is the only type parameter of the impl block, or named like `#[inject_deps(D)]`.
+//!
+//! Without the attribute, resolvers obtain `&D` from their context using [`ctx.deps::<D>()`](ContextDepsExt::deps).
+//!
+//! Deps are stored wrapped in a private type, so they never conflict with other data of the same type.
+//! Deps registered with a request take precedence over deps registered with the schema.
+
+use ::async_graphql::{
+    Context, Error, ObjectType, Request, Result, SchemaBuilder, SubscriptionType,
+};
+
+pub use entrait_macros::inject_deps;
+
+/// Paths used by the code generated by [inject_deps].
+#[doc(hidden)]
+pub mod __private {
+    pub use ::async_graphql::Context;
+}
+
+/// Deps stored as data in the schema or request.
+struct RegisteredDeps<D>(D);
+
+/// Extension trait for registering deps with a [SchemaBuilder].
+pub trait SchemaBuilderDepsExt {
+    /// Register `deps` for all requests executed by the schema.
+    #[must_use]
+    fn deps<D: Send + Sync + 'static>(self, deps: D) -> Self;
+}
+
+impl<Query, Mutation, Subscription> SchemaBuilderDepsExt
+    for SchemaBuilder<Query, Mutation, Subscription>
+where
+    Query: ObjectType + 'static,
+    Mutation: ObjectType + 'static,
+    Subscription: SubscriptionType + 'static,
+{
+    fn deps<D: Send + Sync + 'static>(self, deps: D) -> Self {
+        self.data(RegisteredDeps(deps))
+    }
+}
+
+/// Extension trait for registering deps with a single [Request].
+pub trait RequestDepsExt {
+    /// Register `deps` for this request only, e.g. an `Impl<T>` with request-scoped state.
+    #[must_use]
+    fn deps<D: Send + Sync + 'static>(self, deps: D) -> Self;
+}
+
+impl RequestDepsExt for Request {
+    fn deps<D: Send + Sync + 'static>(self, deps: D) -> Self {
+        self.data(RegisteredDeps(deps))
+    }
+}
+
+/// Extension trait for obtaining deps from a resolver [Context].
+pub trait ContextDepsExt<'a> {
+    /// Get the registered deps of type `D`, or an error if not registered.
+    fn deps<D: Send + Sync + 'static>(&self) -> Result<&'a D>;
+
+    /// Get the registered deps of type `D`.
+    ///
+    /// # Panics
+    /// Panics if deps of type `D` are not registered.
+    fn deps_unchecked<D: Send + Sync + 'static>(&self) -> &'a D;
+}
+
+impl<'a> ContextDepsExt<'a> for Context<'a> {
+    fn deps<D: Send + Sync + 'static>(&self) -> Result<&'a D> {
+        self.data_opt::<RegisteredDeps<D>>()
+            .map(|registered| &registered.0)
+            .ok_or_else(|| Error::new(not_registered_message::<D>()))
+    }
+
+    fn deps_unchecked<D: Send + Sync + 'static>(&self) -> &'a D {
+        match self.data_opt::<RegisteredDeps<D>>() {
+            Some(registered) => &registered.0,
+            None => panic!("{}", not_registered_message::<D>()),
+        }
+    }
+}
+
+fn not_registered_message<D>() -> String {
+    format!(
+        "Deps `{}` are not registered in the schema or request.",
+        core::any::type_name::<D>()
+    )
+}
@@ -0,0 +1,77 @@
+//! Integration with [axum](::axum).
+//!
+//! An `Impl<T>` (or any other type implementing the entrait traits used by the handlers, like `Unimock`)
+//! is used as the router _state_.
+//! Handlers extract it using [Deps], and pass it on to entraited business functions.
+//!
+//! Routers are written generically over the deps type, constrained by the traits the handlers need,
+//! so the same router can be served with the real application or with a mock in tests:
+//!
+//! ```
+//! # use entrait::*;
+//! # use entrait::integration::axum::*;
+//! #[entrait(GetGreeting, no_deps)]
+//! fn get_greeting() -> String {
+//!     "Hello!".to_string()
+//! }
+//!
+//! async fn greeting<D: GetGreeting>(Deps(deps): Deps<D>) -> String {
+//!     deps.get_greeting()
+//! }
+//!
+//! fn routes<D: AxumDeps + GetGreeting>(router: ::axum::Router<D>) -> ::axum::Router<D> {
+//!     router.route("/greeting", ::axum::routing::get(greeting::<D>))
+//! }
+//!
+//! #[derive(Clone)]
+//! struct App;
+//!
+//! let router: ::axum::Router = router(Impl::new(App), routes);
+//! ```
+
+use ::axum::extract::{FromRef, FromRequestParts};
+use ::axum::http::request::Parts;
+use ::axum::Router;
+
+use core::convert::Infallible;
+use core::ops::Deref;
+
+/// Bounds required for a deps type to be used as axum router state.
+///
+/// This is implemented for every type that is `Clone + Send + Sync + 'static`, including `Impl<T>` where `T: Clone + Send + Sync + 'static`.
+pub trait AxumDeps: Clone + Send + Sync + 'static {}
+
+impl<D> AxumDeps for D where D: Clone + Send + Sync + 'static {}
+
+/// Extractor for the deps of an axum handler.
+///
+/// The deps are taken from the router state, either directly or through [FromRef].
+/// `Deps<D>` dereferences to `D`, so `&*deps` can be passed to a function taking `&impl Trait`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Deps<D>(pub D);
+
+impl<D> Deref for Deps<D> {
+    type Target = D;
+
+    fn deref(&self) -> &D {
+        &self.0
+    }
+}
+
+#[::axum::async_trait]
+impl<D, S> FromRequestParts<S> for Deps<D>
+where
+    D: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(_: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Deps(D::from_ref(state)))
+    }
+}
+
+/// Build a router generic over the deps type using `routes`, and provide it with `deps` as state.
+pub fn router<D: AxumDeps>(deps: D, routes: impl FnOnce(Router<D>) -> Router<D>) -> Router {
+    routes(Router::new()).with_state(deps)
+}
@@ -0,0 +1,10 @@
+//! Integrations with web frameworks and other libraries, each enabled by the cargo feature of the same name.
+
+#[cfg(feature = "async-graphql")]
+pub mod async_graphql;
+
+#[cfg(feature = "axum")]
+pub mod axum;
+
+#[cfg(feature = "tonic")]
+pub mod tonic;
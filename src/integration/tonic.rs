@@ -0,0 +1,221 @@
+//! gRPC support using [tonic](::tonic), for the `grpc` entrait option.
+//!
+//! `#[entrait(grpc = FooGrpc)]` on a trait `Foo` generates two types:
+//!
+//! * `FooGrpcServer<D>`: A tonic-compatible gRPC service forwarding every call to any `D: Foo`,
+//!   e.g. an `Impl<App>`. It can be added to a tonic server, as it implements [NamedService](::tonic::server::NamedService).
+//! * `FooGrpcClient<T>`: A client implementing `Foo` itself, by calling a remote `FooGrpcServer` over any [GrpcTransport],
+//!   e.g. a tonic `Channel`.
+//!
+//! Remote and in-process deployments thereby share the same trait as abstraction.
+//! Since a `FooGrpcServer` is itself a [GrpcTransport], a client can also call a server in-process, which is useful for testing:
+//!
+//! ```
+//! # use entrait::*;
+//! #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+//! struct PlanetError(String);
+//!
+//! impl From<tonic::Status> for PlanetError {
+//!     fn from(status: tonic::Status) -> Self {
+//!         Self(status.message().to_string())
+//!     }
+//! }
+//!
+//! #[entrait(grpc = PlanetGrpc, box_future)]
+//! trait FetchPlanet {
+//!     async fn fetch_planet(&self, id: u32) -> Result<String, PlanetError>;
+//! }
+//!
+//! #[derive(Clone)]
+//! struct App;
+//!
+//! #[async_trait::async_trait]
+//! impl FetchPlanet for App {
+//!     async fn fetch_planet(&self, id: u32) -> Result<String, PlanetError> {
+//!         Ok(format!("planet{id}"))
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")] async fn main() {
+//! let client = PlanetGrpcClient(PlanetGrpcServer(Impl::new(App)));
+//! assert_eq!(Ok("planet3".to_string()), client.fetch_planet(3).await);
+//! # }
+//! ```
+//!
+//! All methods must be async using boxed futures, take `&self`, and take their arguments by value.
+//! Arguments and return values must implement [serde::Serialize] and [serde::Deserialize], and are encoded as JSON.
+//! The gRPC path of each method is `/{Trait}/{method}`.
+//!
+//! Methods must return `Result<T, E>` where `E: From<Status>`:
+//! If a client call fails, the client returns the [Status] converted into `E`.
+
+use ::tonic::body::BoxBody;
+use ::tonic::client::GrpcService;
+use ::tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
+use ::tonic::codegen::{empty_body, http, Body, BoxFuture, StdError};
+use ::tonic::server::UnaryService;
+use ::tonic::{Request, Response, Status};
+
+use bytes::{Buf, BufMut};
+use core::convert::Infallible;
+use core::future::Future;
+use core::marker::PhantomData;
+
+/// A transport for calling gRPC servers generated by entrait.
+///
+/// Implemented for every [GrpcService], like a tonic `Channel` or a generated gRPC server.
+pub trait GrpcTransport: Clone + Send + Sync + 'static {
+    /// Perform a unary call to `path`.
+    fn unary<Req, Res>(&self, path: &'static str, request: Req) -> BoxFuture<Res, Status>
+    where
+        Req: serde::Serialize + Send + Sync + 'static,
+        Res: serde::de::DeserializeOwned + Send + Sync + 'static;
+}
+
+impl<T> GrpcTransport for T
+where
+    T: GrpcService<BoxBody> + Clone + Send + Sync + 'static,
+    T::Error: Into<StdError>,
+    T::Future: Send,
+    T::ResponseBody: Body + Send + 'static,
+    <T::ResponseBody as Body>::Data: Send,
+    <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+{
+    fn unary<Req, Res>(&self, path: &'static str, request: Req) -> BoxFuture<Res, Status>
+    where
+        Req: serde::Serialize + Send + Sync + 'static,
+        Res: serde::de::DeserializeOwned + Send + Sync + 'static,
+    {
+        let mut grpc = ::tonic::client::Grpc::new(self.clone());
+
+        Box::pin(async move {
+            grpc.ready().await.map_err(|error| {
+                Status::unknown(format!("Service was not ready: {}", error.into()))
+            })?;
+
+            let response = grpc
+                .unary(
+                    Request::new(request),
+                    http::uri::PathAndQuery::from_static(path),
+                    JsonCodec::<Req, Res>::default(),
+                )
+                .await?;
+
+            Ok(response.into_inner())
+        })
+    }
+}
+
+/// A tonic [Codec] encoding messages as JSON using serde.
+pub struct JsonCodec<E, D>(PhantomData<fn(E) -> D>);
+
+impl<E, D> Default for JsonCodec<E, D> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<E, D> Codec for JsonCodec<E, D>
+where
+    E: serde::Serialize + Send + 'static,
+    D: serde::de::DeserializeOwned + Send + 'static,
+{
+    type Encode = E;
+    type Decode = D;
+    type Encoder = JsonEncoder<E>;
+    type Decoder = JsonDecoder<D>;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        JsonEncoder(PhantomData)
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        JsonDecoder(PhantomData)
+    }
+}
+
+/// The [Encoder] of [JsonCodec].
+pub struct JsonEncoder<E>(PhantomData<fn(E)>);
+
+impl<E: serde::Serialize> Encoder for JsonEncoder<E> {
+    type Item = E;
+    type Error = Status;
+
+    fn encode(&mut self, item: E, dst: &mut EncodeBuf<'_>) -> Result<(), Status> {
+        serde_json::to_writer(dst.writer(), &item)
+            .map_err(|error| Status::internal(format!("Failed to encode message: {error}")))
+    }
+}
+
+/// The [Decoder] of [JsonCodec].
+pub struct JsonDecoder<D>(PhantomData<fn() -> D>);
+
+impl<D: serde::de::DeserializeOwned> Decoder for JsonDecoder<D> {
+    type Item = D;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<D>, Status> {
+        if !src.has_remaining() {
+            return Ok(None);
+        }
+
+        serde_json::from_reader(src.reader())
+            .map(Some)
+            .map_err(|error| Status::internal(format!("Failed to decode message: {error}")))
+    }
+}
+
+/// Serve a unary gRPC request using `handler`.
+#[doc(hidden)]
+pub fn serve_unary<B, Req, Res, F, Fut>(
+    request: http::Request<B>,
+    handler: F,
+) -> BoxFuture<http::Response<BoxBody>, Infallible>
+where
+    B: Body + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+    Req: serde::de::DeserializeOwned + Send + Sync + 'static,
+    Res: serde::Serialize + Send + Sync + 'static,
+    F: FnOnce(Req) -> Fut + Send + 'static,
+    Fut: Future<Output = Res> + Send + 'static,
+{
+    Box::pin(async move {
+        let mut grpc = ::tonic::server::Grpc::new(JsonCodec::<Res, Req>::default());
+        Ok(grpc.unary(UnaryHandler(Some(handler)), request).await)
+    })
+}
+
+/// Respond to a request for an unknown method.
+#[doc(hidden)]
+pub fn unimplemented() -> BoxFuture<http::Response<BoxBody>, Infallible> {
+    Box::pin(async {
+        Ok(http::Response::builder()
+            .status(200)
+            .header("grpc-status", "12")
+            .header("content-type", "application/grpc")
+            .body(empty_body())
+            .unwrap())
+    })
+}
+
+struct UnaryHandler<F>(Option<F>);
+
+impl<Req, Res, F, Fut> UnaryService<Req> for UnaryHandler<F>
+where
+    Res: Send + 'static,
+    F: FnOnce(Req) -> Fut,
+    Fut: Future<Output = Res> + Send + 'static,
+{
+    type Response = Res;
+    type Future = BoxFuture<Response<Res>, Status>;
+
+    fn call(&mut self, request: Request<Req>) -> Self::Future {
+        match self.0.take() {
+            Some(handler) => {
+                let future = handler(request.into_inner());
+                Box::pin(async move { Ok(Response::new(future.await)) })
+            }
+            None => Box::pin(async { Err(Status::internal("Unary handler called twice")) }),
+        }
+    }
+}
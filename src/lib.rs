@@ -1,6 +1,6 @@
 //! A proc macro for designing loosely coupled Rust applications.
 //!
-//! [`entrait`](entrait) is used to generate an _implemented trait_ from the definition of regular functions.
+//! [`entrait`](macro@entrait) is used to generate an _implemented trait_ from the definition of regular functions.
 //! The emergent pattern that results from its use enable the following things:
 //! * Zero-cost loose coupling and inversion of control
 //! * Dependency graph as a compile time concept
@@ -271,6 +271,72 @@
 //! }
 //! ```
 //!
+//! The mock struct is named `Mock{Trait}` by mockall. Another name can be given with the `mock_api` option,
+//! which then names the mockall mock instead of the unimock API.
+//! For modules, the mock is re-exported next to the trait, and like the mocks of unimock,
+//! it only exists in `cfg(test)` unless the `export` option is used:
+//!
+//! ```rust
+//! # use entrait::entrait_export as entrait;
+//! #[entrait(pub Repository, mockall, mock_api = RepositoryMock)]
+//! pub mod repository {
+//!     pub fn get<D>(deps: &D, id: u32) -> Option<String> {
+//!         unimplemented!()
+//!     }
+//!
+//!     pub fn put<D>(deps: &D, id: u32, name: &str) {
+//!         unimplemented!()
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let mut deps = RepositoryMock::new();
+//!     deps.expect_get().returning(|id| Some(format!("name{id}")));
+//!     assert_eq!(Some("name1".to_string()), deps.get(1));
+//! }
+//! ```
+//!
+//! A `mock_api` can't name both mocks, so it is an error when both `unimock` and `mockall` are given as options:
+//!
+//! ```compile_fail
+//! # use entrait::entrait_export as entrait;
+//! #[entrait(Foo, unimock, mockall, mock_api = FooMock)]
+//! fn foo<D>(_: &D) -> u32 {
+//!     unimplemented!()
+//! }
+//! // error: mock_api is ambiguous with both unimock and mockall
+//! ```
+//!
+//! ### Overriding single traits without a mock library
+//! With the `overrides` cargo feature, entrait also generates implementations for [`Overrides<T>`](overrides::Overrides),
+//! a wrapper around the real `Impl<T>` that can replace selected traits with hand-written stubs:
+//!
+//! ```ignore
+//! let app = Impl::new(App).override_with::<dyn FetchPlanet>(Box::new(FakePlanet));
+//! ```
+//!
+//! See the [overrides] module for details.
+//!
+//! ### In-memory fakes of repository traits
+//! With the `fake` cargo feature, the `fake(Name, key = K, value = V)` option on a trait generates an in-memory implementation
+//! for methods named like `get_*`, `insert_*`, `update_*` and `delete_*`, which can also be used as the `Target` of a custom delegation.
+//! Deep integration tests can then run against the fake instead of a database. See the [fake] module for details.
+//!
+//! ### Request-scoped context
+//! With the `scoped` cargo feature, entrait also generates implementations for [`Scoped<T, C>`](scoped::Scoped),
+//! an `Impl<T>` carrying a per-request context value `C`, available through the [`ScopeContext<C>`](scoped::ScopeContext) trait:
+//!
+//! ```ignore
+//! #[entrait(CurrentUser)]
+//! fn current_user(deps: &impl ScopeContext<RequestCtx>) -> &User {
+//!     &deps.scope_context().user
+//! }
+//!
+//! let response = app.clone().scoped(RequestCtx { user }).handle_request(request).await;
+//! ```
+//!
+//! See the [scoped] module for details.
+//!
 //!
 //! # Multi-crate architecture
 //!
@@ -387,6 +453,8 @@
 //!
 //! To use with some `App`, the app type itself should implement the trait.
 //!
+//! Traits for the most common leaf dependencies, like the current time, are available in the [std_deps] module (requires the `std-deps` feature).
+//!
 //!
 //! ### Case 3: Hand-written trait as a leaf dependency using _dynamic dispatch_
 //! Sometimes it might be desirable to have a delegation that involves dynamic dispatch.
@@ -472,7 +540,7 @@
 //! This allows us to continue using entrait patterns within those implementations!
 //!
 //! In _crate 2_, we have to provide an implementation of `RepositoryImpl<T>`.
-//! This can either be done manually, or by using the [entrait] attribute on an `impl` block:
+//! This can either be done manually, or by using the [entrait](macro@entrait) attribute on an `impl` block:
 //!
 //! ```rust
 //! # mod demo {
@@ -538,6 +606,8 @@
 //! # } // demo
 //! ```
 //!
+//! When there are several implementations of `RepositoryImpl<T>`, the [contract] attribute runs the same tests against each of them.
+//!
 //!
 //! ### Case 5: Truly inverted internal dependencies - dynamic dispatch
 //! A small variation of case 4: Use `delegate_by=ref` instead of a custom trait.
@@ -567,6 +637,40 @@
 //! The app must now implement [`AsRef<dyn RepositoryImpl<Self>>`](::core::convert::AsRef).
 //!
 //!
+//! ### Case 6: Actors
+//! With `delegate_by = actor`, the implementation runs in its own task that owns its state,
+//! and `Impl<T>` delegates by sending messages to it through a generated handle.
+//! The app must implement `AsRef<Handle>`. See the [`actor`](crate::actor) module (requires the `actor` feature).
+//!
+//!
+//! ### Case 7: Closures as leaf dependencies
+//! Small leaf dependencies, like a clock or an id generator, may not deserve their own implementation type.
+//! With `delegate_by = fn_field`, the app stores a closure for each method, and implements a generated accessor trait returning it:
+//!
+//! ```rust
+//! # use entrait::*;
+//! #[entrait(ClockFns, delegate_by = fn_field)]
+//! pub trait Clock {
+//!     fn now(&self) -> u64;
+//! }
+//!
+//! struct App {
+//!     now: Box<dyn Fn() -> u64 + Send + Sync>,
+//! }
+//!
+//! impl ClockFns for App {
+//!     fn now(&self) -> &dyn Fn() -> u64 {
+//!         &self.now
+//!     }
+//! }
+//!
+//! let app = Impl::new(App { now: Box::new(|| 42) });
+//! assert_eq!(42, app.now());
+//! ```
+//!
+//! The methods must be synchronous, non-generic and take `&self`, and their outputs can't borrow from `self`.
+//!
+//!
 //!
 //!
 //! # Options and features
@@ -654,13 +758,95 @@
 //!
 //! It is also possible to reduce noise by doing `use entrait::entrait_export as entrait`.
 //!
+//! #### Checking for unused dependencies
+//! After refactoring, a dependency parameter may keep bounds that the function body no longer needs.
+//! The `check_deps` option scans the body for method calls on the dependency parameter,
+//! and warns about bounds that are never used:
+//!
+//! ```
+//! # use entrait::*;
+//! # #[entrait(FetchUser, no_deps)]
+//! # fn fetch_user(id: u32) -> String { todo!() }
+//! # #[entrait(LogEvent, no_deps)]
+//! # fn log_event(event: &str) {}
+//! #[entrait(Greet, check_deps)]
+//! fn greet(deps: &(impl FetchUser + LogEvent), id: u32) -> String {
+//!     // warning: the `LogEvent` bound of the dependency parameter is never used by `greet`
+//!     format!("Hello, {}", deps.fetch_user(id))
+//! }
+//! ```
+//!
+//! Entrait does not know the methods of each trait, so a bound is considered used
+//! when a method named after it is called, following the naming convention of entraited functions.
+//! When the dependency parameter is passed on, or other methods are called on it, nothing is reported.
+//! The warnings are deprecation warnings, which can be silenced with `#[allow(deprecated)]`,
+//! or turned into errors with `#[deny(deprecated)]`:
+//!
+//! ```compile_fail
+//! #![deny(deprecated)]
+//! # use entrait::*;
+//! # #[entrait(FetchUser, no_deps)]
+//! # fn fetch_user(id: u32) -> String { todo!() }
+//! # #[entrait(LogEvent, no_deps)]
+//! # fn log_event(event: &str) {}
+//! #[entrait(Greet, check_deps)]
+//! fn greet(deps: &(impl FetchUser + LogEvent), id: u32) -> String {
+//!     format!("Hello, {}", deps.fetch_user(id))
+//! }
+//! ```
+//!
+//! With `check_deps = strict`, unused bounds are compile errors instead:
+//!
+//! ```compile_fail
+//! # use entrait::*;
+//! # #[entrait(FetchUser, no_deps)]
+//! # fn fetch_user(id: u32) -> String { todo!() }
+//! # #[entrait(LogEvent, no_deps)]
+//! # fn log_event(event: &str) {}
+//! #[entrait(Greet, check_deps = strict)]
+//! fn greet(deps: &(impl FetchUser + LogEvent), id: u32) -> String {
+//!     // error: the `LogEvent` bound of the dependency parameter is never used by `greet`
+//!     format!("Hello, {}", deps.fetch_user(id))
+//! }
+//! ```
+//!
 //! #### Feature overview
 //! | Feature                  | Implies         | Description         |
 //! | -------------------      | --------------- | ------------------- |
+//! | `std`                    | `alloc`         | Enabled by default. Links the standard library. Required by all integrations and by the `cache` and `metrics` features. |
+//! | `alloc`                  |                 | Enables the [runtime] module for the `retry` and `timeout_ms` entrait options, which needs heap allocation. |
 //! | `unimock`                |                 | Adds the [unimock] dependency, and turns on Unimock implementations for all traits. |
 //! | `use-boxed-futures`      | `boxed-futures` | Automatically applies the [async_trait] macro to async trait methods. |
 //! | `use-associated-futures` |                 | Automatically transforms the return type of async trait methods into an associated future by using type-alias-impl-trait syntax. Requires a nightly compiler. |
-//! | `boxed-futures`          |                 | Pulls in the [async_trait] optional dependency, enabling the `box_future` entrait option (macro parameter). |
+//! | `boxed-futures`          | `alloc`         | Pulls in the [async_trait] optional dependency, enabling the `box_future` entrait option (macro parameter). |
+//! | `overrides`              |                 | Generates implementations for [`overrides::Overrides`], for replacing single traits of an `Impl<T>` in tests. |
+//! | `scoped`                 |                 | Generates implementations for [`scoped::Scoped`], an `Impl<T>` with a request-scoped context. |
+//! | `axum`                   |                 | Adds the [axum](::axum) dependency, and the [`integration::axum`] module for using entrait deps in axum handlers. |
+//! | `async-graphql`          |                 | Adds the [async-graphql](::async_graphql) dependency, and the [`integration::async_graphql`] module for registering entrait deps in a schema, and resolvers taking `deps: &impl Trait` from the resolver context. |
+//! | `tower`                  | `boxed-futures` | Adds the [tower-service](::tower_service) dependency, enabling the `tower_service` entrait option. |
+//! | `tonic`                  | `boxed-futures` | Adds the [tonic] dependency, enabling the `grpc` entrait option. See [`integration::tonic`]. |
+//! | `actor`                  | `boxed-futures` | Adds the [tokio] dependency, enabling the `delegate_by = actor` entrait option. See [`actor`]. |
+//! | `tokio`                  |                 | Adds the [tokio] dependency, the `runtime::Tokio` [runtime](crate::runtime) for the `retry` and `timeout_ms` entrait options, and an [`Executor`](crate::runtime::Executor) implementation for tokio's runtime `Handle`. |
+//! | `cache`                  |                 | Enables the `cache` entrait option, memoizing entraited functions in a per-application [`cache::Cache`]. |
+//! | `metrics`                |                 | Enables the instrumentation generated by the `metrics` entrait option, reporting calls to a global [`metrics::Recorder`]. |
+//! | `remote`                 | `boxed-futures` | Adds the [serde] dependency, enabling the `remote` entrait option. See [`remote`]. |
+//! | `serde`                  |                 | Adds the [serde] dependency, implementing `Serialize` and `Deserialize` for [Impl] by forwarding to the wrapped `T`. |
+//! | `fake`                   | `std`           | Enables the `fake` entrait option, generating in-memory fakes of repository traits. See [`fake`]. |
+//! | `std-deps`               | `std`           | Adds the [uuid] dependency, and the [std_deps] module with ready-made traits for time, randomness, UUIDs and environment variables. |
+//! | `stream`                 | `alloc`         | Adds the [futures-core](::futures_core) dependency, enabling [`boxed::BoxStream`] for functions returning `impl Stream`. |
+//!
+//! Features that depend on the standard library, like `overrides` and the integrations, imply `std`.
+//!
+//! #### `no_std`
+//! With `default-features = false`, entrait is `no_std`, and the code it generates only refers to `::core`.
+//! This makes the entrait pattern usable for abstracting over hardware in embedded firmware:
+//!
+//! ```toml
+//! entrait = { version = "0.5", default-features = false }
+//! ```
+//!
+//! Boxed futures need the `alloc` crate, and are enabled with the `boxed-futures` feature, which implies `alloc`.
+//! The [async_trait] macro refers to `Box`, so a `no_std` crate using it must `use alloc::boxed::Box`.
 //!
 //!
 //!
@@ -704,9 +890,20 @@
 //! One might say that a layered application architecture should never contain cycles.
 //! If you do need recursive algorithms, you could model this as utility functions outside of the entraited APIs of the application.
 //!
+//! The compiler reports such cycles as an overflow when evaluating trait requirements, which can be hard to trace back.
+//! Wrapping the entraited items in the [graph!] macro reports the cycle itself, with the functions involved.
+//!
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![forbid(unsafe_code)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+// Makes the `::entrait` paths of generated code resolve inside this crate
+#[cfg(feature = "std-deps")]
+extern crate self as entrait;
+
 #[cfg(feature = "unimock")]
 mod macros {
     #[cfg(feature = "use-boxed-futures")]
@@ -889,7 +1086,7 @@ mod macros {
 /// ```
 ///
 /// ##### `dyn trait` delegation with `AsRef`:
-/// The only attribute parameter currently supported on impl blocks is adding the `ref` keyword, to indicate that the delegation strategy uses dynamic dispatch through `AsRef`:
+/// Adding the `ref` keyword to the attribute indicates that the delegation strategy uses dynamic dispatch through `AsRef`:
 ///
 /// ```rust
 /// # use entrait::*;
@@ -908,14 +1105,53 @@ mod macros {
 /// }
 /// ```
 ///
+/// ##### Mockable API trait
+/// The functions of an impl block can additionally be exposed through an _API trait_, named by the attribute. The name must be UpperCamelCase, anything else is parsed as an option.
+/// Like an entraited module, the API trait is implemented for [Impl], and is mockable.
+/// With unimock, its mocks can be unmocked into the functions of the impl block,
+/// which makes it possible to test the implementation while mocking the dependencies it calls:
+///
+/// ```rust
+/// # use entrait::entrait_export as entrait;
+/// # use unimock::*;
+/// # #[entrait(TraitImpl, delegate_by = DelegateTrait)]
+/// # trait Trait {
+/// #     fn foo(&self, arg: i32) -> i32;
+/// # }
+/// #[entrait(Double, mock_api=DoubleMock)]
+/// fn double(deps: &impl std::any::Any, arg: i32) -> i32 {
+///     arg * 2
+/// }
+///
+/// pub struct MyType;
+///
+/// #[entrait(MyTypeApi, mock_api=MyTypeMock)]
+/// impl TraitImpl for MyType {
+///     fn foo(deps: &impl Double, arg: i32) -> i32 {
+///         deps.double(arg) + 1
+///     }
+/// }
+///
+/// let deps = Unimock::new((
+///     MyTypeMock::foo.next_call(matching!(_)).unmocked(),
+///     DoubleMock.next_call(matching!(20)).returns(41),
+/// ));
+///
+/// assert_eq!(42, MyTypeApi::foo(&deps, 20));
+/// ```
+///
+/// As `Impl<T>` may implement both `Trait` and the API trait, calls of methods present in both traits need to be fully qualified.
+///
 /// #### Syntax
 /// ```no_compile
-/// #[entrait(ref?)]
+/// #[entrait(ref? $visibility? $ApiTraitIdent?, $option, ...)]
 /// impl TraitPath for Type {
 ///     ...
 /// }
 /// ```
 ///
+/// Supported options are `mock_api`, `unimock`, `mockall` and `export`, which apply to the API trait.
+///
 ///
 ///
 /// # Options
@@ -927,10 +1163,21 @@ mod macros {
 /// | `export`            | `bool`                    | `fn`+`mod`         | `false`     | If mocks are generated, exports these mocks even in release builds. Only relevant for libraries. |
 /// | `mock_api`          | `ident`                   | `fn`+`mod`+`trait` |             | The identifier to use for mock APIs (for libraries that support custom identifiers. The `unimock` library requires this to be explicitly specified. |
 /// | `unimock`           | `bool`                    | `fn`+`mod`+`trait` | `false`[^1] | Used to turn _off_ unimock implementation when the `unimock` _feature_ is enabled. |
-/// | `mockall`           | `bool`                    | `fn`+`mod`+`trait` | `false`     | Enable mockall mocks. |
+/// | `mockall`           | `bool`                    | `fn`+`mod`+`trait` | `false`     | Enable mockall mocks. With `mock_api`, the mock struct is re-exported under that name. |
 /// | `box_future`        | `bool`                    | `fn`+`mod`+`trait` | `false`[^2] | In the case of an `async fn`, use the `async_trait` macro on the resulting trait. Requires the `boxed-futures` entrait feature. |
 /// | `associated_future` | `bool`                    | `fn`+`mod`+`trait` | `false`[^3] | In the case of an `async fn`, use an associated future to avoid heap allocation. Currently requires a nighlty Rust compiler, with `feature(type_alias_impl_trait)`. |
-/// | `delegate_by`       | `Self`/`ref`/custom ident | `trait`            | `Self`      | Controls the generated `Impl<T>` delegation of this trait. `Self` generates a `T: Trait` bound. `ref` generates a [`T: AsRef<dyn Trait>`](::core::convert::AsRef) bound. `Borrow` is deprecated and uses the [core::borrow::Borrow] trait. Any other value generates a new trait with that name which controls the delegation. |
+/// | `delegate_by`       | `Self`/`ref`/`actor`/`fn_field`/custom ident | `trait` | `Self`      | Controls the generated `Impl<T>` delegation of this trait. `Self` generates a `T: Trait` bound. `ref` generates a [`T: AsRef<dyn Trait>`](::core::convert::AsRef) bound. `actor` generates an actor handle named by the trait parameter, and a `T: AsRef<Handle>` bound, see [`actor`](crate::actor) (requires the `actor` feature). `fn_field` generates an accessor trait named by the trait parameter, returning a closure for each method, and a `T: Accessor` bound. `Borrow` is deprecated and uses the [core::borrow::Borrow] trait. Any other value generates a new trait with that name which controls the delegation. |
+/// | `tower_service`     | `ident`                   | `fn`               |             | Generates a [`tower::Service`](::tower_service::Service) with this name, taking the single non-dependency argument of an async function as request. A `Result<T, E>` return type becomes `Response = T` and `Error = E`. Requires the `tower` entrait feature and boxed futures. |
+/// | `blocking_variant`  | `ident`                   | `fn`               |             | Generates a synchronous trait with this name for an async function, implemented for `Impl<T>` by blocking on the async method, see [blocking variants](crate::runtime#blocking-variants). Mocks are generated for both traits. |
+/// | `grpc`              | `ident`                   | `trait`            |             | Generates a gRPC server adapter and client with this name as prefix, see [`integration::tonic`](crate::integration::tonic). Requires the `tonic` entrait feature and boxed futures. |
+/// | `cache`             | `bool`                    | `fn`+`mod`         | `false`     | Caches results of the `Impl<T>` delegation, keyed by the arguments, see [`cache`](crate::cache). Requires the `cache` entrait feature. |
+/// | `cache_ttl_secs`    | integer                   | `fn`+`mod`         |             | Expires cached results after this number of seconds. Implies `cache`. |
+/// | `retry`             | `(max = N, backoff = "none"/"fixed"/"exp", delay_ms = N)` | `fn`+`mod` | | Retries failed calls of async functions returning `Result`, see [`runtime`](crate::runtime). |
+/// | `timeout_ms`        | integer                   | `fn`+`mod`         |             | Fails calls of async functions returning `Result` that take longer than this number of milliseconds, see [`runtime`](crate::runtime). |
+/// | `metrics`           | `bool`                    | `fn`+`mod`+`trait` | `false`     | Records call counts and latencies of the `Impl<T>` delegation, labelled with trait and method names, see [`metrics`](crate::metrics). Generates no code without the `metrics` entrait feature. |
+/// | `check_deps`        | `bool`/`strict`           | `fn`+`mod`         | `false`     | Warns about bounds of the dependency parameter that are never used in the function body, see [checking for unused dependencies](crate#checking-for-unused-dependencies). `strict` reports them as errors. |
+/// | `fake`              | `(Name, key = K, value = V)` | `trait`         |             | Generates an in-memory fake implementation of the trait with this name, storing `V`s by `K`, see [`fake`](crate::fake). Requires the `fake` entrait feature. |
+/// | `remote`            | `ident`                   | `trait`            |             | Generates serializable request/response enums, a dispatcher and a client with this name, see [`remote`](crate::remote). Requires the `remote` entrait feature and boxed futures. |
 ///
 /// [^1]: Enabled by default by turning on the `unimock` cargo feature.
 ///
@@ -939,15 +1186,162 @@ mod macros {
 /// [^3]: Enabled by default by turning on the `use-associated-futures` cargo feature.
 pub use macros::entrait;
 
-/// Same as the [`entrait`](entrait) macro, only that the `export` option is set to true.
+/// Same as the [`entrait`](macro@entrait) macro, only that the `export` option is set to true.
 ///
 /// This can be used in libraries to export mocks.
 ///
 /// A good way to reduce noise can to to import it as `use entrait::entrait_export as entrait;`.
 pub use macros::entrait_export;
 
-/// Re-exported from the [implementation] crate.
-pub use ::implementation::Impl;
+/// Check a group of entraited items for cyclic dependencies.
+///
+/// The items are passed through unchanged.
+/// Cycles between the traits of the group are reported at the bound closing the cycle,
+/// listing the traits and functions involved, instead of as a trait solver overflow when the traits are used:
+///
+/// ```compile_fail
+/// # use entrait::*;
+/// entrait::graph! {
+///     #[entrait(Foo)]
+///     fn foo(deps: &impl Bar) {}
+///
+///     #[entrait(Bar)]
+///     fn bar(deps: &impl Foo) {}
+/// }
+/// // error: dependency cycle: `Foo` -> `Bar` -> `Foo`
+/// //          `foo` (Foo) depends on `Bar`
+/// //          `bar` (Bar) depends on `Foo`
+/// ```
+///
+/// A function depending on its own trait is a cycle too:
+///
+/// ```compile_fail
+/// # use entrait::*;
+/// entrait::graph! {
+///     #[entrait(Foo)]
+///     fn foo(deps: &impl Foo) {}
+/// }
+/// // error: dependency cycle: `Foo` -> `Foo`
+/// ```
+///
+/// Entraited functions, modules and traits are recognized in the group, also inside nested modules,
+/// by an `#[entrait]` or `#[entrait_export]` attribute, or the same attribute by its `entrait::` path.
+/// Dependencies on traits defined outside the group are not followed.
+pub use entrait_macros::graph;
+
+/// Run the `#[test]` functions of a module against every listed implementation of a custom delegation target.
+///
+/// Each test function takes the dependency as its only parameter, by reference or by value, and stays generic.
+/// For each target, a module named after the target is generated, containing one test per function.
+/// The tests receive an `Impl` of an application whose `Target` of the delegation trait is that type,
+/// so every implementation is held to the same contract:
+///
+/// ```no_run
+/// # mod demo {
+/// # use entrait::*;
+/// #[entrait(RepositoryImpl, delegate_by = DelegateRepository)]
+/// pub trait Repository {
+///     fn fetch(&self) -> i32;
+/// }
+///
+/// #[derive(Default)]
+/// pub struct ZeroRepository;
+///
+/// #[entrait]
+/// impl RepositoryImpl for ZeroRepository {
+///     fn fetch<D>(deps: &D) -> i32 {
+///         0
+///     }
+/// }
+///
+/// pub struct ConstRepository(i32);
+///
+/// impl<T: AsRef<ConstRepository>> RepositoryImpl<T> for ConstRepository {
+///     fn fetch(deps: &Impl<T>) -> i32 {
+///         (**deps).as_ref().0
+///     }
+/// }
+///
+/// #[entrait::contract(Repository, targets(ZeroRepository, ConstRepository = ConstRepository(0)))]
+/// mod repository_contract {
+///     use super::*;
+///
+///     #[test]
+///     fn fetches_zero(repository: &impl Repository) {
+///         assert_eq!(0, repository.fetch());
+///     }
+/// }
+/// // generates the tests `repository_contract::zero_repository::fetches_zero`
+/// // and `repository_contract::const_repository::fetches_zero`
+/// # } // demo
+/// ```
+///
+/// The delegation trait defaults to `Delegate` followed by the trait name, and can be chosen using `delegate_by = Path`.
+/// Targets are created using [Default], unless followed by `= expression`.
+/// The application implements `AsRef` of the target, so targets owning state can implement the target trait for `T: AsRef<Self>`,
+/// like the fakes generated by the `fake` option.
+/// Attributes of a test function, like `#[should_panic]` or `#[tokio::test]` for async functions, are moved to the generated tests.
+///
+/// Targets with the same type name would generate the same module, so one of them has to be imported under another name:
+///
+/// ```compile_fail
+/// # use entrait::*;
+/// # #[entrait(RepositoryImpl, delegate_by = DelegateRepository)]
+/// # pub trait Repository {}
+/// # mod a { #[derive(Default)] pub struct Repo; impl<T> super::RepositoryImpl<T> for Repo {} }
+/// # mod b { #[derive(Default)] pub struct Repo; impl<T> super::RepositoryImpl<T> for Repo {} }
+/// #[entrait::contract(Repository, targets(a::Repo, b::Repo))]
+/// mod repository_contract {
+///     use super::*;
+///
+///     #[test]
+///     fn test(repository: &impl Repository) {}
+/// }
+/// // error: the tests of each contract target are generated in a module named after the type, and `repo` is used for another target already
+/// ```
+pub use entrait_macros::contract;
+
+mod implementation;
+
+pub use implementation::Impl;
+
+#[cfg(feature = "overrides")]
+pub mod overrides;
+
+#[cfg(feature = "overrides")]
+pub use overrides::OverrideExt;
+
+#[cfg(feature = "scoped")]
+pub mod scoped;
+
+#[cfg(feature = "scoped")]
+pub use scoped::ScopeExt;
+
+pub mod integration;
+
+#[cfg(feature = "remote")]
+pub mod remote;
+
+#[cfg(feature = "actor")]
+pub mod actor;
+
+#[cfg(feature = "cache")]
+pub mod cache;
+
+#[cfg(feature = "metrics")]
+pub mod metrics;
+
+#[cfg(feature = "alloc")]
+pub mod runtime;
+
+#[cfg(feature = "alloc")]
+pub mod boxed;
+
+#[cfg(feature = "std-deps")]
+pub mod std_deps;
+
+#[cfg(feature = "fake")]
+pub mod fake;
 
 /// Optional mock re-exports for macros
 #[cfg(feature = "unimock")]
@@ -960,7 +1354,166 @@ pub mod __async_trait {
     pub use ::async_trait::async_trait;
 }
 
+#[cfg(feature = "tower")]
+#[doc(hidden)]
+pub mod __tower {
+    pub use ::tower_service::Service;
+
+    pub type BoxFuture<T> =
+        ::core::pin::Pin<Box<dyn ::core::future::Future<Output = T> + Send + 'static>>;
+
+    pub fn box_future<F>(future: F) -> BoxFuture<F::Output>
+    where
+        F: ::core::future::Future + Send + 'static,
+    {
+        Box::pin(future)
+    }
+}
+
+#[cfg(feature = "tonic")]
+#[doc(hidden)]
+pub mod __tonic {
+    pub use crate::integration::tonic::{serve_unary, unimplemented, GrpcTransport};
+    pub use ::tonic::body::BoxBody;
+    pub use ::tonic::codegen::{http, Body, BoxFuture, Service, StdError};
+    pub use ::tonic::server::NamedService;
+}
+
+#[cfg(feature = "actor")]
+#[doc(hidden)]
+pub mod __actor {
+    use crate::actor::ActorStopped;
+
+    pub use ::tokio::sync::{mpsc, oneshot};
+
+    /// Send the message created by `message` to the actor, and await its reply.
+    pub async fn call<M, T>(
+        sender: &mpsc::Sender<M>,
+        message: impl FnOnce(oneshot::Sender<T>) -> M,
+    ) -> Result<T, ActorStopped> {
+        let (reply, response) = oneshot::channel();
+        sender
+            .send(message(reply))
+            .await
+            .map_err(|_| ActorStopped)?;
+        response.await.map_err(|_| ActorStopped)
+    }
+}
+
+#[cfg(feature = "fake")]
+#[doc(hidden)]
+pub mod __fake {
+    pub use ::std::borrow::ToOwned;
+}
+
+#[cfg(feature = "remote")]
+#[doc(hidden)]
+pub mod __remote {
+    use crate::remote::{RemoteError, Transport};
+
+    pub use ::serde;
+    pub use ::std::vec::Vec;
+
+    pub fn box_future<'a, F>(future: F) -> crate::remote::BoxFuture<'a, F::Output>
+    where
+        F: ::core::future::Future + Send + 'a,
+    {
+        Box::pin(future)
+    }
+
+    pub fn encode<T: ::serde::Serialize>(value: &T) -> Result<Vec<u8>, RemoteError> {
+        ::serde_json::to_vec(value).map_err(|error| RemoteError::Encode(error.to_string()))
+    }
+
+    pub fn decode<T: ::serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<T, RemoteError> {
+        ::serde_json::from_slice(bytes).map_err(|error| RemoteError::Decode(error.to_string()))
+    }
+
+    /// Encode the request, send it over the transport and decode the response.
+    pub fn call<'a, T, Req, Res>(
+        transport: &'a T,
+        request: &Req,
+    ) -> impl ::core::future::Future<Output = Result<Res, RemoteError>> + Send + 'a
+    where
+        T: Transport,
+        Req: ::serde::Serialize,
+        Res: ::serde::de::DeserializeOwned + 'a,
+    {
+        let encoded = encode(request);
+        async move {
+            let response = transport.send(encoded?).await?;
+            decode(&response)
+        }
+    }
+}
+
 #[doc(hidden)]
 pub mod static_async {
     pub use entrait_macros::static_async_trait as async_trait;
 }
+
+/// Expands the given items only when the `overrides` feature is enabled.
+#[cfg(feature = "overrides")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __overrides {
+    ($($tt:tt)*) => { $($tt)* };
+}
+
+/// Expands the given items only when the `overrides` feature is enabled.
+#[cfg(not(feature = "overrides"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __overrides {
+    ($($tt:tt)*) => {};
+}
+
+/// Expands the given items only when the `scoped` feature is enabled.
+#[cfg(feature = "scoped")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __scoped {
+    ($($tt:tt)*) => { $($tt)* };
+}
+
+/// Expands the given items only when the `scoped` feature is enabled.
+#[cfg(not(feature = "scoped"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __scoped {
+    ($($tt:tt)*) => {};
+}
+
+/// Expands the given statements only when the `metrics` feature is enabled.
+#[cfg(feature = "metrics")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __metrics {
+    ($($tt:tt)*) => { $($tt)* };
+}
+
+/// Expands the given statements only when the `metrics` feature is enabled.
+#[cfg(not(feature = "metrics"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __metrics {
+    ($($tt:tt)*) => {};
+}
+
+/// Expands the given items only when the `runtime` module is available, i.e. with the `alloc` feature.
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __runtime {
+    ($($tt:tt)*) => { $($tt)* };
+}
+
+/// Expands the given items only when the `runtime` module is available, i.e. with the `alloc` feature.
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __runtime {
+    ($($tt:tt)*) => {
+        ::core::compile_error!("blocking_variant requires the `alloc` feature of entrait");
+    };
+}
@@ -0,0 +1,136 @@
+//! Metrics instrumentation, for the `metrics` entrait option.
+//!
+//! With the `metrics` option, the generated implementation for `Impl<T>` starts a [CallTimer] for every method call.
+//! The timer reports each call, labelled with the trait and method names, to the global [Recorder],
+//! so that it can maintain per-method counters and latency histograms:
+//!
+//! ```
+//! # use entrait::*;
+//! # use entrait::metrics::*;
+//! # use std::sync::Mutex;
+//! # use std::time::Duration;
+//! #[entrait(FetchUser, no_deps, metrics)]
+//! fn fetch_user(id: u32) -> String {
+//!     format!("user{id}")
+//! }
+//!
+//! #[derive(Default)]
+//! struct CallLog(Mutex<Vec<String>>);
+//!
+//! impl Recorder for CallLog {
+//!     fn record_call(&self, call: &Call) {
+//!         self.0.lock().unwrap().push(format!("{}::{}", call.trait_name(), call.method()));
+//!     }
+//!
+//!     fn record_latency(&self, _: &Call, _: Duration) {}
+//! }
+//!
+//! let call_log: &'static CallLog = Box::leak(Box::default());
+//! set_recorder(call_log).unwrap();
+//!
+//! let app = Impl::new(());
+//! assert_eq!("user1", app.fetch_user(1));
+//! assert_eq!(vec!["FetchUser::fetch_user"], *call_log.0.lock().unwrap());
+//! ```
+//!
+//! The option is also supported for traits, instrumenting the delegation from `Impl<T>` to the implementation.
+//!
+//! Without the `metrics` feature of entrait, the option generates no code at all,
+//! so libraries may use it and leave the decision to the application.
+//! Calls made before a recorder is set are not recorded.
+//!
+//! Mocks implement the trait directly, and are never instrumented.
+//! The same goes for wrappers like [`Scoped`](crate::scoped::Scoped) when used with entraited functions.
+
+use core::fmt;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::OnceCell;
+
+static RECORDER: OnceCell<&'static dyn Recorder> = OnceCell::new();
+
+/// Receives the metrics of instrumented method calls.
+pub trait Recorder: Send + Sync {
+    /// Record that a call has started, e.g. by incrementing a counter.
+    fn record_call(&self, call: &Call);
+
+    /// Record the latency of a completed call, e.g. in a histogram.
+    fn record_latency(&self, call: &Call, latency: Duration);
+}
+
+/// The labels of an instrumented method call.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Call {
+    trait_name: &'static str,
+    method: &'static str,
+}
+
+impl Call {
+    /// The name of the trait being called.
+    pub fn trait_name(&self) -> &'static str {
+        self.trait_name
+    }
+
+    /// The name of the method being called.
+    pub fn method(&self) -> &'static str {
+        self.method
+    }
+}
+
+/// The error of setting a recorder when one has already been set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SetRecorderError;
+
+impl fmt::Display for SetRecorderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a metrics recorder has already been set")
+    }
+}
+
+impl std::error::Error for SetRecorderError {}
+
+/// Set the global recorder. This can only be done once.
+pub fn set_recorder(recorder: &'static dyn Recorder) -> Result<(), SetRecorderError> {
+    RECORDER.set(recorder).map_err(|_| SetRecorderError)
+}
+
+/// The global recorder, if set.
+pub fn recorder() -> Option<&'static dyn Recorder> {
+    RECORDER.get().copied()
+}
+
+/// Measures a method call, recording its latency when dropped.
+#[must_use = "the latency is recorded when the timer is dropped"]
+pub struct CallTimer {
+    call: Call,
+    started: Option<(&'static dyn Recorder, Instant)>,
+}
+
+impl CallTimer {
+    /// Record a call to `trait_name::method` and start measuring its latency.
+    pub fn start(trait_name: &'static str, method: &'static str) -> Self {
+        let call = Call { trait_name, method };
+        let started = recorder().map(|recorder| {
+            recorder.record_call(&call);
+            (recorder, Instant::now())
+        });
+
+        Self { call, started }
+    }
+}
+
+impl Drop for CallTimer {
+    fn drop(&mut self) {
+        if let Some((recorder, started_at)) = self.started {
+            recorder.record_latency(&self.call, started_at.elapsed());
+        }
+    }
+}
+
+impl fmt::Debug for CallTimer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CallTimer")
+            .field("call", &self.call)
+            .finish()
+    }
+}
@@ -0,0 +1,142 @@
+//! Per-instance trait overrides for [Impl].
+//!
+//! An [Overrides] value wraps a real application `Impl<T>` and a table of _stubs_,
+//! one per overridden trait.
+//! Entrait generates an implementation of every eligible trait for [Overrides], which first consults
+//! the table and otherwise falls back to the real implementation.
+//!
+//! This makes it possible to replace a single dependency in an integration test, without using a mock library:
+//!
+//! ```
+//! # use entrait::*;
+//! # use entrait::overrides::*;
+//! #[entrait]
+//! trait FetchPlanet {
+//!     fn fetch_planet(&self, id: u32) -> String;
+//! }
+//!
+//! #[entrait(SayHello)]
+//! fn say_hello(deps: &impl FetchPlanet, id: u32) -> String {
+//!     format!("Hello {}!", deps.fetch_planet(id))
+//! }
+//!
+//! struct App;
+//!
+//! impl FetchPlanet for App {
+//!     fn fetch_planet(&self, _: u32) -> String {
+//!         "World".to_string()
+//!     }
+//! }
+//!
+//! struct FakePlanet;
+//!
+//! impl FetchPlanet for FakePlanet {
+//!     fn fetch_planet(&self, _: u32) -> String {
+//!         "Mars".to_string()
+//!     }
+//! }
+//!
+//! let app = Impl::new(App).override_with::<dyn FetchPlanet>(Box::new(FakePlanet));
+//!
+//! assert_eq!("Hello Mars!", say_hello(&app, 42));
+//! ```
+//!
+//! Overriding is supported for traits that entrait implements for `Impl<T>`, and which are object safe:
+//! Traits generated from functions with a mock API (`mock_api` or `mockall`), and entraited traits.
+//! Async methods are supported with boxed futures.
+
+use crate::Impl;
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// An `Impl<T>` with a table of overridden traits.
+///
+/// Cloning is cheap with regards to the override table, which is shared between clones.
+pub struct Overrides<T> {
+    implementation: Impl<T>,
+    table: Arc<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>,
+}
+
+impl<T> Overrides<T> {
+    /// Construct a new [Overrides] with an empty override table.
+    pub fn new(implementation: Impl<T>) -> Self {
+        Self {
+            implementation,
+            table: Arc::new(HashMap::new()),
+        }
+    }
+
+    /// Override the trait `D` (written as `dyn Trait`) with the given stub.
+    ///
+    /// A previous override of the same trait is replaced.
+    pub fn override_with<D>(mut self, stub: Box<D::Object>) -> Self
+    where
+        D: ?Sized + Overridable,
+    {
+        Arc::make_mut(&mut self.table).insert(TypeId::of::<D>(), Arc::new(stub));
+        self
+    }
+
+    /// Look up the stub registered for the trait `D`, if any.
+    ///
+    /// This is an associated function rather than a method, so it can't conflict with trait methods.
+    pub fn lookup<D>(this: &Self) -> Option<&D::Object>
+    where
+        D: ?Sized + Overridable,
+    {
+        this.table
+            .get(&TypeId::of::<D>())
+            .and_then(|stub| stub.downcast_ref::<Box<D::Object>>())
+            .map(|stub| stub.as_ref())
+    }
+
+    /// Access the real implementation.
+    pub fn as_impl(&self) -> &Impl<T> {
+        &self.implementation
+    }
+
+    /// Discard the override table and return the real implementation.
+    pub fn into_impl(self) -> Impl<T> {
+        self.implementation
+    }
+}
+
+impl<T: Clone> Clone for Overrides<T> {
+    fn clone(&self) -> Self {
+        Self {
+            implementation: self.implementation.clone(),
+            table: self.table.clone(),
+        }
+    }
+}
+
+impl<T> From<Impl<T>> for Overrides<T> {
+    fn from(implementation: Impl<T>) -> Self {
+        Self::new(implementation)
+    }
+}
+
+/// Extension trait for constructing [Overrides] directly from an [Impl].
+pub trait OverrideExt<T> {
+    /// Convert into [Overrides], and override the trait `D` (written as `dyn Trait`) with the given stub.
+    fn override_with<D>(self, stub: Box<D::Object>) -> Overrides<T>
+    where
+        D: ?Sized + Overridable;
+}
+
+impl<T> OverrideExt<T> for Impl<T> {
+    fn override_with<D>(self, stub: Box<D::Object>) -> Overrides<T>
+    where
+        D: ?Sized + Overridable,
+    {
+        Overrides::new(self).override_with::<D>(stub)
+    }
+}
+
+/// Implemented by entrait for `dyn Trait` of every overridable trait.
+pub trait Overridable: 'static {
+    /// The trait object type stored in the override table.
+    type Object: ?Sized + Send + Sync + 'static;
+}
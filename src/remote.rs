@@ -0,0 +1,111 @@
+//! Calling entraited traits across process boundaries, for the `remote` entrait option.
+//!
+//! `#[entrait(remote = FooRemote)]` on a trait `Foo` generates:
+//!
+//! * `FooRemoteRequest`: A serializable enum with one variant per method, holding its arguments.
+//!   `FooRemoteRequest::dispatch` executes the request against any `D: Foo`.
+//! * `FooRemoteResponse`: A serializable enum with one variant per method, holding its return value.
+//! * `FooRemoteDispatcher<D>`: A [Dispatch] implementation decoding requests and executing them against any `D: Foo`,
+//!   e.g. an `Impl<App>`. This is the server side, to be hooked up to whatever carries the bytes.
+//! * `FooRemote<T>`: A client implementing `Foo` itself, by sending requests over any [Transport].
+//!
+//! Call sites depending on `Foo` thereby stay the same whether the implementation runs in-process or remotely.
+//! [InMemoryTransport] connects a client directly to a dispatcher, which is useful for testing:
+//!
+//! ```
+//! # use entrait::*;
+//! # use entrait::remote::*;
+//! #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+//! struct PlanetError(String);
+//!
+//! impl From<RemoteError> for PlanetError {
+//!     fn from(error: RemoteError) -> Self {
+//!         Self(error.to_string())
+//!     }
+//! }
+//!
+//! #[entrait(remote = PlanetRemote, box_future)]
+//! trait FetchPlanet {
+//!     async fn fetch_planet(&self, id: u32) -> Result<String, PlanetError>;
+//! }
+//!
+//! struct App;
+//!
+//! #[async_trait::async_trait]
+//! impl FetchPlanet for App {
+//!     async fn fetch_planet(&self, id: u32) -> Result<String, PlanetError> {
+//!         Ok(format!("planet{id}"))
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")] async fn main() {
+//! let client = PlanetRemote(InMemoryTransport(PlanetRemoteDispatcher(Impl::new(App))));
+//! assert_eq!(Ok("planet3".to_string()), client.fetch_planet(3).await);
+//! # }
+//! ```
+//!
+//! All methods must be async using boxed futures, take `&self`, and take their arguments by value.
+//! Arguments and return values must implement [serde::Serialize] and [serde::Deserialize], and are encoded as JSON.
+//!
+//! Methods must return `Result<T, E>` where `E: From<RemoteError>`:
+//! If a client call fails, the client returns the [RemoteError] converted into `E`.
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+
+/// A boxed, sendable future.
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// An error occurring while performing a remote call.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RemoteError {
+    /// A request or response could not be encoded.
+    Encode(String),
+    /// A request or response could not be decoded.
+    Decode(String),
+    /// The transport failed to deliver the request or the response.
+    Transport(Box<dyn std::error::Error + Send + Sync>),
+    /// The response did not correspond to the method called.
+    UnexpectedResponse,
+}
+
+impl fmt::Display for RemoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Encode(msg) => write!(f, "failed to encode message: {msg}"),
+            Self::Decode(msg) => write!(f, "failed to decode message: {msg}"),
+            Self::Transport(error) => write!(f, "transport error: {error}"),
+            Self::UnexpectedResponse => write!(f, "unexpected response"),
+        }
+    }
+}
+
+impl std::error::Error for RemoteError {}
+
+/// A transport delivering encoded requests to a remote [Dispatch], and returning its encoded response.
+pub trait Transport: Send + Sync {
+    /// Send an encoded request, and receive the encoded response.
+    fn send(&self, request: Vec<u8>) -> BoxFuture<'_, Result<Vec<u8>, RemoteError>>;
+}
+
+/// Decodes and executes encoded requests, producing encoded responses.
+///
+/// Implemented by the dispatchers generated by the `remote` entrait option.
+pub trait Dispatch: Send + Sync {
+    /// Execute an encoded request, returning the encoded response.
+    fn dispatch(&self, request: Vec<u8>) -> BoxFuture<'_, Result<Vec<u8>, RemoteError>>;
+}
+
+/// A [Transport] calling a [Dispatch] in-process.
+///
+/// Requests and responses are still encoded, so serialization is exercised as for a real transport.
+#[derive(Clone, Debug)]
+pub struct InMemoryTransport<D>(pub D);
+
+impl<D: Dispatch> Transport for InMemoryTransport<D> {
+    fn send(&self, request: Vec<u8>) -> BoxFuture<'_, Result<Vec<u8>, RemoteError>> {
+        self.0.dispatch(request)
+    }
+}
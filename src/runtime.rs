@@ -0,0 +1,353 @@
+//! Async runtime abstraction, for the `retry`, `timeout_ms` and `blocking_variant` entrait options.
+//!
+//! Outbound calls can be made resilient declaratively.
+//! The generated delegation of an async function returning `Result<T, E>` then wraps the call:
+//!
+//! * `timeout_ms = N` fails an attempt that takes longer than `N` milliseconds, with [TimedOut] converted into `E` using `From<TimedOut>`.
+//! * `retry(max = N)` retries a failed call up to `N` times. Arguments are cloned for every attempt, so they must implement [Clone].
+//!   The delay between attempts is controlled by `backoff = "none"`, `"fixed"` or `"exp"` (the default),
+//!   starting at `delay_ms` milliseconds (default `100`), see [Backoff].
+//!
+//! With both options, the timeout applies to each attempt.
+//!
+//! Waiting is delegated to a [Runtime], which the application provides by implementing [HasRuntime].
+//! The `tokio` feature includes the `Tokio` runtime.
+//! This example uses a runtime that does not wait at all:
+//!
+//! ```
+//! # use entrait::*;
+//! # use entrait::runtime::*;
+//! # use std::sync::atomic::{AtomicU32, Ordering};
+//! # use std::time::Duration;
+//! #[derive(Debug)]
+//! enum FetchError {
+//!     Unavailable,
+//! }
+//!
+//! static ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+//!
+//! #[entrait(Fetch, no_deps, box_future, retry(max = 3, backoff = "fixed", delay_ms = 10))]
+//! async fn fetch(id: u32) -> Result<String, FetchError> {
+//!     match ATTEMPTS.fetch_add(1, Ordering::SeqCst) {
+//!         0 | 1 => Err(FetchError::Unavailable),
+//!         _ => Ok(format!("item{id}")),
+//!     }
+//! }
+//!
+//! struct NoWait;
+//!
+//! impl Runtime for NoWait {
+//!     fn sleep(&self, _: Duration) -> BoxFuture<'static, ()> {
+//!         Box::pin(async {})
+//!     }
+//! }
+//!
+//! struct App;
+//!
+//! impl HasRuntime for App {
+//!     fn entrait_runtime(&self) -> &dyn Runtime {
+//!         &NoWait
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")] async fn main() {
+//! let app = Impl::new(App);
+//! assert_eq!("item1", app.fetch(1).await.unwrap());
+//! assert_eq!(3, ATTEMPTS.load(Ordering::SeqCst));
+//! # }
+//! ```
+//!
+//! Mocks implement the trait directly, and are never retried or timed out.
+//!
+//! # Blocking variants
+//! Functions called from both async tasks and blocking threads can get a synchronous trait in addition to the async one,
+//! using `blocking_variant = FooBlocking`.
+//! Callers choose between them through their bounds.
+//! The `Impl<T>` implementation of the blocking trait runs the async method to completion on an [Executor],
+//! which the application provides by implementing [HasExecutor]:
+//!
+//! ```
+//! # use entrait::*;
+//! # use entrait::runtime::*;
+//! #[entrait(FetchName, blocking_variant = FetchNameBlocking)]
+//! async fn fetch_name(_deps: &impl std::any::Any, id: u32) -> String {
+//!     format!("name{id}")
+//! }
+//!
+//! #[entrait(Report)]
+//! fn report(deps: &impl FetchNameBlocking) -> String {
+//!     deps.fetch_name(42)
+//! }
+//!
+//! struct App;
+//!
+//! impl HasExecutor for App {
+//!     fn entrait_executor(&self) -> &dyn Executor {
+//!         &CurrentThread
+//!     }
+//! }
+//!
+//! assert_eq!("name42", Impl::new(App).report());
+//! ```
+//!
+//! With the `tokio` feature, a tokio runtime [Handle](::tokio::runtime::Handle) is an executor.
+//! Like `Handle::block_on`, blocking panics when called from within an async task.
+//!
+//! Mocks are generated for both traits, and the mock API of the blocking trait is named after it, e.g. `FetchNameBlockingMock`.
+//! The blocking traits of mockable functions are also implemented for the wrappers around `Impl<T>`, like `Overrides<T>` and `Scoped<T, C>`,
+//! which implement [HasExecutor] as well.
+
+use alloc::boxed::Box;
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+use crate::Impl;
+
+/// A boxed, sendable future.
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A boxed future, which is not necessarily sendable.
+pub type LocalBoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// The timer functionality of an async runtime.
+pub trait Runtime: Send + Sync {
+    /// Create a future completing after `duration`.
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()>;
+}
+
+/// Provides the [Runtime] used by entraited functions with the `retry` or `timeout_ms` options.
+///
+/// Implement this for the application type `T`, and `Impl<T>` implements it as well.
+pub trait HasRuntime {
+    /// The runtime of the application.
+    fn entrait_runtime(&self) -> &dyn Runtime;
+}
+
+impl<T: HasRuntime> HasRuntime for Impl<T> {
+    fn entrait_runtime(&self) -> &dyn Runtime {
+        (**self).entrait_runtime()
+    }
+}
+
+#[cfg(feature = "overrides")]
+impl<T: HasRuntime> HasRuntime for crate::overrides::Overrides<T> {
+    fn entrait_runtime(&self) -> &dyn Runtime {
+        self.as_impl().entrait_runtime()
+    }
+}
+
+#[cfg(feature = "scoped")]
+impl<T: HasRuntime, C> HasRuntime for crate::scoped::Scoped<T, C> {
+    fn entrait_runtime(&self) -> &dyn Runtime {
+        self.as_impl().entrait_runtime()
+    }
+}
+
+/// The [tokio](::tokio) runtime.
+#[cfg(feature = "tokio")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Tokio;
+
+#[cfg(feature = "tokio")]
+impl Runtime for Tokio {
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(::tokio::time::sleep(duration))
+    }
+}
+
+/// The error of a call that did not complete within its timeout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimedOut;
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the call timed out")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TimedOut {}
+
+/// The delay between retries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backoff {
+    /// Retry immediately.
+    None,
+    /// Wait the same duration before every retry.
+    Fixed(Duration),
+    /// Wait the given duration before the first retry, doubling it for every following retry.
+    Exponential(Duration),
+}
+
+impl Backoff {
+    /// The delay before the retry with the zero-based index `retry`.
+    pub fn delay(&self, retry: u32) -> Option<Duration> {
+        match self {
+            Self::None => None,
+            Self::Fixed(delay) => Some(*delay),
+            Self::Exponential(initial) => Some(
+                initial
+                    .checked_mul(2u32.saturating_pow(retry))
+                    .unwrap_or(Duration::MAX),
+            ),
+        }
+    }
+}
+
+/// How to retry failed calls.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum number of retries after the first attempt.
+    pub max_retries: u32,
+    /// The delay between retries.
+    pub backoff: Backoff,
+}
+
+/// Call `attempt` until it succeeds, or `policy` permits no more retries.
+pub async fn retry<F, Fut, T, E>(
+    runtime: &dyn Runtime,
+    policy: RetryPolicy,
+    mut attempt: F,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut retries = 0;
+    loop {
+        let delay = {
+            let result = attempt().await;
+            if result.is_ok() || retries >= policy.max_retries {
+                return result;
+            }
+            policy.backoff.delay(retries)
+        };
+        retries += 1;
+
+        if let Some(delay) = delay {
+            runtime.sleep(delay).await;
+        }
+    }
+}
+
+/// Await `future`, failing with [TimedOut] if it does not complete within `duration`.
+pub fn timeout<F, T, E>(
+    runtime: &dyn Runtime,
+    duration: Duration,
+    future: F,
+) -> impl Future<Output = Result<T, E>>
+where
+    F: Future<Output = Result<T, E>>,
+    E: From<TimedOut>,
+{
+    Timeout {
+        future: Box::pin(future),
+        sleep: runtime.sleep(duration),
+    }
+}
+
+struct Timeout<F> {
+    future: Pin<Box<F>>,
+    sleep: BoxFuture<'static, ()>,
+}
+
+impl<F, T, E> Future for Timeout<F>
+where
+    F: Future<Output = Result<T, E>>,
+    E: From<TimedOut>,
+{
+    type Output = Result<T, E>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Poll::Ready(output) = self.future.as_mut().poll(cx) {
+            return Poll::Ready(output);
+        }
+
+        match self.sleep.as_mut().poll(cx) {
+            Poll::Ready(()) => Poll::Ready(Err(E::from(TimedOut))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// An executor for running futures to completion on the calling thread.
+pub trait Executor: Send + Sync {
+    /// Run `future` to completion, blocking the current thread.
+    fn block_on(&self, future: LocalBoxFuture<'_, ()>);
+}
+
+/// Provides the [Executor] used by blocking variants of entraited functions, see the `blocking_variant` option.
+///
+/// Implement this for the application type `T`, and `Impl<T>` implements it as well.
+pub trait HasExecutor {
+    /// The executor of the application.
+    fn entrait_executor(&self) -> &dyn Executor;
+}
+
+impl<T: HasExecutor> HasExecutor for Impl<T> {
+    fn entrait_executor(&self) -> &dyn Executor {
+        (**self).entrait_executor()
+    }
+}
+
+#[cfg(feature = "overrides")]
+impl<T: HasExecutor> HasExecutor for crate::overrides::Overrides<T> {
+    fn entrait_executor(&self) -> &dyn Executor {
+        self.as_impl().entrait_executor()
+    }
+}
+
+#[cfg(feature = "scoped")]
+impl<T: HasExecutor, C> HasExecutor for crate::scoped::Scoped<T, C> {
+    fn entrait_executor(&self) -> &dyn Executor {
+        self.as_impl().entrait_executor()
+    }
+}
+
+/// Run `future` to completion using `executor`, returning its output.
+pub fn block_on<F: Future>(executor: &dyn Executor, future: F) -> F::Output {
+    let mut output = None;
+    executor.block_on(Box::pin(async {
+        output = Some(future.await);
+    }));
+    output.expect("the executor did not run the future to completion")
+}
+
+/// An executor polling the future on the calling thread, parking the thread while waiting to be woken.
+///
+/// It has no timers or I/O of its own, so it only suits futures that are driven by other threads,
+/// like futures communicating over channels, or that complete without waiting.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CurrentThread;
+
+#[cfg(feature = "std")]
+impl Executor for CurrentThread {
+    fn block_on(&self, mut future: LocalBoxFuture<'_, ()>) {
+        struct ThreadWaker(std::thread::Thread);
+
+        impl std::task::Wake for ThreadWaker {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+
+        let waker = core::task::Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        while future.as_mut().poll(&mut cx).is_pending() {
+            std::thread::park();
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Executor for ::tokio::runtime::Handle {
+    fn block_on(&self, future: LocalBoxFuture<'_, ()>) {
+        ::tokio::runtime::Handle::block_on(self, future)
+    }
+}
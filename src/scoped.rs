@@ -0,0 +1,102 @@
+//! Request-scoped context for [Impl].
+//!
+//! A [Scoped] value wraps the application `Impl<T>` together with a _context_ value `C`,
+//! e.g. the authenticated user of the current request.
+//! Entrait generates an implementation of every trait implemented for `Impl<T>` for [Scoped] as well,
+//! and [Scoped] implements [ScopeContext] for accessing the context.
+//!
+//! Accessor traits for the context are written as ordinary entraited functions depending on [ScopeContext],
+//! so that business functions can depend on e.g. `&impl CurrentUser`, which is easy to mock:
+//!
+//! ```
+//! # use entrait::*;
+//! # use entrait::scoped::*;
+//! struct RequestCtx {
+//!     user_name: String,
+//! }
+//!
+//! #[entrait(CurrentUser)]
+//! fn current_user(deps: &impl ScopeContext<RequestCtx>) -> &str {
+//!     &deps.scope_context().user_name
+//! }
+//!
+//! #[entrait(Greet)]
+//! fn greet(deps: &impl CurrentUser) -> String {
+//!     format!("Hello, {}!", deps.current_user())
+//! }
+//!
+//! struct App;
+//!
+//! let app = Impl::new(App);
+//! let scoped = Scoped::new(app, RequestCtx { user_name: "Alice".to_string() });
+//!
+//! assert_eq!("Hello, Alice!", scoped.greet());
+//! ```
+//!
+//! With unimock, write the bound of such accessors with its full path, `impl entrait::scoped::ScopeContext<C>`.
+//! A mock can't provide the context, so entrait only skips unmocking of accessors it recognizes by that path.
+//!
+//! [Scoped] owns its `Impl<T>`, so `T` should be cheap to clone when creating one per request,
+//! e.g. by keeping the application state behind an `Arc`.
+//!
+//! Implementations of entraited traits for [Scoped] forward to `Impl<T>`.
+//! Hence code reached through `delegate_by` or a hand-written implementation of a trait for the inner `T`
+//! does not have access to the context.
+
+use crate::Impl;
+
+/// An `Impl<T>` together with a request-scoped context `C`.
+#[derive(Clone, Debug)]
+pub struct Scoped<T, C> {
+    implementation: Impl<T>,
+    context: C,
+}
+
+impl<T, C> Scoped<T, C> {
+    /// Construct a new [Scoped] from the application and the context.
+    pub fn new(implementation: Impl<T>, context: C) -> Self {
+        Self {
+            implementation,
+            context,
+        }
+    }
+
+    /// Access the application implementation.
+    pub fn as_impl(&self) -> &Impl<T> {
+        &self.implementation
+    }
+
+    /// Access the context.
+    pub fn context(&self) -> &C {
+        &self.context
+    }
+
+    /// Split into the application implementation and the context.
+    pub fn into_parts(self) -> (Impl<T>, C) {
+        (self.implementation, self.context)
+    }
+}
+
+/// Access to the request-scoped context of type `C`.
+pub trait ScopeContext<C> {
+    /// Access the context.
+    fn scope_context(&self) -> &C;
+}
+
+impl<T, C> ScopeContext<C> for Scoped<T, C> {
+    fn scope_context(&self) -> &C {
+        &self.context
+    }
+}
+
+/// Extension trait for constructing [Scoped] directly from an [Impl].
+pub trait ScopeExt<T> {
+    /// Attach the request-scoped context `context`.
+    fn scoped<C>(self, context: C) -> Scoped<T, C>;
+}
+
+impl<T> ScopeExt<T> for Impl<T> {
+    fn scoped<C>(self, context: C) -> Scoped<T, C> {
+        Scoped::new(self, context)
+    }
+}
@@ -0,0 +1,249 @@
+//! Ready-made entrait traits for common leaf dependencies: time, randomness, UUIDs and environment variables.
+//!
+//! Each trait uses `delegate_by = ref`, so the application chooses an implementation by implementing `AsRef<dyn Trait>`.
+//! The real implementations talk to the system, while the fake implementations are deterministic, for tests:
+//!
+//! | Trait     | Real implementation | Fake implementation |
+//! | --------- | ------------------- | ------------------- |
+//! | [Clock]   | [SystemClock]       | [FakeClock]         |
+//! | [Random]  | [StdRandom]         | [SeededRandom]      |
+//! | [Uuids]   | [RandomUuids]       | [SequentialUuids]   |
+//! | [Env]     | [ProcessEnv]        | [FakeEnv]           |
+//!
+//! With the `unimock` feature, the traits are also mockable with unimock, using `ClockMock`, `RandomMock`, `UuidsMock` and `EnvMock`.
+//!
+//! ```
+//! # use entrait::*;
+//! use entrait::std_deps::*;
+//! use std::time::{Duration, SystemTime};
+//!
+//! #[entrait(Greet)]
+//! fn greet(deps: &(impl Clock + Env)) -> String {
+//!     let name = deps.var("NAME").unwrap_or_else(|| "world".to_string());
+//!     let secs = deps.now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs();
+//!     format!("Hello, {name}! It's {secs}.")
+//! }
+//!
+//! struct App {
+//!     clock: Box<dyn Clock + Send + Sync>,
+//!     env: Box<dyn Env + Send + Sync>,
+//! }
+//!
+//! impl AsRef<dyn Clock> for App {
+//!     fn as_ref(&self) -> &dyn Clock {
+//!         self.clock.as_ref()
+//!     }
+//! }
+//!
+//! impl AsRef<dyn Env> for App {
+//!     fn as_ref(&self) -> &dyn Env {
+//!         self.env.as_ref()
+//!     }
+//! }
+//!
+//! // In production:
+//! let app = Impl::new(App {
+//!     clock: Box::new(SystemClock),
+//!     env: Box::new(ProcessEnv),
+//! });
+//! app.greet();
+//!
+//! // In tests:
+//! let app = Impl::new(App {
+//!     clock: Box::new(FakeClock::new(SystemTime::UNIX_EPOCH + Duration::from_secs(42))),
+//!     env: Box::new(FakeEnv::new().with("NAME", "entrait")),
+//! });
+//! assert_eq!("Hello, entrait! It's 42.", app.greet());
+//! ```
+
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use crate::entrait_export as entrait;
+
+/// The current time.
+#[entrait(delegate_by = ref, mock_api = ClockMock)]
+pub trait Clock: 'static {
+    /// The current time.
+    fn now(&self) -> SystemTime;
+}
+
+/// A [Clock] reading the system time.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A [Clock] that only moves when told to.
+#[derive(Debug)]
+pub struct FakeClock(Mutex<SystemTime>);
+
+impl FakeClock {
+    /// A clock standing still at `now`.
+    pub fn new(now: SystemTime) -> Self {
+        Self(Mutex::new(now))
+    }
+
+    /// Set the current time.
+    pub fn set(&self, now: SystemTime) {
+        *self.0.lock().unwrap() = now;
+    }
+
+    /// Move the current time forward.
+    pub fn advance(&self, duration: Duration) {
+        *self.0.lock().unwrap() += duration;
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> SystemTime {
+        *self.0.lock().unwrap()
+    }
+}
+
+/// Random numbers.
+#[entrait(delegate_by = ref, mock_api = RandomMock)]
+pub trait Random: 'static {
+    /// A random `u64`.
+    fn next_u64(&self) -> u64;
+}
+
+/// A [Random] using the randomly seeded hasher of the standard library.
+///
+/// The numbers are unpredictable, but not suitable for cryptography.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StdRandom;
+
+impl Random for StdRandom {
+    fn next_u64(&self) -> u64 {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+        hasher.finish()
+    }
+}
+
+/// A [Random] producing the same sequence of numbers for the same seed.
+#[derive(Debug)]
+pub struct SeededRandom(AtomicU64);
+
+impl SeededRandom {
+    /// A generator seeded with `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self(AtomicU64::new(seed))
+    }
+}
+
+impl Random for SeededRandom {
+    fn next_u64(&self) -> u64 {
+        // SplitMix64
+        let mut z = self
+            .0
+            .fetch_add(0x9E37_79B9_7F4A_7C15, Ordering::Relaxed)
+            .wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// Generation of unique ids.
+#[entrait(delegate_by = ref, mock_api = UuidsMock)]
+pub trait Uuids: 'static {
+    /// A new UUID.
+    fn new_uuid(&self) -> ::uuid::Uuid;
+}
+
+/// [Uuids] generating random (version 4) UUIDs, using [StdRandom].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RandomUuids;
+
+impl Uuids for RandomUuids {
+    fn new_uuid(&self) -> ::uuid::Uuid {
+        let high = StdRandom.next_u64().to_be_bytes();
+        let low = StdRandom.next_u64().to_be_bytes();
+
+        let mut bytes = [0; 16];
+        bytes[..8].copy_from_slice(&high);
+        bytes[8..].copy_from_slice(&low);
+
+        ::uuid::Builder::from_random_bytes(bytes).into_uuid()
+    }
+}
+
+/// [Uuids] counting upwards from `00000000-0000-0000-0000-000000000001`.
+#[derive(Debug, Default)]
+pub struct SequentialUuids(AtomicU64);
+
+impl SequentialUuids {
+    /// Ids counting from 1.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Uuids for SequentialUuids {
+    fn new_uuid(&self) -> ::uuid::Uuid {
+        let n = self.0.fetch_add(1, Ordering::Relaxed) + 1;
+        ::uuid::Uuid::from_u128(n as u128)
+    }
+}
+
+/// Environment variables.
+#[entrait(delegate_by = ref, mock_api = EnvMock)]
+pub trait Env: 'static {
+    /// The value of the environment variable `key`, if it is set to valid unicode.
+    fn var(&self, key: &str) -> Option<String>;
+}
+
+/// An [Env] reading the environment of the process.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ProcessEnv;
+
+impl Env for ProcessEnv {
+    fn var(&self, key: &str) -> Option<String> {
+        std::env::var(key).ok()
+    }
+}
+
+/// An [Env] with a fixed set of variables.
+#[derive(Clone, Debug, Default)]
+pub struct FakeEnv(HashMap<String, String>);
+
+impl FakeEnv {
+    /// An environment without variables.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the variable `key` to `value`.
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.0.insert(key.into(), value.into());
+        self
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for FakeEnv {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        )
+    }
+}
+
+impl Env for FakeEnv {
+    fn var(&self, key: &str) -> Option<String> {
+        self.0.get(key).cloned()
+    }
+}
@@ -3,5 +3,6 @@ set -e
 set -x
 
 cargo hack --feature-powerset --exclude-features "default use-associated-futures nightly-tests" --exclude-no-default-features test
-cargo test --workspace --features "unimock use-boxed-futures"
-cargo test --doc --features "unimock use-boxed-futures"
+cargo test --workspace --features "unimock use-boxed-futures overrides scoped axum async-graphql tower tonic remote actor cache tokio metrics serde stream std-deps fake"
+cargo test --doc --features "unimock use-boxed-futures overrides scoped axum async-graphql tower tonic remote actor cache tokio metrics serde stream std-deps fake"
+cargo test -p test-no-std
@@ -0,0 +1,120 @@
+use entrait::actor::ActorStopped;
+use entrait::*;
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+#[derive(Debug, PartialEq)]
+enum CounterError {
+    TooLarge,
+    Stopped,
+}
+
+impl From<ActorStopped> for CounterError {
+    fn from(_: ActorStopped) -> Self {
+        Self::Stopped
+    }
+}
+
+#[entrait(CounterActor, delegate_by = actor, box_future)]
+trait Counter {
+    async fn increment(&self, amount: u32) -> Result<u32, CounterError>;
+    async fn get(&self) -> Result<u32, CounterError>;
+    async fn reset(&self) -> Result<(), CounterError>;
+}
+
+#[entrait(IncrementTwice, box_future)]
+async fn increment_twice(deps: &impl Counter) -> Result<u32, CounterError> {
+    deps.increment(1).await?;
+    deps.increment(1).await
+}
+
+#[derive(Default)]
+struct Count(AtomicU32);
+
+#[async_trait::async_trait]
+impl Counter for Count {
+    async fn increment(&self, amount: u32) -> Result<u32, CounterError> {
+        if amount > 100 {
+            return Err(CounterError::TooLarge);
+        }
+        Ok(self.0.fetch_add(amount, Ordering::SeqCst) + amount)
+    }
+
+    async fn get(&self) -> Result<u32, CounterError> {
+        Ok(self.0.load(Ordering::SeqCst))
+    }
+
+    async fn reset(&self) -> Result<(), CounterError> {
+        self.0.store(0, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+struct App {
+    counter: CounterActor,
+}
+
+impl AsRef<CounterActor> for App {
+    fn as_ref(&self) -> &CounterActor {
+        &self.counter
+    }
+}
+
+fn spawn_counter() -> CounterActor {
+    let (counter, receiver) = CounterActor::channel(8);
+    tokio::spawn(CounterActor::run(receiver, Count::default()));
+    counter
+}
+
+#[tokio::test]
+async fn handle_calls_actor() {
+    let counter = spawn_counter();
+
+    assert_eq!(Ok(3), counter.increment(3).await);
+    assert_eq!(Err(CounterError::TooLarge), counter.increment(101).await);
+    assert_eq!(Ok(3), counter.get().await);
+    assert_eq!(Ok(()), counter.reset().await);
+    assert_eq!(Ok(0), counter.get().await);
+}
+
+#[tokio::test]
+async fn impl_delegates_to_actor() {
+    let app = Impl::new(App {
+        counter: spawn_counter(),
+    });
+
+    assert_eq!(Ok(2), app.increment_twice().await);
+    assert_eq!(Ok(2), app.get().await);
+}
+
+#[tokio::test]
+async fn cloned_handles_share_actor() {
+    let counter = spawn_counter();
+    let other = counter.clone();
+
+    counter.increment(1).await.unwrap();
+    other.increment(1).await.unwrap();
+
+    assert_eq!(Ok(2), counter.get().await);
+}
+
+#[tokio::test]
+async fn message_can_be_handled_directly() {
+    let count = Count::default();
+    let (reply, response) = tokio::sync::oneshot::channel();
+
+    CounterActorMessage::Increment { amount: 5, reply }
+        .handle(&count)
+        .await;
+
+    assert_eq!(Ok(5), response.await.unwrap());
+}
+
+#[tokio::test]
+async fn stopped_actor_is_converted_to_error() {
+    let (counter, receiver) = CounterActor::channel(1);
+    drop(receiver);
+
+    assert_eq!(Err(CounterError::Stopped), counter.increment(1).await);
+    assert_eq!(Err(CounterError::Stopped), counter.reset().await);
+}
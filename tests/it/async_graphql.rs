@@ -0,0 +1,165 @@
+use entrait::integration::async_graphql::*;
+use entrait::*;
+
+use ::async_graphql::{value, EmptyMutation, EmptySubscription, Object, Request, Schema};
+use std::marker::PhantomData;
+
+#[entrait(FetchValue, no_deps, mock_api = FetchValueMock)]
+fn fetch_value() -> i32 {
+    42
+}
+
+struct Query<D>(PhantomData<D>);
+
+#[Object]
+impl<D: FetchValue + Send + Sync + 'static> Query<D> {
+    async fn value(&self, ctx: &::async_graphql::Context<'_>) -> ::async_graphql::Result<i32> {
+        Ok(ctx.deps::<D>()?.fetch_value())
+    }
+
+    async fn value_unchecked(&self, ctx: &::async_graphql::Context<'_>) -> i32 {
+        ctx.deps_unchecked::<D>().fetch_value()
+    }
+}
+
+fn schema_builder<D: FetchValue + Send + Sync + 'static>(
+) -> ::async_graphql::SchemaBuilder<Query<D>, EmptyMutation, EmptySubscription> {
+    Schema::build(Query::<D>(PhantomData), EmptyMutation, EmptySubscription)
+}
+
+struct App;
+
+#[tokio::test]
+async fn deps_registered_in_schema() {
+    let response = schema_builder::<Impl<App>>()
+        .deps(Impl::new(App))
+        .finish()
+        .execute("{ value valueUnchecked }")
+        .await;
+
+    assert_eq!(value!({ "value": 42, "valueUnchecked": 42 }), response.data);
+}
+
+#[tokio::test]
+async fn deps_registered_in_request() {
+    let response = schema_builder::<Impl<App>>()
+        .finish()
+        .execute(Request::new("{ value }").deps(Impl::new(App)))
+        .await;
+
+    assert_eq!(value!({ "value": 42 }), response.data);
+}
+
+#[tokio::test]
+async fn deps_not_registered() {
+    let response = schema_builder::<Impl<App>>()
+        .data(Impl::new(App))
+        .finish()
+        .execute("{ value }")
+        .await;
+
+    assert_eq!(1, response.errors.len());
+    assert!(response.errors[0].message.contains("are not registered"));
+}
+
+struct InjectedQuery<D>(PhantomData<D>);
+
+#[inject_deps]
+#[Object]
+impl<D> InjectedQuery<D> {
+    async fn value(&self, deps: &impl FetchValue) -> i32 {
+        deps.fetch_value()
+    }
+
+    async fn checked_value(&self, deps: &impl FetchValue) -> ::async_graphql::Result<i32> {
+        Ok(deps.fetch_value())
+    }
+
+    async fn value_with_ctx(
+        &self,
+        ctx: &::async_graphql::Context<'_>,
+        deps: &impl FetchValue,
+        add: i32,
+    ) -> i32 {
+        assert!(ctx.deps::<D>().is_ok());
+        deps.fetch_value() + add
+    }
+}
+
+fn injected_schema<D: FetchValue + Send + Sync + 'static>(
+) -> ::async_graphql::SchemaBuilder<InjectedQuery<D>, EmptyMutation, EmptySubscription> {
+    Schema::build(
+        InjectedQuery::<D>(PhantomData),
+        EmptyMutation,
+        EmptySubscription,
+    )
+}
+
+#[tokio::test]
+async fn injected_deps() {
+    let response = injected_schema::<Impl<App>>()
+        .deps(Impl::new(App))
+        .finish()
+        .execute("{ value checkedValue valueWithCtx(add: 1) }")
+        .await;
+
+    assert_eq!(
+        value!({ "value": 42, "checkedValue": 42, "valueWithCtx": 43 }),
+        response.data
+    );
+}
+
+#[tokio::test]
+async fn injected_deps_not_registered() {
+    let response = injected_schema::<Impl<App>>()
+        .finish()
+        .execute("{ checkedValue }")
+        .await;
+
+    assert_eq!(1, response.errors.len());
+    assert!(response.errors[0].message.contains("are not registered"));
+}
+
+#[cfg(feature = "unimock")]
+#[tokio::test]
+async fn injected_mock() {
+    use unimock::*;
+
+    let deps = Unimock::new(FetchValueMock.each_call(matching!()).returns(1337));
+    let response = injected_schema::<Unimock>()
+        .deps(deps.clone())
+        .finish()
+        .execute("{ value }")
+        .await;
+
+    assert_eq!(value!({ "value": 1337 }), response.data);
+}
+
+#[cfg(feature = "unimock")]
+#[tokio::test]
+async fn mocked_deps() {
+    use unimock::*;
+
+    #[entrait(FetchMockedValue, mock_api = FetchMockedValueMock)]
+    fn fetch_mocked_value(_: &impl FetchValue) -> i32 {
+        0
+    }
+
+    struct MockQuery;
+
+    #[Object]
+    impl MockQuery {
+        async fn value(&self, ctx: &::async_graphql::Context<'_>) -> ::async_graphql::Result<i32> {
+            Ok(ctx.deps::<Unimock>()?.fetch_mocked_value())
+        }
+    }
+
+    let deps = Unimock::new(FetchMockedValueMock.each_call(matching!()).returns(1337));
+    let response = Schema::build(MockQuery, EmptyMutation, EmptySubscription)
+        .deps(deps.clone())
+        .finish()
+        .execute("{ value }")
+        .await;
+
+    assert_eq!(value!({ "value": 1337 }), response.data);
+}
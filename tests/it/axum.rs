@@ -0,0 +1,99 @@
+use entrait::integration::axum::*;
+use entrait::*;
+
+use ::axum::body::Body;
+use ::axum::http::Request;
+use ::axum::routing::get;
+use ::axum::Router;
+use tower::ServiceExt;
+
+#[entrait(FetchPlanet, no_deps)]
+fn fetch_planet() -> String {
+    "World".to_string()
+}
+
+#[entrait(SayHello)]
+fn say_hello(deps: &impl FetchPlanet) -> String {
+    format!("Hello {}!", deps.fetch_planet())
+}
+
+async fn hello<D: SayHello>(deps: Deps<D>) -> String {
+    deps.say_hello()
+}
+
+fn routes<D: AxumDeps + SayHello>(router: Router<D>) -> Router<D> {
+    router.route("/hello", get(hello::<D>))
+}
+
+async fn get_body(router: Router, uri: &str) -> String {
+    let response = router
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[derive(Clone)]
+struct App;
+
+#[tokio::test]
+async fn serve_impl() {
+    assert_eq!(
+        "Hello World!",
+        get_body(router(Impl::new(App), routes), "/hello").await
+    );
+}
+
+#[tokio::test]
+async fn deps_from_substate() {
+    #[derive(Clone)]
+    struct State {
+        app: Impl<App>,
+    }
+
+    impl ::axum::extract::FromRef<State> for Impl<App> {
+        fn from_ref(state: &State) -> Self {
+            state.app.clone()
+        }
+    }
+
+    let router = Router::new()
+        .route("/hello", get(hello::<Impl<App>>))
+        .with_state(State {
+            app: Impl::new(App),
+        });
+
+    assert_eq!("Hello World!", get_body(router, "/hello").await);
+}
+
+#[cfg(feature = "unimock")]
+#[tokio::test]
+async fn serve_unimock() {
+    use unimock::*;
+
+    #[entrait(Greeting, mock_api = GreetingMock)]
+    fn greeting(_: &impl FetchPlanet) -> String {
+        "Hello".to_string()
+    }
+
+    async fn greeting_handler<D: Greeting>(Deps(deps): Deps<D>) -> String {
+        deps.greeting()
+    }
+
+    let deps = Unimock::new(
+        GreetingMock
+            .each_call(matching!())
+            .returns("Mocked".to_string()),
+    );
+
+    assert_eq!(
+        "Mocked",
+        get_body(
+            router(deps.clone(), |router| router
+                .route("/", get(greeting_handler::<Unimock>))),
+            "/"
+        )
+        .await
+    );
+}
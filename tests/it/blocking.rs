@@ -0,0 +1,193 @@
+use entrait::runtime::{CurrentThread, Executor, HasExecutor};
+use entrait::*;
+
+struct App {
+    greeting: String,
+}
+
+impl HasExecutor for App {
+    fn entrait_executor(&self) -> &dyn Executor {
+        &CurrentThread
+    }
+}
+
+#[entrait(GetGreeting)]
+fn get_greeting(app: &App) -> &str {
+    &app.greeting
+}
+
+#[entrait(Greet, blocking_variant = GreetBlocking)]
+async fn greet(deps: &impl GetGreeting, name: &str) -> String {
+    format!("{}, {name}!", deps.get_greeting())
+}
+
+#[entrait(GreetAll)]
+fn greet_all(deps: &impl GreetBlocking, names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| deps.greet(name)).collect()
+}
+
+#[entrait(Ping, no_deps, blocking_variant = PingBlocking)]
+async fn ping(count: u32) -> u32 {
+    count + 1
+}
+
+fn app() -> Impl<App> {
+    Impl::new(App {
+        greeting: "Hello".to_string(),
+    })
+}
+
+#[test]
+fn blocking_variant_from_sync_code() {
+    let app = app();
+
+    assert_eq!(
+        vec!["Hello, Ann!".to_string(), "Hello, Bob!".to_string()],
+        app.greet_all(&["Ann", "Bob"])
+    );
+    assert_eq!(2, PingBlocking::ping(&app, 1));
+}
+
+#[tokio::test]
+async fn async_variant_is_unchanged() {
+    let app = app();
+
+    assert_eq!("Hello, Ann!", Greet::greet(&app, "Ann").await);
+    assert_eq!(2, Ping::ping(&app, 1).await);
+}
+
+#[test]
+fn blocking_on_futures_woken_by_other_threads() {
+    struct Channel(std::sync::Mutex<Option<tokio::sync::oneshot::Receiver<u32>>>);
+
+    impl HasExecutor for Channel {
+        fn entrait_executor(&self) -> &dyn Executor {
+            &CurrentThread
+        }
+    }
+
+    #[entrait(Receive, blocking_variant = ReceiveBlocking)]
+    async fn receive(channel: &Channel) -> u32 {
+        let receiver = channel.0.lock().unwrap().take().unwrap();
+        receiver.await.unwrap()
+    }
+
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    let app = Impl::new(Channel(std::sync::Mutex::new(Some(receiver))));
+
+    let thread = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        sender.send(42).unwrap();
+    });
+
+    assert_eq!(42, ReceiveBlocking::receive(&app));
+    thread.join().unwrap();
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn tokio_handle_executor() {
+    struct TokioApp(tokio::runtime::Handle);
+
+    impl HasExecutor for TokioApp {
+        fn entrait_executor(&self) -> &dyn Executor {
+            &self.0
+        }
+    }
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let app = Impl::new(TokioApp(runtime.handle().clone()));
+
+    assert_eq!(2, PingBlocking::ping(&app, 1));
+}
+
+#[cfg(feature = "unimock")]
+mod unimock_mocks {
+    use super::*;
+    use unimock::*;
+
+    #[entrait(Fetch, no_deps, blocking_variant = FetchBlocking, mock_api = FetchMock)]
+    async fn fetch(id: u32) -> String {
+        format!("item{id}")
+    }
+
+    #[entrait(Summarize)]
+    fn summarize(deps: &impl FetchBlocking) -> String {
+        deps.fetch(1)
+    }
+
+    #[test]
+    fn blocking_variant_is_mockable() {
+        let deps = Unimock::new(
+            FetchBlockingMock
+                .each_call(matching!(1))
+                .returns("mocked".to_string()),
+        );
+
+        assert_eq!("mocked", summarize(&deps));
+    }
+
+    #[tokio::test]
+    async fn async_variant_is_mockable() {
+        let deps = Unimock::new(
+            FetchMock
+                .each_call(matching!(1))
+                .returns("mocked".to_string()),
+        );
+
+        assert_eq!("mocked", Fetch::fetch(&deps, 1).await);
+    }
+}
+
+mod mockall_mocks {
+    use super::*;
+
+    #[entrait(Count, no_deps, blocking_variant = CountBlocking, mockall)]
+    async fn count() -> u32 {
+        42
+    }
+
+    #[test]
+    fn blocking_variant_is_mockable() {
+        let mut mock = MockCountBlocking::new();
+        mock.expect_count().return_const(7u32);
+
+        assert_eq!(7, mock.count());
+    }
+}
+
+#[cfg(any(feature = "overrides", feature = "scoped"))]
+mod wrappers {
+    use super::*;
+
+    #[entrait(Double, no_deps, blocking_variant = DoubleBlocking, mockall)]
+    async fn double(n: u32) -> u32 {
+        n * 2
+    }
+
+    struct WrappedApp;
+
+    impl HasExecutor for WrappedApp {
+        fn entrait_executor(&self) -> &dyn Executor {
+            &CurrentThread
+        }
+    }
+
+    #[cfg(feature = "overrides")]
+    #[test]
+    fn blocking_variant_is_implemented_for_overrides() {
+        let app = entrait::overrides::Overrides::new(Impl::new(WrappedApp));
+
+        assert_eq!(4, DoubleBlocking::double(&app, 2));
+    }
+
+    #[cfg(feature = "scoped")]
+    #[test]
+    fn blocking_variant_is_implemented_for_scoped() {
+        let app = Impl::new(WrappedApp).scoped(());
+
+        assert_eq!(4, DoubleBlocking::double(&app, 2));
+    }
+}
@@ -0,0 +1,195 @@
+use entrait::cache::*;
+use entrait::*;
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+#[derive(Default)]
+struct App {
+    cache: Cache,
+}
+
+impl HasCache for App {
+    fn entrait_cache(&self) -> &Cache {
+        &self.cache
+    }
+}
+
+mod sync {
+    use super::*;
+
+    static CALLS: AtomicU32 = AtomicU32::new(0);
+
+    #[entrait(Square, cache)]
+    fn square(deps: &impl Offset, n: u32) -> u32 {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        n * n + deps.offset()
+    }
+
+    #[entrait(Offset, no_deps)]
+    fn offset() -> u32 {
+        1
+    }
+
+    #[test]
+    fn results_are_cached_per_argument() {
+        let app = Impl::new(App::default());
+
+        assert_eq!(5, app.square(2));
+        assert_eq!(5, app.square(2));
+        assert_eq!(10, app.square(3));
+        assert_eq!(2, CALLS.load(Ordering::SeqCst));
+
+        app.cache.invalidate(concat!(module_path!(), "::square"));
+        assert_eq!(5, app.square(2));
+        assert_eq!(3, CALLS.load(Ordering::SeqCst));
+    }
+}
+
+mod ttl {
+    use super::*;
+
+    static CALLS: AtomicU32 = AtomicU32::new(0);
+
+    #[entrait(Expiring, no_deps, cache_ttl_secs = 0)]
+    fn expiring(name: String) -> String {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        name
+    }
+
+    #[test]
+    fn expired_results_are_recomputed() {
+        let app = Impl::new(App::default());
+
+        assert_eq!("a", app.expiring("a".to_string()));
+        assert_eq!("a", app.expiring("a".to_string()));
+        assert_eq!(2, CALLS.load(Ordering::SeqCst));
+    }
+}
+
+mod disabled {
+    use super::*;
+
+    static CALLS: AtomicU32 = AtomicU32::new(0);
+
+    #[entrait(NotCached, no_deps, cache = false, cache_ttl_secs = 60)]
+    fn not_cached() -> u32 {
+        CALLS.fetch_add(1, Ordering::SeqCst)
+    }
+
+    #[test]
+    fn cache_can_be_disabled() {
+        // `App` does not need to implement `HasCache`
+        struct NoCache;
+        let app = Impl::new(NoCache);
+
+        assert_eq!(0, app.not_cached());
+        assert_eq!(1, app.not_cached());
+    }
+}
+
+mod collisions {
+    use super::*;
+
+    /// An argument whose values all have the same hash
+    #[derive(Clone, PartialEq, Eq)]
+    struct Colliding(u32);
+
+    impl std::hash::Hash for Colliding {
+        fn hash<H: std::hash::Hasher>(&self, _: &mut H) {}
+    }
+
+    #[entrait(Identity, no_deps, cache)]
+    fn identity(arg: &Colliding) -> u32 {
+        arg.0
+    }
+
+    #[test]
+    fn colliding_hashes_do_not_share_results() {
+        let app = Impl::new(App::default());
+
+        assert_eq!(1, app.identity(&Colliding(1)));
+        assert_eq!(2, app.identity(&Colliding(2)));
+        assert_eq!(1, app.identity(&Colliding(1)));
+    }
+}
+
+#[cfg(feature = "boxed-futures")]
+mod asynchronous {
+    use super::*;
+
+    static CALLS: AtomicU32 = AtomicU32::new(0);
+
+    #[entrait(FetchConfig, no_deps, cache, box_future)]
+    async fn fetch_config(key: &str) -> Option<String> {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        Some(format!("value of {key}"))
+    }
+
+    #[tokio::test]
+    async fn async_results_are_cached() {
+        let app = Impl::new(App::default());
+
+        assert_eq!(Some("value of a".to_string()), app.fetch_config("a").await);
+        assert_eq!(Some("value of a".to_string()), app.fetch_config("a").await);
+        assert_eq!(1, CALLS.load(Ordering::SeqCst));
+    }
+}
+
+mod module {
+    use super::*;
+
+    static CALLS: AtomicU32 = AtomicU32::new(0);
+
+    #[entrait(pub Math, cache)]
+    pub mod math {
+        use super::*;
+
+        pub fn double(deps: &impl std::any::Any, n: u32) -> u32 {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            n * 2
+        }
+
+        pub fn triple(deps: &impl std::any::Any, n: u32) -> u32 {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            n * 3
+        }
+    }
+
+    #[test]
+    fn module_functions_are_cached_separately() {
+        let app = Impl::new(App::default());
+
+        assert_eq!(4, app.double(2));
+        assert_eq!(6, app.triple(2));
+        assert_eq!(4, app.double(2));
+        assert_eq!(2, CALLS.load(Ordering::SeqCst));
+    }
+}
+
+#[cfg(feature = "unimock")]
+mod mocks {
+    use super::*;
+    use unimock::*;
+
+    static CALLS: AtomicU32 = AtomicU32::new(0);
+
+    #[entrait(Cached, no_deps, cache, mock_api = CachedMock)]
+    fn cached(n: u32) -> u32 {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        n
+    }
+
+    #[test]
+    fn mocks_bypass_the_cache() {
+        let deps = Unimock::new(
+            CachedMock
+                .each_call(matching!(_))
+                .answers(|n| n + 1)
+                .n_times(2),
+        );
+
+        assert_eq!(2, deps.cached(1));
+        assert_eq!(2, deps.cached(1));
+        assert_eq!(0, CALLS.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,101 @@
+use entrait::*;
+
+#[entrait(FetchUser, no_deps)]
+fn fetch_user(id: u32) -> String {
+    format!("user{id}")
+}
+
+#[entrait(SendEmail, no_deps)]
+fn send_email(to: &str) -> bool {
+    !to.is_empty()
+}
+
+#[entrait(LogEvent, no_deps)]
+fn log_event(_event: &str) {}
+
+/// Warnings are denied, to make sure that the check doesn't report used bounds.
+#[deny(deprecated)]
+mod all_used {
+    use super::*;
+
+    #[entrait(NotifyUser, check_deps = strict)]
+    fn notify_user(deps: &(impl FetchUser + SendEmail + Send + Sync), id: u32) -> bool {
+        let user = deps.fetch_user(id);
+        deps.send_email(&user)
+    }
+
+    #[entrait(pub Notifications, check_deps)]
+    pub mod notifications {
+        use super::*;
+
+        pub fn notify<D>(deps: &D, id: u32) -> bool
+        where
+            D: FetchUser + SendEmail + LogEvent,
+        {
+            deps.log_event("notify");
+            deps.send_email(&deps.fetch_user(id))
+        }
+    }
+
+    #[test]
+    fn functions_using_all_bounds_compile() {
+        let app = Impl::new(());
+
+        assert!(app.notify_user(1));
+        assert!(app.notify(1));
+    }
+}
+
+#[deny(deprecated)]
+mod inconclusive {
+    use super::*;
+
+    #[entrait(Forward, check_deps = strict)]
+    fn forward(deps: &(impl FetchUser + LogEvent), id: u32) -> String {
+        super::all_used::notifications::notify(&Impl::new(()), id);
+        fetch_with(deps, id)
+    }
+
+    fn fetch_with(deps: &impl FetchUser, id: u32) -> String {
+        deps.fetch_user(id)
+    }
+
+    #[entrait(Arithmetic, check_deps = strict)]
+    fn arithmetic(deps: &(impl super::all_used::Notifications + LogEvent), id: u32) -> bool {
+        deps.notify(id)
+    }
+
+    #[test]
+    fn passing_deps_on_or_calling_unknown_methods_is_not_reported() {
+        let app = Impl::new(());
+
+        assert_eq!("user1", app.forward(1));
+        assert!(app.arithmetic(1));
+    }
+}
+
+/// Unused bounds are reported as deprecation warnings, which can be allowed.
+#[allow(deprecated)]
+mod unused {
+    use super::*;
+
+    #[entrait(Greet, check_deps)]
+    fn greet(deps: &(impl FetchUser + LogEvent), id: u32) -> String {
+        format!("Hello, {}", deps.fetch_user(id))
+    }
+
+    #[entrait(Ignore, check_deps)]
+    fn ignore(_: &impl LogEvent) {}
+
+    #[entrait(Unchecked, check_deps = false)]
+    fn unchecked(deps: &(impl FetchUser + LogEvent)) {}
+
+    #[test]
+    fn functions_with_unused_bounds_still_work() {
+        let app = Impl::new(());
+
+        assert_eq!("Hello, user1", app.greet(1));
+        app.ignore();
+        app.unchecked();
+    }
+}
@@ -0,0 +1,205 @@
+use entrait::*;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+#[entrait(RepositoryImpl, delegate_by = DelegateRepository)]
+pub trait Repository {
+    fn get(&self, key: &str) -> Option<i32>;
+    fn insert(&self, key: &str, value: i32);
+}
+
+#[derive(Default)]
+pub struct HashMapRepository(Mutex<HashMap<String, i32>>);
+
+impl<T: AsRef<Self>> RepositoryImpl<T> for HashMapRepository {
+    fn get(deps: &Impl<T>, key: &str) -> Option<i32> {
+        (**deps).as_ref().0.lock().unwrap().get(key).copied()
+    }
+
+    fn insert(deps: &Impl<T>, key: &str, value: i32) {
+        (**deps)
+            .as_ref()
+            .0
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), value);
+    }
+}
+
+pub struct VecRepository(Mutex<Vec<(String, i32)>>);
+
+impl VecRepository {
+    fn with(entries: &[(&str, i32)]) -> Self {
+        Self(Mutex::new(
+            entries
+                .iter()
+                .map(|(key, value)| (key.to_string(), *value))
+                .collect(),
+        ))
+    }
+}
+
+impl<T: AsRef<Self>> RepositoryImpl<T> for VecRepository {
+    fn get(deps: &Impl<T>, key: &str) -> Option<i32> {
+        let entries = (**deps).as_ref().0.lock().unwrap();
+        entries
+            .iter()
+            .rev()
+            .find(|(entry_key, _)| entry_key == key)
+            .map(|(_, value)| *value)
+    }
+
+    fn insert(deps: &Impl<T>, key: &str, value: i32) {
+        (**deps)
+            .as_ref()
+            .0
+            .lock()
+            .unwrap()
+            .push((key.to_string(), value));
+    }
+}
+
+#[entrait::contract(Repository, targets(HashMapRepository, VecRepository = VecRepository::with(&[])))]
+mod repository_contract {
+    use super::*;
+
+    #[test]
+    fn get_returns_inserted_value(repository: &impl Repository) {
+        assert_eq!(None, repository.get("a"));
+        repository.insert("a", 1);
+        assert_eq!(Some(1), repository.get("a"));
+    }
+
+    #[test]
+    fn insert_replaces_value(repository: &impl Repository) {
+        repository.insert("a", 1);
+        repository.insert("a", 2);
+        assert_eq!(Some(2), repository.get("a"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn attributes_are_kept(repository: &impl Repository) {
+        repository.get("a").unwrap();
+    }
+
+    fn helpers_are_kept() -> i32 {
+        42
+    }
+}
+
+mod explicit_delegation_trait {
+    use super::*;
+
+    #[entrait::contract(
+        super::Repository,
+        delegate_by = super::DelegateRepository,
+        targets(VecRepository = VecRepository::with(&[("seeded", 1)]))
+    )]
+    mod contract {
+        use super::*;
+
+        #[test]
+        fn seeded_by_init_expression(repository: impl Repository) {
+            assert_eq!(Some(1), repository.get("seeded"));
+        }
+    }
+}
+
+#[cfg(feature = "fake")]
+mod fake_target {
+    use super::*;
+
+    #[entrait(
+        UserRepositoryImpl,
+        delegate_by = DelegateUserRepository,
+        fake(InMemoryUserRepository, key = u32, value = String)
+    )]
+    pub trait UserRepository {
+        fn get(&self, id: u32) -> Option<String>;
+        fn insert(&self, id: u32, name: String);
+    }
+
+    #[entrait::contract(UserRepository, targets(InMemoryUserRepository))]
+    mod contract {
+        use super::*;
+
+        #[test]
+        fn get_returns_inserted_user(users: &impl UserRepository) {
+            users.insert(1, "Alice".to_string());
+            assert_eq!(Some("Alice".to_string()), users.get(1));
+        }
+    }
+}
+
+#[cfg(all(feature = "fake", feature = "boxed-futures"))]
+mod async_contract {
+    use super::*;
+
+    #[entrait(
+        CounterImpl,
+        delegate_by = DelegateCounter,
+        box_future,
+        fake(InMemoryCounter, key = String, value = u32)
+    )]
+    pub trait Counter {
+        async fn get(&self, name: &str) -> Option<u32>;
+        async fn insert(&self, name: &str, count: u32);
+    }
+
+    #[entrait::contract(Counter, targets(InMemoryCounter))]
+    mod contract {
+        use super::*;
+
+        #[tokio::test]
+        async fn counts_are_stored(counter: &impl Counter) {
+            counter.insert("a", 1).await;
+            assert_eq!(Some(1), counter.get("a").await);
+        }
+    }
+}
+
+mod test_attr_paths {
+    use super::*;
+
+    #[entrait::contract(Repository, targets(HashMapRepository))]
+    mod contract {
+        use super::*;
+
+        #[core::prelude::v1::test]
+        fn test_attr_by_path(repository: &impl Repository) {
+            assert_eq!(None, repository.get("a"));
+        }
+    }
+}
+
+mod same_named_targets {
+    use super::*;
+
+    pub mod other {
+        use super::*;
+
+        /// A repository that forgets everything
+        #[derive(Default)]
+        pub struct HashMapRepository;
+
+        impl<T> RepositoryImpl<T> for HashMapRepository {
+            fn get(_: &Impl<T>, _: &str) -> Option<i32> {
+                None
+            }
+
+            fn insert(_: &Impl<T>, _: &str, _: i32) {}
+        }
+    }
+
+    #[entrait::contract(Repository, targets(HashMapRepository, OtherHashMapRepository))]
+    mod contract {
+        use super::*;
+        use other::HashMapRepository as OtherHashMapRepository;
+
+        #[test]
+        fn targets_are_distinguished_by_import_name(repository: &impl Repository) {
+            assert_eq!(None, repository.get("a"));
+        }
+    }
+}
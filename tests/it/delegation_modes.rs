@@ -81,3 +81,73 @@ mod borrow_dyn_use_boxed_futures {
         app.foo().await;
     }
 }
+
+mod fn_field {
+    use super::*;
+    use entrait::*;
+
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+
+    #[entrait(ClockFns, delegate_by = fn_field)]
+    pub trait Clock {
+        fn now(&self) -> u64;
+    }
+
+    #[entrait(IdsFns, delegate_by = fn_field)]
+    pub trait Ids {
+        fn next_id(&self, prefix: &str) -> String;
+    }
+
+    #[entrait(RegionFns, delegate_by = fn_field)]
+    pub trait Region {
+        fn region(&self, fallback: &str) -> &'static str;
+    }
+
+    #[entrait(Stamp)]
+    fn stamp(deps: &(impl Clock + Ids), name: &str) -> String {
+        format!("{}@{}", deps.next_id(name), deps.now())
+    }
+
+    struct App {
+        now: Box<dyn Fn() -> u64 + Send + Sync>,
+        next_id: Box<dyn Fn(&str) -> String + Send + Sync>,
+        region: Box<dyn Fn(&str) -> &'static str + Send + Sync>,
+    }
+
+    impl ClockFns for App {
+        fn now(&self) -> &dyn Fn() -> u64 {
+            &self.now
+        }
+    }
+
+    impl IdsFns for App {
+        fn next_id(&self) -> &dyn Fn(&str) -> String {
+            &self.next_id
+        }
+    }
+
+    impl RegionFns for App {
+        fn region(&self) -> &dyn Fn(&str) -> &'static str {
+            &self.region
+        }
+    }
+
+    #[test]
+    fn closures_implement_the_traits() {
+        let counter = Arc::new(AtomicU64::new(0));
+        let app = Impl::new(App {
+            now: Box::new(|| 1000),
+            next_id: Box::new(move |prefix| {
+                format!("{prefix}-{}", counter.fetch_add(1, Ordering::SeqCst))
+            }),
+            region: Box::new(|_| "eu"),
+        });
+
+        assert_is_sync(&app);
+
+        assert_eq!("user-0@1000", app.stamp("user"));
+        assert_eq!("user-1@1000", app.stamp("user"));
+        assert_eq!("eu", app.region("us"));
+    }
+}
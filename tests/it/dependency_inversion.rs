@@ -49,6 +49,36 @@ mod simple_static {
     }
 }
 
+mod api_trait {
+    use entrait::*;
+
+    #[entrait(FoobarImpl, delegate_by = DelegateFoobar)]
+    pub trait Foobar {
+        fn foo(&self) -> i32;
+    }
+
+    pub struct MyImpl;
+
+    #[entrait(MyImplApi)]
+    impl FoobarImpl for MyImpl {
+        fn foo(deps: &impl super::Baz) -> i32 {
+            deps.baz() + 1
+        }
+    }
+
+    impl DelegateFoobar<Self> for () {
+        type Target = MyImpl;
+    }
+
+    #[test]
+    fn api_trait_calls_the_impl_block() {
+        let app = Impl::new(());
+
+        assert_eq!(43, Foobar::foo(&app));
+        assert_eq!(43, MyImplApi::foo(&app));
+    }
+}
+
 mod simple_dyn {
     use entrait::*;
 
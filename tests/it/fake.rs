@@ -0,0 +1,181 @@
+use entrait::*;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct User {
+    name: String,
+}
+
+fn user(name: &str) -> User {
+    User {
+        name: name.to_string(),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DbError;
+
+mod self_delegation {
+    use super::*;
+
+    #[entrait(fake(InMemoryUsers, key = String, value = User))]
+    pub trait Users {
+        fn get_user(&self, name: &str) -> Result<Option<User>, DbError>;
+        fn insert_user(&self, name: &str, user: &User) -> Option<User>;
+        fn update_user(&self, name: &str, user: User) -> Result<bool, DbError>;
+        fn delete(&self, name: &str);
+    }
+
+    #[entrait(Rename)]
+    fn rename(deps: &impl Users, from: &str, to: &str) -> Result<bool, DbError> {
+        deps.update_user(from, user(to))
+    }
+
+    #[test]
+    fn fake_implements_the_trait() {
+        let app = Impl::new(InMemoryUsers::new());
+
+        assert_eq!(None, app.insert_user("a", &user("Alice")));
+        assert_eq!(Some(user("Alice")), app.insert_user("a", &user("Alicia")));
+        assert_eq!(Ok(Some(user("Alicia"))), app.get_user("a"));
+
+        assert_eq!(Ok(true), app.rename("a", "Ann"));
+        assert_eq!(Ok(false), app.rename("b", "Bob"));
+        assert_eq!(Ok(Some(user("Ann"))), app.get_user("a"));
+        assert_eq!(Ok(None), app.get_user("b"));
+
+        app.delete("a");
+        assert!(app.is_empty());
+    }
+
+    #[test]
+    fn seeded_fake() {
+        let fake: InMemoryUsers = [("a".to_string(), user("Alice"))].into_iter().collect();
+
+        assert_eq!(Ok(Some(user("Alice"))), fake.get_user("a"));
+        assert_eq!(vec![("a".to_string(), user("Alice"))], fake.entries());
+    }
+}
+
+mod custom_delegation {
+    use super::*;
+
+    #[entrait(
+        UserRepositoryImpl,
+        delegate_by = DelegateUserRepository,
+        fake(InMemoryUserRepository, key = u32, value = User)
+    )]
+    pub trait UserRepository {
+        fn get(&self, id: u32) -> Option<User>;
+        fn insert(&self, id: u32, user: User) -> bool;
+        fn delete_user(&self, id: &u32) -> Option<User>;
+    }
+
+    #[derive(Default)]
+    struct App {
+        users: InMemoryUserRepository,
+    }
+
+    impl DelegateUserRepository<Self> for App {
+        type Target = InMemoryUserRepository;
+    }
+
+    impl AsRef<InMemoryUserRepository> for App {
+        fn as_ref(&self) -> &InMemoryUserRepository {
+            &self.users
+        }
+    }
+
+    #[test]
+    fn fake_as_delegation_target() {
+        let app = Impl::new(App::default());
+
+        assert!(!app.insert(1, user("Alice")));
+        assert!(app.insert(1, user("Alice")));
+        assert_eq!(Some(user("Alice")), app.get(1));
+        assert_eq!(1, app.users.len());
+
+        assert_eq!(Some(user("Alice")), app.delete_user(&1));
+        assert_eq!(None, app.get(1));
+    }
+}
+
+mod ref_delegation {
+    use super::*;
+
+    #[entrait(UserRepositoryImpl, delegate_by = ref, fake(InMemoryUserRepository, key = u32, value = User))]
+    pub trait UserRepository {
+        fn get(&self, id: u32) -> Option<User>;
+        fn insert(&self, id: u32, user: User);
+    }
+
+    #[derive(Default)]
+    struct App {
+        users: InMemoryUserRepository,
+    }
+
+    impl AsRef<dyn UserRepositoryImpl<Self>> for App {
+        fn as_ref(&self) -> &dyn UserRepositoryImpl<Self> {
+            &self.users
+        }
+    }
+
+    #[test]
+    fn fake_as_dyn_implementation() {
+        let app = Impl::new(App::default());
+
+        app.insert(1, user("Alice"));
+        assert_eq!(Some(user("Alice")), app.get(1));
+    }
+}
+
+#[cfg(feature = "boxed-futures")]
+mod async_delegation {
+    use super::*;
+
+    #[entrait(
+        UserRepositoryImpl,
+        delegate_by = DelegateUserRepository,
+        box_future,
+        fake(InMemoryUserRepository, key = u32, value = User)
+    )]
+    pub trait UserRepository {
+        async fn get(&self, id: u32) -> Result<Option<User>, DbError>;
+        async fn insert(&self, id: u32, user: User) -> Result<(), DbError>;
+    }
+
+    #[entrait(CopyUser, box_future)]
+    async fn copy_user(deps: &impl UserRepository, from: u32, to: u32) -> Result<bool, DbError> {
+        match deps.get(from).await? {
+            Some(user) => {
+                deps.insert(to, user).await?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    struct App {
+        users: InMemoryUserRepository,
+    }
+
+    impl DelegateUserRepository<Self> for App {
+        type Target = InMemoryUserRepository;
+    }
+
+    impl AsRef<InMemoryUserRepository> for App {
+        fn as_ref(&self) -> &InMemoryUserRepository {
+            &self.users
+        }
+    }
+
+    #[tokio::test]
+    async fn async_fake() {
+        let app = Impl::new(App {
+            users: [(1, user("Alice"))].into_iter().collect(),
+        });
+
+        assert_eq!(Ok(true), app.copy_user(1, 2).await);
+        assert_eq!(Ok(false), app.copy_user(3, 4).await);
+        assert_eq!(Ok(Some(user("Alice"))), app.get(2).await);
+    }
+}
@@ -0,0 +1,51 @@
+use entrait::*;
+
+entrait::graph! {
+    #[entrait(pub FetchConfig, no_deps)]
+    fn fetch_config() -> u32 {
+        2
+    }
+
+    #[entrait(pub Scale)]
+    fn scale(deps: &impl FetchConfig, n: u32) -> u32 {
+        n * deps.fetch_config()
+    }
+
+    #[entrait(pub Reports)]
+    pub mod reports {
+        use super::*;
+
+        pub fn report<D: Scale + FetchConfig>(deps: &D, n: u32) -> String {
+            format!("{} at scale {}", deps.scale(n), deps.fetch_config())
+        }
+    }
+
+    mod nested {
+        use super::*;
+
+        #[entrait(pub Publish)]
+        pub fn publish(deps: &(impl Reports + Clock), n: u32) -> String {
+            format!("{}: {}", deps.now(), deps.report(n))
+        }
+    }
+
+    #[entrait]
+    pub trait Clock {
+        fn now(&self) -> u64;
+    }
+}
+
+struct App;
+
+impl Clock for App {
+    fn now(&self) -> u64 {
+        42
+    }
+}
+
+#[test]
+fn acyclic_graph_is_passed_through() {
+    let app = Impl::new(App);
+
+    assert_eq!("42: 6 at scale 2", nested::publish(&app, 3));
+}
@@ -0,0 +1,168 @@
+use entrait::*;
+
+mod native {
+    use super::*;
+
+    #[entrait(Numbers)]
+    fn numbers(_deps: &impl std::any::Any, count: u32) -> impl Iterator<Item = u32> {
+        0..count
+    }
+
+    #[entrait(Evens)]
+    fn evens(deps: &impl Numbers, count: u32) -> Vec<u32> {
+        deps.numbers(count).filter(|n| n % 2 == 0).collect()
+    }
+
+    #[test]
+    fn iterator_is_returned() {
+        assert_eq!(vec![0, 2, 4], Impl::new(()).evens(6));
+    }
+}
+
+mod boxed_iter {
+    use super::*;
+    use entrait::boxed::BoxIter;
+
+    struct Words(Vec<&'static str>);
+
+    #[entrait(AllWords, box_future)]
+    fn all_words(words: &Words) -> impl Iterator<Item = &'static str> + '_ {
+        words.0.iter().copied()
+    }
+
+    #[entrait(Numbers, box_future)]
+    fn numbers(_deps: &impl std::any::Any, count: u32) -> impl Iterator<Item = u32> {
+        0..count
+    }
+
+    #[test]
+    fn iterators_are_boxed() {
+        let app = Impl::new(Words(vec!["a", "b"]));
+
+        let words: BoxIter<'_, &str> = app.all_words();
+        assert_eq!(vec!["a", "b"], words.collect::<Vec<_>>());
+
+        let numbers: BoxIter<'_, u32> = app.numbers(3);
+        assert_eq!(vec![0, 1, 2], numbers.collect::<Vec<_>>());
+    }
+}
+
+#[cfg(feature = "stream")]
+mod boxed_stream {
+    use super::*;
+    use entrait::boxed::BoxStream;
+    use tokio_stream::{Stream, StreamExt};
+
+    #[entrait(Ticks, box_future)]
+    fn ticks(_deps: &impl std::any::Any, count: u32) -> impl Stream<Item = u32> {
+        tokio_stream::iter(0..count)
+    }
+
+    #[entrait(SumTicks)]
+    async fn sum_ticks(deps: &impl Ticks, count: u32) -> u32 {
+        let mut ticks = deps.ticks(count);
+        let mut sum = 0;
+        while let Some(tick) = ticks.next().await {
+            sum += tick;
+        }
+        sum
+    }
+
+    #[tokio::test]
+    async fn streams_are_boxed() {
+        let app = Impl::new(());
+
+        let ticks: BoxStream<'_, u32> = app.ticks(3);
+        assert_eq!(vec![0, 1, 2], ticks.collect::<Vec<_>>().await);
+        assert_eq!(6, app.sum_ticks(4).await);
+    }
+
+    #[tokio::test]
+    async fn streams_from_vec() {
+        let stream = BoxStream::from(vec![1, 2, 3]);
+        assert_eq!(vec![1, 2, 3], stream.collect::<Vec<_>>().await);
+    }
+}
+
+#[cfg(feature = "unimock")]
+mod unimock_mocks {
+    use super::*;
+    use unimock::*;
+
+    #[entrait(Numbers, box_future, mock_api = NumbersMock)]
+    fn numbers(_deps: &impl std::any::Any, count: u32) -> impl Iterator<Item = u32> {
+        0..count
+    }
+
+    #[entrait(Total)]
+    fn total(deps: &impl Numbers) -> u32 {
+        deps.numbers(100).sum()
+    }
+
+    #[test]
+    fn mocked_iterator_from_vec() {
+        let deps = Unimock::new(
+            NumbersMock
+                .each_call(matching!(100))
+                .answers(|_| vec![1, 2]),
+        );
+
+        assert_eq!(3, total(&deps));
+    }
+
+    #[cfg(feature = "stream")]
+    mod stream {
+        use super::*;
+        use tokio_stream::{Stream, StreamExt};
+
+        #[entrait(Events, box_future, mock_api = EventsMock)]
+        fn events(_deps: &impl std::any::Any) -> impl Stream<Item = String> {
+            tokio_stream::empty()
+        }
+
+        #[tokio::test]
+        async fn mocked_stream_from_vec() {
+            let events = vec!["created".to_string(), "deleted".to_string()];
+            let deps = Unimock::new(
+                EventsMock
+                    .each_call(matching!())
+                    .answers(move |_| events.clone()),
+            );
+
+            assert_eq!(
+                vec!["created".to_string(), "deleted".to_string()],
+                deps.events().collect::<Vec<_>>().await
+            );
+        }
+    }
+}
+
+#[cfg(feature = "nightly-tests")]
+mod associated_type {
+    use super::*;
+
+    #[entrait(Numbers, associated_future)]
+    fn numbers(_deps: &impl std::any::Any, count: u32) -> impl Iterator<Item = u32> + Send {
+        0..count
+    }
+
+    #[entrait(Total, associated_future)]
+    async fn total(deps: &impl Numbers, count: u32) -> u32 {
+        deps.numbers(count).sum()
+    }
+
+    struct Counted<'a> {
+        numbers: <Impl<()> as Numbers>::Ret__numbers<'a>,
+    }
+
+    #[tokio::test]
+    async fn iterator_is_an_associated_type() {
+        let app = Impl::new(());
+        let counted = Counted {
+            numbers: app.numbers(3),
+        };
+
+        assert_eq!(3, counted.numbers.sum::<u32>());
+        assert_eq!(6, app.total(4).await);
+    }
+}
@@ -0,0 +1,91 @@
+use entrait::*;
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+fn assert_is_send<T: Send>(_: &T) {}
+
+#[derive(Default, Debug, PartialEq)]
+struct Counted {
+    value: u32,
+}
+
+static CLONES: AtomicU32 = AtomicU32::new(0);
+
+impl Clone for Counted {
+    fn clone(&self) -> Self {
+        CLONES.fetch_add(1, Ordering::SeqCst);
+        Self { value: self.value }
+    }
+}
+
+#[entrait(Double, no_deps)]
+fn double(n: u32) -> u32 {
+    n * 2
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn shared_clones_do_not_clone_the_value() {
+    let app = Impl::shared(Counted { value: 1 });
+    let clones = (0..10).map(|_| app.clone()).collect::<Vec<_>>();
+
+    assert!(clones.iter().all(|clone| clone.value == 1));
+    assert_eq!(0, CLONES.load(Ordering::SeqCst));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn shared_impl_implements_entraited_traits() {
+    let app = Impl::shared(());
+
+    assert_eq!(4, app.clone().double(2));
+}
+
+#[test]
+fn is_copy_when_t_is_copy() {
+    let app = Impl::new(2);
+    let copy = app;
+
+    assert_eq!(app, copy);
+    assert_eq!(4, copy.double(*app));
+}
+
+#[test]
+fn map_and_into_inner() {
+    let app = Impl::new(Counted { value: 1 });
+
+    assert_eq!(3, app.map(|counted| counted.value + 2).into_inner());
+}
+
+#[test]
+fn forwards_std_traits() {
+    let app: Impl<Option<u32>> = Default::default();
+
+    assert_eq!("Impl(None)", format!("{app:?}"));
+    assert_eq!(Impl::new(None), app);
+    assert!(Impl::new(1) < Impl::new(2));
+}
+
+#[test]
+fn auto_traits_only_depend_on_t() {
+    // `Cell` is `Send` but not `Sync`
+    let app = Impl::new(Cell::new(1));
+
+    assert_is_send(&app);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serializes_the_wrapped_value() {
+    #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+    struct Config {
+        port: u16,
+    }
+
+    let json = serde_json::to_string(&Impl::new(Config { port: 80 })).unwrap();
+    assert_eq!(r#"{"port":80}"#, json);
+
+    let app: Impl<Config> = serde_json::from_str(&json).unwrap();
+    assert_eq!(Config { port: 80 }, *app);
+}
@@ -18,11 +18,48 @@
     feature(impl_trait_in_assoc_type)
 )]
 
+mod check_deps;
+mod contract;
 mod delegation_modes;
 mod dependency_inversion;
+mod graph;
+mod implementation;
 mod mockall;
 mod simple;
 
+#[cfg(feature = "actor")]
+mod actor;
+#[cfg(feature = "async-graphql")]
+mod async_graphql;
+#[cfg(feature = "axum")]
+mod axum;
+#[cfg(feature = "std")]
+mod blocking;
+#[cfg(feature = "cache")]
+mod cache;
+#[cfg(feature = "fake")]
+mod fake;
+#[cfg(feature = "alloc")]
+mod impl_return;
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(feature = "overrides")]
+mod overrides;
+#[cfg(feature = "remote")]
+mod remote;
+#[cfg(feature = "boxed-futures")]
+mod runtime;
+#[cfg(feature = "scoped")]
+mod scoped;
+#[cfg(feature = "nightly-tests")]
+mod static_async;
+#[cfg(feature = "std-deps")]
+mod std_deps;
+#[cfg(feature = "tonic")]
+mod tonic;
+#[cfg(feature = "tower")]
+mod tower;
+
 #[cfg(feature = "unimock")]
 mod unimock;
 
@@ -0,0 +1,195 @@
+use entrait::metrics::*;
+use entrait::*;
+
+use std::cell::RefCell;
+use std::sync::Once;
+use std::time::Duration;
+
+/// The recorder is global, but tests run on separate threads, so calls are stored per thread.
+struct TestRecorder;
+
+thread_local! {
+    static CALLS: RefCell<Vec<Call>> = const { RefCell::new(vec![]) };
+    static LATENCIES: RefCell<Vec<(Call, Duration)>> = const { RefCell::new(vec![]) };
+}
+
+impl Recorder for TestRecorder {
+    fn record_call(&self, call: &Call) {
+        CALLS.with(|calls| calls.borrow_mut().push(*call));
+    }
+
+    fn record_latency(&self, call: &Call, latency: Duration) {
+        LATENCIES.with(|latencies| latencies.borrow_mut().push((*call, latency)));
+    }
+}
+
+fn install_recorder() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| set_recorder(&TestRecorder).unwrap());
+}
+
+fn calls_of(trait_name: &str) -> Vec<&'static str> {
+    CALLS.with(|calls| {
+        calls
+            .borrow()
+            .iter()
+            .filter(|call| call.trait_name() == trait_name)
+            .map(|call| call.method())
+            .collect()
+    })
+}
+
+fn latencies_of(trait_name: &str) -> Vec<Duration> {
+    LATENCIES.with(|latencies| {
+        latencies
+            .borrow()
+            .iter()
+            .filter(|(call, _)| call.trait_name() == trait_name)
+            .map(|(_, latency)| *latency)
+            .collect()
+    })
+}
+
+mod sync {
+    use super::*;
+
+    #[entrait(Sleepy, no_deps, metrics)]
+    fn sleepy(millis: u64) -> u64 {
+        std::thread::sleep(Duration::from_millis(millis));
+        millis
+    }
+
+    #[test]
+    fn calls_and_latencies_are_recorded() {
+        install_recorder();
+        let app = Impl::new(());
+
+        assert_eq!(5, app.sleepy(5));
+        assert_eq!(0, app.sleepy(0));
+
+        assert_eq!(vec!["sleepy", "sleepy"], calls_of("Sleepy"));
+        let latencies = latencies_of("Sleepy");
+        assert_eq!(2, latencies.len());
+        assert!(latencies[0] >= Duration::from_millis(5));
+    }
+}
+
+mod disabled {
+    use super::*;
+
+    #[entrait(NotMeasured, no_deps, metrics = false)]
+    fn not_measured() {}
+
+    #[test]
+    fn metrics_can_be_disabled() {
+        install_recorder();
+        Impl::new(()).not_measured();
+
+        assert!(calls_of("NotMeasured").is_empty());
+    }
+}
+
+mod module {
+    use super::*;
+
+    #[entrait(pub Arithmetic, metrics)]
+    pub mod arithmetic {
+        pub fn add(deps: &impl std::any::Any, a: u32, b: u32) -> u32 {
+            a + b
+        }
+
+        pub fn sub(deps: &impl std::any::Any, a: u32, b: u32) -> u32 {
+            a - b
+        }
+    }
+
+    #[test]
+    fn module_functions_are_labelled_by_method() {
+        install_recorder();
+        let app = Impl::new(());
+
+        assert_eq!(3, app.add(1, 2));
+        assert_eq!(1, app.sub(2, 1));
+
+        assert_eq!(vec!["add", "sub"], calls_of("Arithmetic"));
+    }
+}
+
+mod delegated_trait {
+    use super::*;
+
+    #[entrait(delegate_by = ref, metrics)]
+    trait Greeter: 'static {
+        fn greet(&self, name: &str) -> String;
+    }
+
+    struct English;
+
+    impl Greeter for English {
+        fn greet(&self, name: &str) -> String {
+            format!("Hello, {name}")
+        }
+    }
+
+    struct App(English);
+
+    impl AsRef<dyn Greeter> for App {
+        fn as_ref(&self) -> &dyn Greeter {
+            &self.0
+        }
+    }
+
+    #[test]
+    fn trait_delegation_is_recorded() {
+        install_recorder();
+        let app = Impl::new(App(English));
+
+        assert_eq!("Hello, world", app.greet("world"));
+
+        assert_eq!(vec!["greet"], calls_of("Greeter"));
+        assert_eq!(1, latencies_of("Greeter").len());
+    }
+}
+
+#[cfg(feature = "boxed-futures")]
+mod asynchronous {
+    use super::*;
+
+    #[entrait(Waiting, no_deps, metrics, box_future)]
+    async fn waiting() {
+        tokio::task::yield_now().await;
+    }
+
+    #[tokio::test]
+    async fn latency_is_recorded_when_the_future_completes() {
+        install_recorder();
+        let app = Impl::new(());
+
+        let future = app.waiting();
+        assert!(latencies_of("Waiting").is_empty());
+
+        future.await;
+        assert_eq!(vec!["waiting"], calls_of("Waiting"));
+        assert_eq!(1, latencies_of("Waiting").len());
+    }
+}
+
+#[cfg(feature = "unimock")]
+mod mocks {
+    use super::*;
+    use unimock::*;
+
+    #[entrait(Mocked, no_deps, metrics, mock_api = MockedMock)]
+    fn mocked() -> u32 {
+        0
+    }
+
+    #[test]
+    fn mocks_are_not_instrumented() {
+        install_recorder();
+        let deps = Unimock::new(MockedMock.each_call(matching!()).returns(1_u32));
+
+        assert_eq!(1, deps.mocked());
+        assert!(calls_of("Mocked").is_empty());
+    }
+}
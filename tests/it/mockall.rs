@@ -37,3 +37,80 @@ mod entrait_for_trait {
         assert_eq!(42, mock.method());
     }
 }
+
+mod mock_api {
+    use entrait::*;
+
+    #[entrait(FetchName, mock_api = FetchNameMock, mockall)]
+    fn fetch_name(_deps: &(), id: u32) -> String {
+        format!("name{id}")
+    }
+
+    #[entrait(pub Greet, mockall = true, mock_api = GreetMock, export)]
+    fn greet(_deps: &(), name: &str) -> String {
+        format!("Hello, {name}")
+    }
+
+    #[test]
+    fn mock_is_named_by_mock_api() {
+        let mut fetch_name = FetchNameMock::new();
+        fetch_name.expect_fetch_name().return_const("mocked");
+
+        let mut greet = GreetMock::new();
+        greet.expect_greet().return_const("Hi");
+
+        assert_eq!("mocked", fetch_name.fetch_name(1));
+        assert_eq!("Hi", greet.greet("you"));
+    }
+}
+
+mod module {
+    use entrait::*;
+
+    #[entrait(Repository, mockall)]
+    mod repository {
+        pub fn get(_deps: &impl std::any::Any, id: u32) -> Option<String> {
+            None
+        }
+
+        pub fn put(_deps: &impl std::any::Any, id: u32, name: &str) -> bool {
+            false
+        }
+
+        pub async fn load(_deps: &impl std::any::Any, id: u32) -> String {
+            String::new()
+        }
+
+        pub async fn delete(_deps: &impl std::any::Any, id: u32) {}
+    }
+
+    #[entrait(pub Invalidate, mockall, mock_api = InvalidateMock)]
+    pub mod invalidation {
+        pub fn invalidate(_deps: &impl std::any::Any) {}
+    }
+
+    async fn rename(repository: &impl Repository, id: u32, name: &str) -> String {
+        repository.delete(id).await;
+        repository.put(id, name);
+        repository.load(id).await
+    }
+
+    #[tokio::test]
+    async fn mock_is_reexported_next_to_the_trait() {
+        let mut repository = MockRepository::new();
+        repository.expect_delete().return_const(());
+        repository
+            .expect_put()
+            .withf(|id, name| *id == 1 && name == "new")
+            .return_const(true);
+        repository
+            .expect_load()
+            .returning(|id| format!("loaded{id}"));
+
+        assert_eq!("loaded1", rename(&repository, 1, "new").await);
+
+        let mut invalidate = InvalidateMock::new();
+        invalidate.expect_invalidate().times(1).return_const(());
+        invalidate.invalidate();
+    }
+}
@@ -0,0 +1,172 @@
+mod entraited_trait {
+    use entrait::overrides::*;
+    use entrait::*;
+
+    #[entrait]
+    trait FetchPlanet {
+        fn fetch_planet(&self, id: u32) -> String;
+    }
+
+    #[entrait(SayHello)]
+    fn say_hello(deps: &impl FetchPlanet, id: u32) -> String {
+        format!("Hello {}!", deps.fetch_planet(id))
+    }
+
+    #[derive(Clone)]
+    struct App;
+
+    impl FetchPlanet for App {
+        fn fetch_planet(&self, _: u32) -> String {
+            "World".to_string()
+        }
+    }
+
+    struct FakePlanet;
+
+    impl FetchPlanet for FakePlanet {
+        fn fetch_planet(&self, id: u32) -> String {
+            format!("Mars{id}")
+        }
+    }
+
+    #[test]
+    fn no_overrides_calls_real_impl() {
+        let app = Overrides::new(Impl::new(App));
+        assert_eq!("Hello World!", app.say_hello(1));
+    }
+
+    #[test]
+    fn override_leaf_trait() {
+        let app = Impl::new(App).override_with::<dyn FetchPlanet>(Box::new(FakePlanet));
+        assert_eq!("Hello Mars1!", app.say_hello(1));
+    }
+
+    #[test]
+    fn overrides_are_shared_between_clones() {
+        let app = Impl::new(App).override_with::<dyn FetchPlanet>(Box::new(FakePlanet));
+        let clone = app.clone();
+        assert_eq!("Hello Mars2!", clone.say_hello(2));
+        assert_eq!("Hello World!", clone.into_impl().say_hello(2));
+    }
+}
+
+mod mockable_fn {
+    use entrait::overrides::*;
+    use entrait::*;
+
+    #[entrait(Foo, mockall)]
+    fn foo(deps: &impl Bar) -> i32 {
+        deps.bar() + 1
+    }
+
+    #[entrait(Bar, mockall)]
+    fn bar<D>(_: &D) -> i32 {
+        1
+    }
+
+    struct BarStub;
+
+    impl Bar for BarStub {
+        fn bar(&self) -> i32 {
+            41
+        }
+    }
+
+    struct FooStub;
+
+    impl Foo for FooStub {
+        fn foo(&self) -> i32 {
+            1337
+        }
+    }
+
+    #[test]
+    fn override_deep_fn() {
+        let app = Impl::new(()).override_with::<dyn Bar>(Box::new(BarStub));
+        assert_eq!(42, app.foo());
+    }
+
+    #[test]
+    fn override_top_fn() {
+        let app = Impl::new(())
+            .override_with::<dyn Bar>(Box::new(BarStub))
+            .override_with::<dyn Foo>(Box::new(FooStub));
+        assert_eq!(1337, app.foo());
+        assert_eq!(41, app.bar());
+    }
+
+    #[test]
+    fn last_override_wins() {
+        struct OtherBarStub;
+
+        impl Bar for OtherBarStub {
+            fn bar(&self) -> i32 {
+                2
+            }
+        }
+
+        let app = Impl::new(())
+            .override_with::<dyn Bar>(Box::new(BarStub))
+            .override_with::<dyn Bar>(Box::new(OtherBarStub));
+        assert_eq!(3, app.foo());
+    }
+}
+
+mod not_overridable {
+    use entrait::overrides::*;
+    use entrait::*;
+
+    // Generic traits cannot be made into trait objects,
+    // but Overrides<T> should still implement them by forwarding to Impl<T>:
+    #[entrait]
+    trait Generic<T> {
+        fn generic(&self) -> T;
+    }
+
+    impl Generic<i32> for () {
+        fn generic(&self) -> i32 {
+            42
+        }
+    }
+
+    #[entrait(TakesGeneric)]
+    fn takes_generic(deps: &impl Generic<i32>) -> i32 {
+        deps.generic()
+    }
+
+    #[test]
+    fn forwards_to_impl() {
+        assert_eq!(42, Overrides::new(Impl::new(())).takes_generic());
+    }
+}
+
+#[cfg(feature = "boxed-futures")]
+mod boxed_futures {
+    use entrait::overrides::*;
+    use entrait::*;
+
+    #[entrait(Foo, box_future, mockall)]
+    async fn foo(deps: &impl Bar) -> i32 {
+        deps.bar().await + 1
+    }
+
+    #[entrait(Bar, box_future, mockall)]
+    async fn bar<D>(_: &D) -> i32 {
+        1
+    }
+
+    struct BarStub;
+
+    #[async_trait::async_trait]
+    impl Bar for BarStub {
+        async fn bar(&self) -> i32 {
+            41
+        }
+    }
+
+    #[tokio::test]
+    async fn override_async() {
+        let app = Impl::new(()).override_with::<dyn Bar>(Box::new(BarStub));
+        assert_eq!(42, app.foo().await);
+    }
+}
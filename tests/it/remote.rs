@@ -0,0 +1,143 @@
+use entrait::remote::*;
+use entrait::*;
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+struct Planet {
+    name: String,
+    moons: u32,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+enum PlanetError {
+    NotFound,
+    Remote(String),
+}
+
+impl From<RemoteError> for PlanetError {
+    fn from(error: RemoteError) -> Self {
+        Self::Remote(error.to_string())
+    }
+}
+
+#[entrait(remote = PlanetsRemote, box_future)]
+trait Planets {
+    async fn get_planet(&self, name: String) -> Result<Planet, PlanetError>;
+    async fn count_moons(&self, first: String, second: String) -> Result<u32, PlanetError>;
+    async fn ping(&self) -> Result<(), PlanetError>;
+}
+
+#[entrait(CountAllMoons, box_future)]
+async fn count_all_moons(deps: &impl Planets) -> Result<u32, PlanetError> {
+    deps.count_moons("Earth".to_string(), "Mars".to_string())
+        .await
+}
+
+struct App;
+
+#[async_trait::async_trait]
+impl Planets for App {
+    async fn get_planet(&self, name: String) -> Result<Planet, PlanetError> {
+        match name.as_str() {
+            "Earth" => Ok(Planet { name, moons: 1 }),
+            "Mars" => Ok(Planet { name, moons: 2 }),
+            _ => Err(PlanetError::NotFound),
+        }
+    }
+
+    async fn count_moons(&self, first: String, second: String) -> Result<u32, PlanetError> {
+        let first = self.get_planet(first).await.map(|p| p.moons).unwrap_or(0);
+        let second = self.get_planet(second).await.map(|p| p.moons).unwrap_or(0);
+        Ok(first + second)
+    }
+
+    async fn ping(&self) -> Result<(), PlanetError> {
+        Ok(())
+    }
+}
+
+fn in_memory_client() -> PlanetsRemote<InMemoryTransport<PlanetsRemoteDispatcher<Impl<App>>>> {
+    PlanetsRemote(InMemoryTransport(PlanetsRemoteDispatcher(Impl::new(App))))
+}
+
+#[tokio::test]
+async fn client_calls_dispatcher_in_memory() {
+    let client = in_memory_client();
+
+    assert_eq!(
+        Ok(Planet {
+            name: "Mars".to_string(),
+            moons: 2
+        }),
+        client.get_planet("Mars".to_string()).await
+    );
+    assert_eq!(
+        Ok(3),
+        client.count_moons("Earth".into(), "Mars".into()).await
+    );
+    assert_eq!(Ok(()), client.ping().await);
+}
+
+#[tokio::test]
+async fn application_errors_are_transferred() {
+    assert_eq!(
+        Err(PlanetError::NotFound),
+        in_memory_client().get_planet("Pluto".to_string()).await
+    );
+}
+
+#[tokio::test]
+async fn client_is_usable_as_deps() {
+    assert_eq!(Ok(3), count_all_moons(&in_memory_client()).await);
+}
+
+#[tokio::test]
+async fn request_can_be_dispatched_directly() {
+    let request = PlanetsRemoteRequest::CountMoons {
+        first: "Mars".to_string(),
+        second: "Mars".to_string(),
+    };
+
+    match request.dispatch(&Impl::new(App)).await {
+        PlanetsRemoteResponse::CountMoons(moons) => assert_eq!(Ok(4), moons),
+        _ => panic!("Unexpected response"),
+    }
+}
+
+struct FailingTransport;
+
+impl Transport for FailingTransport {
+    fn send(&self, _: Vec<u8>) -> BoxFuture<'_, Result<Vec<u8>, RemoteError>> {
+        Box::pin(async { Err(RemoteError::Transport("connection refused".into())) })
+    }
+}
+
+#[tokio::test]
+async fn transport_errors_are_converted() {
+    assert_eq!(
+        Err(PlanetError::Remote(
+            "transport error: connection refused".to_string()
+        )),
+        PlanetsRemote(FailingTransport)
+            .get_planet("Earth".to_string())
+            .await
+    );
+}
+
+#[tokio::test]
+async fn decode_errors_are_converted() {
+    struct GarbageTransport;
+
+    impl Transport for GarbageTransport {
+        fn send(&self, _: Vec<u8>) -> BoxFuture<'_, Result<Vec<u8>, RemoteError>> {
+            Box::pin(async { Ok(b"garbage".to_vec()) })
+        }
+    }
+
+    let result = PlanetsRemote(GarbageTransport)
+        .count_moons("Earth".into(), "Mars".into())
+        .await;
+    assert!(
+        matches!(&result, Err(PlanetError::Remote(message)) if message.starts_with("failed to decode message")),
+        "{result:?}"
+    );
+}
@@ -0,0 +1,202 @@
+use entrait::runtime::*;
+use entrait::*;
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq)]
+enum CallError {
+    Failed(u32),
+    TimedOut,
+}
+
+impl From<TimedOut> for CallError {
+    fn from(_: TimedOut) -> Self {
+        Self::TimedOut
+    }
+}
+
+/// A runtime that completes sleeps immediately, recording their durations
+#[derive(Default)]
+struct RecordingRuntime {
+    sleeps: Mutex<Vec<Duration>>,
+}
+
+impl Runtime for RecordingRuntime {
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        self.sleeps.lock().unwrap().push(duration);
+        Box::pin(async {})
+    }
+}
+
+#[derive(Default)]
+struct App {
+    runtime: RecordingRuntime,
+}
+
+impl HasRuntime for App {
+    fn entrait_runtime(&self) -> &dyn Runtime {
+        &self.runtime
+    }
+}
+
+impl App {
+    fn sleeps(&self) -> Vec<Duration> {
+        self.runtime.sleeps.lock().unwrap().clone()
+    }
+}
+
+mod exponential {
+    use super::*;
+
+    static ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+
+    #[entrait(FlakyCall, no_deps, box_future, retry(max = 3))]
+    async fn flaky_call(succeed_at: u32) -> Result<u32, CallError> {
+        let attempt = ATTEMPTS.fetch_add(1, Ordering::SeqCst);
+        if attempt >= succeed_at {
+            Ok(attempt)
+        } else {
+            Err(CallError::Failed(attempt))
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_with_exponential_backoff() {
+        let app = Impl::new(App::default());
+
+        assert_eq!(Ok(2), app.flaky_call(2).await);
+        assert_eq!(
+            vec![Duration::from_millis(100), Duration::from_millis(200)],
+            app.sleeps()
+        );
+    }
+}
+
+mod exhausted {
+    use super::*;
+
+    static ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+
+    #[entrait(
+        FailingCall,
+        no_deps,
+        box_future,
+        retry(max = 2, backoff = "fixed", delay_ms = 5)
+    )]
+    async fn failing_call(name: String) -> Result<(), CallError> {
+        Err(CallError::Failed(ATTEMPTS.fetch_add(1, Ordering::SeqCst)))
+    }
+
+    #[tokio::test]
+    async fn returns_last_error_when_retries_are_exhausted() {
+        let app = Impl::new(App::default());
+
+        assert_eq!(
+            Err(CallError::Failed(2)),
+            app.failing_call("x".to_string()).await
+        );
+        assert_eq!(vec![Duration::from_millis(5); 2], app.sleeps());
+    }
+}
+
+mod timeout {
+    use super::*;
+
+    #[entrait(HangingCall, no_deps, box_future, timeout_ms = 50)]
+    async fn hanging_call() -> Result<(), CallError> {
+        std::future::pending::<()>().await;
+        Ok(())
+    }
+
+    #[entrait(QuickCall, no_deps, box_future, timeout_ms = 50)]
+    async fn quick_call() -> Result<u32, CallError> {
+        Ok(42)
+    }
+
+    #[tokio::test]
+    async fn times_out() {
+        let app = Impl::new(App::default());
+
+        assert_eq!(Err(CallError::TimedOut), app.hanging_call().await);
+        assert_eq!(Ok(42), app.quick_call().await);
+        assert_eq!(vec![Duration::from_millis(50); 2], app.sleeps());
+    }
+}
+
+mod retry_and_timeout {
+    use super::*;
+
+    static ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+
+    #[entrait(pub Outbound, box_future, retry(max = 1, backoff = "none"), timeout_ms = 10)]
+    pub mod outbound {
+        use super::*;
+
+        pub async fn call(deps: &impl std::any::Any) -> Result<u32, CallError> {
+            if ATTEMPTS.fetch_add(1, Ordering::SeqCst) == 0 {
+                std::future::pending::<()>().await;
+            }
+            Ok(1)
+        }
+    }
+
+    #[tokio::test]
+    async fn timeout_applies_to_each_attempt() {
+        let app = Impl::new(App::default());
+
+        assert_eq!(Ok(1), app.call().await);
+        assert_eq!(vec![Duration::from_millis(10); 2], app.sleeps());
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod tokio_runtime {
+    use super::*;
+
+    #[entrait(SlowCall, no_deps, box_future, timeout_ms = 10)]
+    async fn slow_call(millis: u64) -> Result<(), CallError> {
+        tokio::time::sleep(Duration::from_millis(millis)).await;
+        Ok(())
+    }
+
+    struct TokioApp;
+
+    impl HasRuntime for TokioApp {
+        fn entrait_runtime(&self) -> &dyn Runtime {
+            &Tokio
+        }
+    }
+
+    #[tokio::test]
+    async fn tokio_runtime_times_out() {
+        let app = Impl::new(TokioApp);
+
+        assert_eq!(Ok(()), app.slow_call(0).await);
+        assert_eq!(Err(CallError::TimedOut), app.slow_call(1000).await);
+    }
+}
+
+#[cfg(feature = "unimock")]
+mod mocks {
+    use super::*;
+    use unimock::*;
+
+    #[entrait(MockedCall, no_deps, box_future, retry(max = 5), mock_api = MockedCallMock)]
+    async fn mocked_call() -> Result<(), CallError> {
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mocks_are_not_retried() {
+        let deps = Unimock::new(
+            MockedCallMock
+                .each_call(matching!())
+                .returns(Err(CallError::Failed(0)))
+                .once(),
+        );
+
+        assert_eq!(Err(CallError::Failed(0)), deps.mocked_call().await);
+    }
+}
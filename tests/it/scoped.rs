@@ -0,0 +1,137 @@
+mod context_accessor {
+    use entrait::scoped::*;
+    use entrait::*;
+
+    struct RequestCtx {
+        user_id: u32,
+    }
+
+    #[entrait(CurrentUserId)]
+    fn current_user_id(deps: &impl ScopeContext<RequestCtx>) -> u32 {
+        deps.scope_context().user_id
+    }
+
+    #[entrait]
+    trait FetchUserName {
+        fn fetch_user_name(&self, id: u32) -> String;
+    }
+
+    #[entrait(Greet)]
+    fn greet(deps: &(impl CurrentUserId + FetchUserName)) -> String {
+        format!("Hello {}!", deps.fetch_user_name(deps.current_user_id()))
+    }
+
+    #[derive(Clone)]
+    struct App;
+
+    impl FetchUserName for App {
+        fn fetch_user_name(&self, id: u32) -> String {
+            format!("user{id}")
+        }
+    }
+
+    #[test]
+    fn context_is_available_through_accessor_trait() {
+        let app = Impl::new(App);
+        assert_eq!(
+            "Hello user1!",
+            app.clone().scoped(RequestCtx { user_id: 1 }).greet()
+        );
+        assert_eq!(
+            "Hello user2!",
+            greet(&app.scoped(RequestCtx { user_id: 2 }))
+        );
+    }
+
+    #[test]
+    fn into_parts() {
+        let (_app, ctx) = Scoped::new(Impl::new(App), RequestCtx { user_id: 3 }).into_parts();
+        assert_eq!(3, ctx.user_id);
+    }
+}
+
+mod mockable_fn {
+    use entrait::scoped::*;
+    use entrait::*;
+
+    #[entrait(Foo, mockall)]
+    fn foo(deps: &impl Bar) -> i32 {
+        deps.bar() + 1
+    }
+
+    #[entrait(Bar, mockall)]
+    fn bar(deps: &impl ScopeContext<i32>) -> i32 {
+        *deps.scope_context()
+    }
+
+    #[test]
+    fn mockable_traits_are_implemented_for_scoped() {
+        assert_eq!(42, Impl::new(()).scoped(41).foo());
+    }
+}
+
+#[cfg(feature = "boxed-futures")]
+mod boxed_futures {
+    use entrait::scoped::*;
+    use entrait::*;
+
+    #[entrait(Foo, box_future, mockall)]
+    async fn foo(deps: &impl Bar) -> String {
+        format!("{}!", deps.bar().await)
+    }
+
+    #[entrait(Bar, box_future, mockall)]
+    async fn bar(deps: &impl ScopeContext<&'static str>) -> String {
+        deps.scope_context().to_string()
+    }
+
+    #[tokio::test]
+    async fn scoped_async() {
+        assert_eq!("hi!", Impl::new(()).scoped("hi").foo().await);
+    }
+}
+
+#[cfg(feature = "unimock")]
+mod unimock {
+    use entrait::*;
+    use unimock::*;
+
+    #[entrait(CurrentUserId, mock_api = CurrentUserIdMock)]
+    fn current_user_id(deps: &impl entrait::scoped::ScopeContext<u32>) -> u32 {
+        *deps.scope_context()
+    }
+
+    #[entrait(IsAdmin)]
+    fn is_admin(deps: &impl CurrentUserId) -> bool {
+        deps.current_user_id() == 0
+    }
+
+    #[test]
+    fn accessor_can_be_mocked() {
+        assert!(is_admin(&Unimock::new(
+            CurrentUserIdMock.each_call(matching!()).returns(0_u32)
+        )));
+    }
+
+    mod unrelated_scope_context {
+        use entrait::*;
+        use unimock::*;
+
+        // Not the request-scoped context of entrait
+        #[entrait(ScopeContext, no_deps, mock_api = ScopeContextMock)]
+        fn scope_context() -> u32 {
+            42
+        }
+
+        #[entrait(Answer, mock_api = AnswerMock)]
+        fn answer(deps: &impl ScopeContext) -> u32 {
+            deps.scope_context()
+        }
+
+        #[test]
+        fn can_be_unmocked() {
+            let deps = Unimock::new_partial(ScopeContextMock.each_call(matching!()).returns(7_u32));
+            assert_eq!(7, deps.answer());
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use entrait::static_async::async_trait;
+
+use std::fmt::Display;
+
+#[async_trait]
+pub trait Store {
+    async fn get(&self, key: &str) -> Option<String>;
+
+    async fn get_or_default(&self, key: &str) -> String {
+        self.get(key).await.unwrap_or_default()
+    }
+
+    async fn describe<T: Display + Send + Sync>(&self, value: &T) -> String;
+
+    async fn put<V>(&self, key: &str, value: V) -> bool
+    where
+        V: Into<String> + Send;
+
+    async fn into_len(self) -> usize
+    where
+        Self: Sized;
+}
+
+struct MemoryStore;
+
+#[async_trait]
+impl Store for MemoryStore {
+    async fn get(&self, key: &str) -> Option<String> {
+        (key == "known").then(|| "value".to_string())
+    }
+
+    async fn describe<T: Display + Send + Sync>(&self, value: &T) -> String {
+        format!("<{value}>")
+    }
+
+    async fn put<V>(&self, key: &str, value: V) -> bool
+    where
+        V: Into<String> + Send,
+    {
+        !key.is_empty() && !value.into().is_empty()
+    }
+
+    async fn into_len(self) -> usize
+    where
+        Self: Sized,
+    {
+        0
+    }
+}
+
+struct Overriding;
+
+#[async_trait]
+impl Store for Overriding {
+    async fn get(&self, _key: &str) -> Option<String> {
+        None
+    }
+
+    #[provided]
+    async fn get_or_default(&self, key: &str) -> String {
+        format!("default:{key}")
+    }
+
+    async fn describe<T: Display + Send + Sync>(&self, value: &T) -> String {
+        value.to_string()
+    }
+
+    async fn put<V>(&self, _key: &str, _value: V) -> bool
+    where
+        V: Into<String> + Send,
+    {
+        false
+    }
+
+    async fn into_len(self) -> usize {
+        1
+    }
+}
+
+fn assert_send<T: Send>(value: T) -> T {
+    value
+}
+
+#[tokio::test]
+async fn default_bodies_and_generic_methods() {
+    assert_eq!("value", MemoryStore.get_or_default("known").await);
+    assert_eq!("", MemoryStore.get_or_default("unknown").await);
+    assert_eq!("default:key", Overriding.get_or_default("key").await);
+
+    assert_eq!("<42>", assert_send(MemoryStore.describe(&42)).await);
+    assert!(MemoryStore.put("key", "value").await);
+    assert!(!Overriding.put("key", String::from("value")).await);
+    assert_eq!(0, MemoryStore.into_len().await);
+    assert_eq!(1, Overriding.into_len().await);
+}
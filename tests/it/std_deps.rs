@@ -0,0 +1,131 @@
+use entrait::std_deps::*;
+use entrait::*;
+
+use std::time::{Duration, SystemTime};
+
+#[entrait(Expiry)]
+fn expiry(deps: &impl Clock, ttl: Duration) -> SystemTime {
+    deps.now() + ttl
+}
+
+#[entrait(NewSession)]
+fn new_session(deps: &(impl Uuids + Random + Env)) -> String {
+    let prefix = deps.var("SESSION_PREFIX").unwrap_or_default();
+    format!("{prefix}{}:{}", deps.new_uuid(), deps.next_u64() % 100)
+}
+
+struct App {
+    clock: Box<dyn Clock + Send + Sync>,
+    random: Box<dyn Random + Send + Sync>,
+    uuids: Box<dyn Uuids + Send + Sync>,
+    env: Box<dyn Env + Send + Sync>,
+}
+
+impl AsRef<dyn Clock> for App {
+    fn as_ref(&self) -> &dyn Clock {
+        self.clock.as_ref()
+    }
+}
+
+impl AsRef<dyn Random> for App {
+    fn as_ref(&self) -> &dyn Random {
+        self.random.as_ref()
+    }
+}
+
+impl AsRef<dyn Uuids> for App {
+    fn as_ref(&self) -> &dyn Uuids {
+        self.uuids.as_ref()
+    }
+}
+
+impl AsRef<dyn Env> for App {
+    fn as_ref(&self) -> &dyn Env {
+        self.env.as_ref()
+    }
+}
+
+fn fake_app() -> Impl<App> {
+    Impl::new(App {
+        clock: Box::new(FakeClock::new(SystemTime::UNIX_EPOCH)),
+        random: Box::new(SeededRandom::new(7)),
+        uuids: Box::new(SequentialUuids::new()),
+        env: Box::new(FakeEnv::new().with("SESSION_PREFIX", "s-")),
+    })
+}
+
+#[test]
+fn fakes_are_deterministic() {
+    let first = fake_app();
+    let second = fake_app();
+
+    assert_eq!(
+        SystemTime::UNIX_EPOCH + Duration::from_secs(10),
+        first.expiry(Duration::from_secs(10))
+    );
+
+    let session = first.new_session();
+    assert!(session.starts_with("s-00000000-0000-0000-0000-000000000001:"));
+    assert_eq!(session, second.new_session());
+    assert_ne!(session, first.new_session());
+}
+
+#[test]
+fn fake_clock_moves_when_told() {
+    let clock = FakeClock::new(SystemTime::UNIX_EPOCH);
+    clock.advance(Duration::from_secs(5));
+    assert_eq!(SystemTime::UNIX_EPOCH + Duration::from_secs(5), clock.now());
+
+    clock.set(SystemTime::UNIX_EPOCH);
+    assert_eq!(SystemTime::UNIX_EPOCH, clock.now());
+}
+
+#[test]
+fn fake_env_from_iter() {
+    let env: FakeEnv = [("A", "1"), ("B", "2")].into_iter().collect();
+    assert_eq!(Some("2".to_string()), env.var("B"));
+    assert_eq!(None, env.var("C"));
+}
+
+#[test]
+fn real_implementations() {
+    let app = Impl::new(App {
+        clock: Box::new(SystemClock),
+        random: Box::new(StdRandom),
+        uuids: Box::new(RandomUuids),
+        env: Box::new(ProcessEnv),
+    });
+
+    assert!(app.expiry(Duration::from_secs(60)) > SystemTime::now());
+    assert_ne!(app.new_uuid(), app.new_uuid());
+    assert_eq!(Some(uuid::Version::Random), app.new_uuid().get_version());
+    assert_ne!(
+        (0..4).map(|_| app.next_u64()).collect::<Vec<_>>(),
+        (0..4).map(|_| app.next_u64()).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        std::env::var("PATH").ok(),
+        <Impl<App> as Env>::var(&app, "PATH")
+    );
+}
+
+#[cfg(feature = "unimock")]
+mod unimock {
+    use super::*;
+    use ::unimock::*;
+
+    #[test]
+    fn mock_std_deps() {
+        let deps = Unimock::new((
+            ClockMock::now
+                .each_call(matching!())
+                .returns(SystemTime::UNIX_EPOCH),
+            EnvMock::var
+                .each_call(matching!("TTL"))
+                .returns(Some("3".to_string())),
+        ));
+
+        assert_eq!(SystemTime::UNIX_EPOCH, deps.now());
+        assert_eq!(Some("3".to_string()), deps.var("TTL"));
+    }
+}
@@ -0,0 +1,128 @@
+use entrait::*;
+use tower::ServiceExt;
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+struct Planet {
+    name: String,
+    moons: u32,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+enum PlanetError {
+    NotFound,
+    Transport(String),
+}
+
+impl From<tonic::Status> for PlanetError {
+    fn from(status: tonic::Status) -> Self {
+        Self::Transport(status.message().to_string())
+    }
+}
+
+#[entrait(grpc = PlanetsGrpc, box_future)]
+trait Planets {
+    async fn get_planet(&self, name: String) -> Result<Planet, PlanetError>;
+    async fn count_moons(&self, first: String, second: String) -> Result<u32, PlanetError>;
+}
+
+#[entrait(CountAllMoons, box_future)]
+async fn count_all_moons(deps: &impl Planets) -> Result<u32, PlanetError> {
+    deps.count_moons("Earth".to_string(), "Mars".to_string())
+        .await
+}
+
+#[derive(Clone)]
+struct App;
+
+#[async_trait::async_trait]
+impl Planets for App {
+    async fn get_planet(&self, name: String) -> Result<Planet, PlanetError> {
+        match name.as_str() {
+            "Earth" => Ok(Planet { name, moons: 1 }),
+            "Mars" => Ok(Planet { name, moons: 2 }),
+            _ => Err(PlanetError::NotFound),
+        }
+    }
+
+    async fn count_moons(&self, first: String, second: String) -> Result<u32, PlanetError> {
+        let first = self.get_planet(first).await.map(|p| p.moons).unwrap_or(0);
+        let second = self.get_planet(second).await.map(|p| p.moons).unwrap_or(0);
+        Ok(first + second)
+    }
+}
+
+fn in_process_client() -> PlanetsGrpcClient<PlanetsGrpcServer<Impl<App>>> {
+    PlanetsGrpcClient(PlanetsGrpcServer(Impl::new(App)))
+}
+
+#[tokio::test]
+async fn client_calls_server_in_process() {
+    let client = in_process_client();
+
+    assert_eq!(
+        Ok(Planet {
+            name: "Mars".to_string(),
+            moons: 2
+        }),
+        client.get_planet("Mars".to_string()).await
+    );
+    assert_eq!(
+        Ok(3),
+        client.count_moons("Earth".into(), "Mars".into()).await
+    );
+}
+
+#[tokio::test]
+async fn application_errors_are_transferred() {
+    assert_eq!(
+        Err(PlanetError::NotFound),
+        in_process_client().get_planet("Pluto".to_string()).await
+    );
+}
+
+#[tokio::test]
+async fn client_is_usable_as_deps() {
+    assert_eq!(Ok(3), count_all_moons(&in_process_client()).await);
+}
+
+#[tokio::test]
+async fn transport_errors_are_converted() {
+    #[entrait(grpc = OtherGrpc, box_future)]
+    trait Other {
+        async fn other(&self) -> Result<(), PlanetError>;
+    }
+
+    // Calling a server not serving the `Other` service:
+    let client = OtherGrpcClient(PlanetsGrpcServer(Impl::new(App)));
+
+    match client.other().await {
+        Err(PlanetError::Transport(_)) => {}
+        result => panic!("Unexpected result: {result:?}"),
+    }
+}
+
+#[tokio::test]
+async fn server_responds_unimplemented_to_unknown_path() {
+    use tonic::codegen::http;
+
+    let response = PlanetsGrpcServer(Impl::new(App))
+        .oneshot(
+            http::Request::post("/Planets/unknown")
+                .body(tonic::body::empty_body())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!("12", response.headers()["grpc-status"]);
+}
+
+#[test]
+fn server_is_named_after_trait() {
+    use tonic::server::NamedService;
+
+    assert_eq!(
+        "Planets",
+        <PlanetsGrpcServer<Impl<App>> as NamedService>::NAME
+    );
+}
@@ -0,0 +1,81 @@
+use entrait::*;
+use tower::{Service, ServiceExt};
+
+#[derive(Debug, PartialEq)]
+struct InvalidId;
+
+#[entrait(FetchPlanet, box_future)]
+async fn fetch_planet(_deps: &impl std::any::Any, id: u32) -> Result<String, InvalidId> {
+    match id {
+        3 => Ok("Earth".to_string()),
+        _ => Err(InvalidId),
+    }
+}
+
+#[entrait(SayHello, box_future, tower_service = SayHelloService)]
+async fn say_hello(deps: &impl FetchPlanet, id: u32) -> Result<String, InvalidId> {
+    Ok(format!("Hello {}!", deps.fetch_planet(id).await?))
+}
+
+#[entrait(Double, no_deps, box_future)]
+async fn double(n: u32) -> u32 {
+    n * 2
+}
+
+#[entrait(Quadruple, box_future, tower_service = QuadrupleService)]
+async fn quadruple(deps: &impl Double, n: u32) -> u32 {
+    deps.double(deps.double(n).await).await
+}
+
+#[tokio::test]
+async fn result_becomes_response_and_error() {
+    let service = SayHelloService(Impl::new(()));
+
+    assert_eq!(
+        Ok("Hello Earth!".to_string()),
+        service.clone().oneshot(3).await
+    );
+    assert_eq!(Err(InvalidId), service.oneshot(4).await);
+}
+
+#[tokio::test]
+async fn infallible_service() {
+    let mut service = QuadrupleService(Impl::new(()));
+
+    let response: Result<u32, std::convert::Infallible> =
+        service.ready().await.unwrap().call(2).await;
+    assert_eq!(Ok(8), response);
+}
+
+#[tokio::test]
+async fn with_middleware() {
+    let service = tower::ServiceBuilder::new()
+        .map_request(|n: u32| n + 1)
+        .service(QuadrupleService(Impl::new(())));
+
+    assert_eq!(Ok(12), service.oneshot(2).await);
+}
+
+#[cfg(feature = "unimock")]
+#[tokio::test]
+async fn mocked_deps() {
+    use unimock::*;
+
+    #[entrait(Greet, box_future, mock_api = GreetMock, tower_service = GreetService)]
+    async fn greet(_: &impl FetchPlanet, name: String) -> String {
+        format!("Hello {name}!")
+    }
+
+    let deps = Unimock::new(
+        GreetMock
+            .each_call(matching!(_))
+            .returns("Mocked".to_string()),
+    );
+
+    assert_eq!(
+        Ok("Mocked".to_string()),
+        GreetService(deps.clone())
+            .oneshot("World".to_string())
+            .await
+    );
+}
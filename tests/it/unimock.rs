@@ -239,7 +239,7 @@ mod tokio_spawn {
 
     #[tokio::test]
     async fn test_spawning_impl() {
-        let result = spawning(&implementation::Impl::new(())).await;
+        let result = spawning(&Impl::new(())).await;
         assert_eq!(2, result);
     }
 
@@ -505,6 +505,63 @@ mod entrait_for_trait_unimock {
     }
 }
 
+mod impl_block_api {
+    use entrait::*;
+    use unimock::*;
+
+    #[entrait(FetchRow, mock_api=FetchRowMock)]
+    fn fetch_row(deps: &impl std::any::Any, id: u32) -> String {
+        format!("row{id}")
+    }
+
+    #[entrait(RepositoryImpl, delegate_by = DelegateRepository)]
+    pub trait Repository {
+        fn fetch(&self, id: u32) -> String;
+        fn count(&self) -> u32;
+    }
+
+    pub struct SqlRepository;
+
+    #[entrait(SqlRepositoryApi, mock_api=SqlRepositoryMock)]
+    impl RepositoryImpl for SqlRepository {
+        fn fetch(deps: &impl FetchRow, id: u32) -> String {
+            deps.fetch_row(id).to_uppercase()
+        }
+
+        fn count<D>(_: &D) -> u32 {
+            1
+        }
+    }
+
+    #[test]
+    fn impl_block_can_be_unmocked_with_mocked_deps() {
+        let deps = Unimock::new((
+            SqlRepositoryMock::fetch.next_call(matching!(42)).unmocked(),
+            FetchRowMock
+                .next_call(matching!(42))
+                .returns("mocked".to_string()),
+        ));
+
+        assert_eq!("MOCKED", SqlRepositoryApi::fetch(&deps, 42));
+    }
+
+    #[test]
+    fn impl_block_api_can_be_mocked() {
+        let deps = Unimock::new(
+            SqlRepositoryMock::count
+                .each_call(matching!())
+                .returns(5_u32),
+        );
+
+        assert_eq!(5, SqlRepositoryApi::count(&deps));
+    }
+
+    #[test]
+    fn impl_block_api_is_implemented_for_impl() {
+        assert_eq!("ROW1", SqlRepositoryApi::fetch(&Impl::new(()), 1));
+    }
+}
+
 mod naming_conflict_between_fn_and_param {
     use entrait::*;
 
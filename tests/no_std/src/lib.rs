@@ -0,0 +1,144 @@
+//! Entrait used for abstracting over hardware, without the standard library.
+//!
+//! This crate depends on entrait with `default-features = false`,
+//! so it fails to compile if entrait or the code it generates needs `std`.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use core::time::Duration;
+use entrait::runtime::{BoxFuture, Runtime};
+use entrait::*;
+
+#[entrait]
+pub trait OutputPin {
+    fn set_high(&self, high: bool);
+    fn is_high(&self) -> bool;
+}
+
+#[entrait(delegate_by = ref)]
+pub trait Adc: 'static {
+    fn read(&self, channel: u8) -> u16;
+}
+
+#[entrait(pub Toggle)]
+fn toggle(deps: &impl OutputPin) -> bool {
+    let high = !deps.is_high();
+    deps.set_high(high);
+    high
+}
+
+#[entrait(pub Sensors)]
+pub mod sensors {
+    use super::*;
+
+    pub fn read_millivolts(deps: &impl Adc, channel: u8) -> u32 {
+        u32::from(deps.read(channel)) * 3300 / 4096
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RadioError {
+    Busy,
+}
+
+#[entrait(box_future)]
+pub trait Radio {
+    async fn send(&self, byte: u8) -> Result<(), RadioError>;
+}
+
+#[entrait(pub Transmit, box_future, retry(max = 3, backoff = "fixed", delay_ms = 1))]
+async fn transmit(deps: &impl Radio, byte: u8) -> Result<(), RadioError> {
+    deps.send(byte).await
+}
+
+/// A runtime for a single-threaded executor that polls without waiting.
+pub struct BusyWait;
+
+impl Runtime for BusyWait {
+    fn sleep(&self, _: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(async {})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+    use entrait::runtime::HasRuntime;
+
+    struct Board {
+        led: AtomicBool,
+        adc: FixedAdc,
+        radio_failures: AtomicU32,
+    }
+
+    struct FixedAdc(u16);
+
+    impl Adc for FixedAdc {
+        fn read(&self, _: u8) -> u16 {
+            self.0
+        }
+    }
+
+    impl OutputPin for Board {
+        fn set_high(&self, high: bool) {
+            self.led.store(high, Ordering::SeqCst);
+        }
+
+        fn is_high(&self) -> bool {
+            self.led.load(Ordering::SeqCst)
+        }
+    }
+
+    impl AsRef<dyn Adc> for Board {
+        fn as_ref(&self) -> &dyn Adc {
+            &self.adc
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Radio for Board {
+        async fn send(&self, _: u8) -> Result<(), RadioError> {
+            match self.radio_failures.fetch_sub(1, Ordering::SeqCst) {
+                0 => Ok(()),
+                _ => Err(RadioError::Busy),
+            }
+        }
+    }
+
+    impl HasRuntime for Board {
+        fn entrait_runtime(&self) -> &dyn Runtime {
+            &BusyWait
+        }
+    }
+
+    fn board(radio_failures: u32) -> Impl<Board> {
+        Impl::new(Board {
+            led: AtomicBool::new(false),
+            adc: FixedAdc(2048),
+            radio_failures: AtomicU32::new(radio_failures),
+        })
+    }
+
+    #[test]
+    fn toggles_pin() {
+        let board = board(0);
+
+        assert!(board.toggle());
+        assert!(!board.toggle());
+    }
+
+    #[test]
+    fn converts_adc_reading() {
+        assert_eq!(1650, board(0).read_millivolts(0));
+    }
+
+    #[tokio::test]
+    async fn retries_transmission() {
+        assert_eq!(Ok(()), board(2).transmit(42).await);
+    }
+}